@@ -1,5 +1,7 @@
 pub mod parsing_json;
+pub mod tokenizing_json;
 
 criterion::criterion_main! {
     parsing_json::benches,
+    tokenizing_json::benches,
 }
@@ -43,6 +43,18 @@ fn benchmark_parsing_json(criterion: &mut Criterion) {
                     );
                 },
             );
+            group.bench_function(
+                format!("{} {}(1) Events", name, if lalr { "LALR" } else { "LR" }),
+                |b| {
+                    b.iter_batched(
+                        || tokens.clone(),
+                        |tokens| {
+                            criterion::black_box(parser.events(tokens.into_iter().map(Ok)).count());
+                        },
+                        BatchSize::PerIteration,
+                    );
+                },
+            );
         }
     }
 }
@@ -0,0 +1,134 @@
+use {
+    criterion::{
+        Criterion,
+        Throughput,
+        criterion_group,
+    },
+    dotlr::{
+        Grammar,
+        Parser,
+        Span,
+        Spanned,
+        Token,
+    },
+};
+
+/// Counts the new lines in a slice, along with the offset after the last new line.
+fn count_new_lines(slice: &str) -> (usize, Option<usize>) {
+    let mut offset_after_newline = None;
+
+    let mut count = 0;
+    for (offset, byte) in slice.bytes().enumerate() {
+        if byte == b'\n' {
+            offset_after_newline = Some(offset + 1);
+            count += 1;
+        }
+    }
+
+    (count, offset_after_newline)
+}
+
+/// Tokenizes an input by trying each token of the grammar one by one at each position,
+/// which is how the tokenizer worked before compiling the tokens into a single DFA.
+fn tokenize_one_by_one<'i>(grammar: &Grammar, input: &'i str) -> Vec<(Spanned<Token>, &'i str)> {
+    let mut tokens: Vec<(Spanned<Token>, &'i str)> = Vec::new();
+
+    let mut ordered_constant_tokens = grammar.constant_tokens().iter().collect::<Vec<_>>();
+    ordered_constant_tokens.sort_by_key(|token| token.len());
+
+    let mut remaining_input = input.trim_start();
+    let mut offset = input.len() - remaining_input.len();
+    let (initial_new_lines, initial_newline_offset) = count_new_lines(&input[..offset]);
+    let mut line = initial_new_lines + 1;
+    let mut last_newline_offset = initial_newline_offset.unwrap_or(0);
+    let mut column = input[last_newline_offset..offset].chars().count() + 1;
+    while !remaining_input.is_empty() {
+        let mut matching_token = None;
+        let mut matching_slice = "";
+
+        for token in ordered_constant_tokens.iter().rev() {
+            if remaining_input.starts_with(token.as_str()) {
+                matching_token = Some(Token::Constant((*token).clone()));
+                matching_slice = &remaining_input[..token.len()];
+                break;
+            }
+        }
+
+        for (regex_token, regex) in grammar.regular_expressions() {
+            if let Some(match_info) = regex.find(remaining_input) {
+                if match_info.len() > matching_slice.len() {
+                    matching_token = Some(Token::Regex(regex_token.clone()));
+                    matching_slice = &remaining_input[..match_info.end()];
+                }
+            }
+        }
+
+        let token = Spanned::new(
+            matching_token.unwrap(),
+            Span { offset, length: matching_slice.len(), line, column },
+        );
+
+        let (slice_lines, slice_newline_offset) = count_new_lines(matching_slice);
+        line += slice_lines;
+
+        if let Some(slice_newline_offset) = slice_newline_offset {
+            last_newline_offset = offset + slice_newline_offset
+        }
+
+        tokens.push((token, matching_slice));
+        remaining_input = remaining_input[matching_slice.len()..].trim_start();
+
+        let old_offset = offset;
+        offset = input.len() - remaining_input.len();
+        let whitespace = &input[old_offset..offset];
+        let (whitespace_lines, whitespace_newline_offset) = count_new_lines(whitespace);
+        line += whitespace_lines;
+
+        if let Some(whitespace_newline_offset) = whitespace_newline_offset {
+            last_newline_offset = old_offset + whitespace_newline_offset;
+        }
+        column = input[last_newline_offset..offset].chars().count() + 1;
+    }
+    let eof = Spanned::new(Token::Eof, Span { offset, length: 0, line, column });
+    tokens.push((eof, "\0"));
+
+    tokens
+}
+
+fn benchmark_tokenizing_json(criterion: &mut Criterion) {
+    let grammars = [
+        ("Simple", include_str!("../assets/grammars/correct/json.lr")),
+        ("Optimized", include_str!("../assets/grammars/correct/json.optimized.lr")),
+    ];
+
+    let mut group = criterion.benchmark_group("Tokenizing JSON");
+
+    let input = include_str!("../assets/data/large.json");
+    group.throughput(Throughput::Bytes(input.len() as u64));
+
+    for (name, definition) in grammars {
+        let grammar = Grammar::parse(definition).unwrap();
+        let parser = Parser::lalr(grammar.clone()).unwrap();
+
+        group.bench_function(format!("{} One By One", name), |b| {
+            b.iter(|| criterion::black_box(tokenize_one_by_one(&grammar, input)));
+        });
+        group.bench_function(format!("{} DFA", name), |b| {
+            b.iter(|| criterion::black_box(parser.tokenize(input)).unwrap());
+        });
+    }
+}
+
+criterion_group! {
+    name =
+        benches;
+
+    config =
+        Criterion::default()
+            .sample_size(30)
+            .confidence_level(0.95)
+            .with_plots();
+
+    targets =
+        benchmark_tokenizing_json,
+}
@@ -5,14 +5,18 @@ import {
 } from "./pkg/dotlr";
 import {
   ActionTable,
+  AtomicPattern,
   Automaton,
+  Completion,
   FirstTable,
   FollowTable,
   GoToTable,
   GrammarError,
   LALR1ParserOfGrammar,
+  LexerMode,
   LR1ParserOfGrammar,
   ParserError,
+  Precedence,
   ParsingError,
   ParsingTables,
   Rule,
@@ -34,6 +38,12 @@ export class Grammar<
     constant_tokens: null as T[] | null,
     start_symbol: null as NT | null,
     regex_tokens: null as Map<R, string> | null,
+    repetition_symbols: null as NT[] | null,
+    precedences: null as Map<AtomicPattern<Token<T, R>>, Precedence> | null,
+    lexer_modes: null as Map<string, LexerMode<Token<T, R>>> | null,
+    priorities: null as Map<R, number> | null,
+    keywords: null as T[] | null,
+    reserved_words: null as Map<R, T[]> | null,
     productions: null as Rule<Token<T, R>>[] | null,
     stringify: null as string | null,
   };
@@ -79,6 +89,41 @@ export class Grammar<
       this.grammar.regular_expressions_wasm() as Map<R, string>);
   }
 
+  getRepetitionSymbols() {
+    return (this.cache.repetition_symbols ??=
+      this.grammar.repetition_symbols_wasm() as NT[]);
+  }
+
+  getPrecedences() {
+    return (this.cache.precedences ??= this.grammar.precedences_wasm() as Map<
+      AtomicPattern<Token<T, R>>,
+      Precedence
+    >);
+  }
+
+  getLexerModes() {
+    return (this.cache.lexer_modes ??= this.grammar.lexer_modes_wasm() as Map<
+      string,
+      LexerMode<Token<T, R>>
+    >);
+  }
+
+  getPriorities() {
+    return (this.cache.priorities ??= this.grammar.priorities_wasm() as Map<
+      R,
+      number
+    >);
+  }
+
+  getKeywords() {
+    return (this.cache.keywords ??= this.grammar.keywords_wasm() as T[]);
+  }
+
+  getReservedWords() {
+    return (this.cache.reserved_words ??=
+      this.grammar.reserved_words_wasm() as Map<R, T[]>);
+  }
+
   stringify() {
     return (this.cache.stringify ??= this.grammar.to_string_wasm() as string);
   }
@@ -163,6 +208,28 @@ export class Parser<
     }
   }
 
+  complete(prefix: string) {
+    try {
+      return Ok(this.parser.complete_wasm(prefix) as Completion<T, R>[]);
+    } catch (e) {
+      return Err(e as ParsingError);
+    }
+  }
+
+  tokenizeWithRecovery(input: string) {
+    const [tokens, errors] = this.parser.tokenize_with_recovery_wasm(input) as [
+      [Spanned<Token<T, R>>, string][],
+      ParsingError[],
+    ];
+    return {
+      tokens: tokens.map(([token, slice]) => ({
+        token,
+        slice,
+      })),
+      errors,
+    };
+  }
+
   trace(input: string) {
     try {
       const [trace, tree] = this.parser.trace_wasm(input) as [
@@ -182,7 +249,7 @@ export class Parser<
 // this function tries to recover the serialized parser into the actual parser
 function mapParserError(error: WasmParserError, kind: "lalr1" | "lr1") {
   const serialized = error.serialize() as ParserError;
-  if (serialized.type === "Conflict") {
+  if (serialized.type === "Conflicts") {
     serialized.value.parser =
       kind === "lalr1"
         ? // @ts-expect-error private constructor
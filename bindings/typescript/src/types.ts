@@ -3,6 +3,28 @@ import { Grammar, LALR1Parser, LR1Parser, Parser } from "./index";
 export type Rule<T extends Token = Token> = {
   symbol: string;
   pattern: AtomicPattern<T>[];
+  precedence: AtomicPattern<T> | null;
+};
+
+//prettier-ignore
+export type LexerModeTransition = {
+    type: 'Push',
+    value: string
+} | {
+    type: 'Pop'
+}
+
+export type LexerMode<T extends Token = Token> = {
+  tokens: T[];
+  ignored_regular_expressions: string[];
+  transitions: Map<T, LexerModeTransition>;
+};
+
+export type Associativity = "Left" | "Right" | "NonAssociative";
+
+export type Precedence = {
+  level: number;
+  associativity: Associativity;
 };
 
 //TODO not sure how to type Symbol
@@ -38,12 +60,27 @@ export type Token<C = string, R = string> = {
 } | {
     type: 'Regex',
     value: R
+} | {
+    type: 'Indent'
+} | {
+    type: 'Dedent'
+} | {
+    type: 'Newline'
 } | {
     type: 'Eof'
 } | {
     type: 'Empty'
 }
 
+//prettier-ignore
+export type Completion<C = string, R = string> = {
+    type: 'Constant'
+    value: C
+} | {
+    type: 'Regex',
+    value: R
+}
+
 //prettier-ignore
 export type GrammarError = {
     type: "UnexpectedToken",
@@ -83,20 +120,53 @@ export type ParserError<P extends Parser = Parser> = {
         rule: Rule<TokenOfParser<P>>
     }
 } | {
-    type: "Conflict",
+    type: "UndefinedPrecedence",
+    value: {
+        precedence: AtomicPattern<TokenOfParser<P>>
+        rule: Rule<TokenOfParser<P>>
+    }
+} | {
+    type: "UndefinedLexerMode",
+    value: {
+        mode: string
+    }
+} | {
+    type: "Conflicts",
     value: {
         parser: P
-        state: number,
-        token: TokenOfParser<P>
+        conflicts: Conflict<TokenOfParser<P>>[]
     }
 }
 
+export type ConflictKind = "ShiftReduce" | "ReduceReduce";
+
+export type Conflict<T extends Token = Token> = {
+  state: number;
+  token: T;
+  kind: ConflictKind;
+  actions: Action[];
+  items: Item<T>[];
+  rules: Rule<T>[];
+};
+
 //prettier-ignore
 export type ParsingError<T extends Token = Token> = {
     type: "UnknownToken",
     value: {
         token: string
         span: Span
+        mode: string | null
+    }
+} | {
+    type: "InconsistentDedent",
+    value: {
+        span: Span
+    }
+} | {
+    type: "UndefinedToken",
+    value: {
+        token: T
+        span: Span
     }
 } | {
     type: "UnexpectedToken"
@@ -148,10 +218,7 @@ export type Action = {
         rule_index: number
     }
 } | {
-    type: 'Accept',
-    value: {
-        rule_index: number
-    }
+    type: 'Accept'
 }
 export type Span = {
   offset: number;
@@ -173,12 +240,21 @@ export type GoToTable<NT extends string = string> = Map<NT, number>[];
 
 export type ActionTable<T extends Token = Token> = Map<T, Action[]>[];
 
+export type ResolvedConflict<T extends Token = Token> = {
+  state: number;
+  token: T;
+  shift: Action;
+  reduce: Action;
+  chosen_action: Action | null;
+};
+
 export type ParsingTables<
   NT extends string = string,
   T extends Token = Token,
 > = {
   action_table: ActionTable<T>;
   goto_table: GoToTable<NT>;
+  resolved_conflicts: ResolvedConflict<T>[];
 };
 
 export type TokenOfParser<P extends Parser> =
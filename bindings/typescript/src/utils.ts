@@ -14,6 +14,9 @@ export function stringifyToken(token: Token, noApostrophes = false) {
   if (token.type === "Eof") return "$";
   if (token.type === "Empty") return "ε";
   if (token.type === "Regex") return `%${token.value}`;
+  if (token.type === "Indent") return "%indent";
+  if (token.type === "Dedent") return "%dedent";
+  if (token.type === "Newline") return "%newline";
   if (token.type === "Constant")
     return noApostrophes ? token.value : `'${token.value}'`;
   return "";
@@ -36,6 +39,8 @@ export function stringifyItem(item: Item, noApostrophes = false) {
 
 export function stringifyRule(rule: Rule, noApostrophes = false) {
   const children = rule.pattern.map((a) => stringifyAtom(a, noApostrophes));
+  if (rule.precedence)
+    children.push(`%prec ${stringifyAtom(rule.precedence, noApostrophes)}`);
   return `${rule.symbol} -> ${children.join(" ")}`;
 }
 
@@ -45,7 +50,7 @@ export function stringifyLookahead(item: Token[], noApostrophes = false) {
 }
 
 export function stringifyAction(action: Action) {
-  if (action.type === "Accept") return `a${action.value.rule_index + 1}`;
+  if (action.type === "Accept") return "acc";
   if (action.type === "Reduce") return `r${action.value.rule_index + 1}`;
   if (action.type === "Shift") return `s${action.value.next_state}`;
   return "";
@@ -59,7 +64,7 @@ export function stringifyActionVerbose(
   if (action.type === "Shift") {
     return `Shift ${action.value.next_state}`;
   } else if (action.type === "Accept") {
-    return `Accept ${action.value.rule_index + 1} (${stringifyRule(rules[action.value.rule_index], noApostrophes)})`;
+    return "Accept";
   } else if (action.type === "Reduce") {
     return `Reduce ${action.value.rule_index + 1} (${stringifyRule(rules[action.value.rule_index], noApostrophes)})`;
   }
@@ -88,7 +93,8 @@ export function stringifyTree(
   if (tree.type === "Terminal") {
     const { token, slice } = tree.value;
     if (token.type !== "Eof" && token.type !== "Empty") {
-      result += `${indent}${linePrefix}${token.value} [${slice}]\n`;
+      const name = "value" in token ? token.value : stringifyToken(token);
+      result += `${indent}${linePrefix}${name} [${slice}]\n`;
     }
   } else {
     const { symbol, pattern } = tree.value;
@@ -126,7 +132,12 @@ export function stringifyParsingError(error: ParsingError) {
   if (error.type === "UnexpectedEof") {
     return `Unexpected end of input at ${stringifySpan(error.value.span)}, expected one of:\n${error.value.expected.map(maybeToken).join(", ")}`;
   } else if (error.type === "UnknownToken") {
-    return `Unknown token at ${stringifySpan(error.value.span)}: ${error.value.token}`;
+    const mode = error.value.mode ? ` in ${error.value.mode} mode` : "";
+    return `Unknown token at ${stringifySpan(error.value.span)}${mode}: ${error.value.token}`;
+  } else if (error.type === "InconsistentDedent") {
+    return `Inconsistent dedent at ${stringifySpan(error.value.span)}`;
+  } else if (error.type === "UndefinedToken") {
+    return `Undefined token at ${stringifySpan(error.value.span)}: ${stringifyToken(error.value.token)}`;
   } else if (error.type === "UnexpectedToken") {
     return `Unexpected token at ${stringifySpan(error.value.span)}, expected one of:\n${error.value.expected.map(maybeToken).join(", ")}`;
   }
@@ -139,8 +150,17 @@ export function stringifyParserError(error: ParserError) {
     return `Undefined symbol: ${error.value.symbol}`;
   if (error.type === "UndefinedRegexToken")
     return `Undefined regex token: ${error.value.regex_token}`;
-  if (error.type === "Conflict")
-    return `Conflict in state ${error.value.state} on token ${stringifyToken(error.value.token)}`;
+  if (error.type === "UndefinedLexerMode")
+    return `Undefined lexer mode: ${error.value.mode}`;
+  if (error.type === "UndefinedPrecedence")
+    return `Undefined precedence: ${stringifyAtom(error.value.precedence)}`;
+  if (error.type === "Conflicts")
+    return error.value.conflicts
+      .map(
+        (conflict) =>
+          `Conflict in state ${conflict.state} on token ${stringifyToken(conflict.token)}`,
+      )
+      .join("\n");
   return "Unknown error";
 }
 
@@ -1,16 +1,22 @@
 use {
     colored::Colorize,
     dotlr::{
+        AtomicPattern,
         Grammar,
         Parser,
+        Rule,
+        SemanticActions,
+        Spanned,
         Token,
-        Tree,
     },
     rustyline::{
         DefaultEditor,
         error::ReadlineError,
     },
-    std::process::ExitCode,
+    std::{
+        ops::Deref,
+        process::ExitCode,
+    },
 };
 
 fn main() -> ExitCode {
@@ -70,9 +76,9 @@ fn calculate(parser: &Parser, input: &str) -> ExitCode {
             return ExitCode::FAILURE;
         },
     };
-    match parser.parse(tokens) {
-        Ok(parse_tree) => {
-            println!("{}", evaluate(parse_tree));
+    match parser.parse_with(tokens, &mut Evaluator) {
+        Ok(result) => {
+            println!("{}", result);
             ExitCode::SUCCESS
         },
         Err(error) => {
@@ -82,96 +88,38 @@ fn calculate(parser: &Parser, input: &str) -> ExitCode {
     }
 }
 
-fn evaluate(tree: Tree<'_>) -> f64 {
-    match tree {
-        Tree::Terminal { token, slice, .. } => {
-            match token {
-                Token::Regex(regex_token) => {
-                    match regex_token.as_str() {
-                        "f" => slice.parse().unwrap(),
-                        _ => unreachable!(),
-                    }
-                },
-                Token::Constant(_) | Token::Eof | Token::Empty => {
-                    unreachable!();
-                },
-            }
-        },
-        Tree::NonTerminal { symbol, pattern } => {
-            let mut pattern = pattern.into_iter();
-            match symbol.as_str() {
-                "Expr" => {
-                    if pattern.len() == 1 {
-                        evaluate(pattern.next().unwrap())
-                    } else {
-                        assert_eq!(pattern.len(), 3);
-                        let lhs = pattern.next().unwrap();
-                        let operation = pattern.next().unwrap();
-                        let rhs = pattern.next().unwrap();
-                        match operation {
-                            Tree::Terminal { slice, .. } => {
-                                match slice {
-                                    "+" => evaluate(lhs) + evaluate(rhs),
-                                    "-" => evaluate(lhs) - evaluate(rhs),
-                                    _ => unreachable!(),
-                                }
-                            },
-                            _ => unreachable!(),
-                        }
-                    }
-                },
-                "Factor" => {
-                    if pattern.len() == 1 {
-                        evaluate(pattern.next().unwrap())
-                    } else {
-                        assert_eq!(pattern.len(), 3);
-                        let lhs = pattern.next().unwrap();
-                        let operation = pattern.next().unwrap();
-                        let rhs = pattern.next().unwrap();
-                        match operation {
-                            Tree::Terminal { slice, .. } => {
-                                match slice {
-                                    "*" => evaluate(lhs) * evaluate(rhs),
-                                    "/" => evaluate(lhs) / evaluate(rhs),
-                                    _ => unreachable!(),
-                                }
-                            },
-                            _ => unreachable!(),
-                        }
-                    }
-                },
-                "Exponent" => {
-                    if pattern.len() == 1 {
-                        evaluate(pattern.next().unwrap())
-                    } else {
-                        assert_eq!(pattern.len(), 3);
-                        let lhs = pattern.next().unwrap();
-                        let operation = pattern.next().unwrap();
-                        let rhs = pattern.next().unwrap();
-                        match operation {
-                            Tree::Terminal { slice, .. } => {
-                                if slice == "^" {
-                                    evaluate(lhs).powf(evaluate(rhs))
-                                } else {
-                                    unreachable!()
-                                }
-                            },
-                            _ => unreachable!(),
-                        }
-                    }
-                },
-                "Term" => {
-                    if pattern.len() == 1 {
-                        evaluate(pattern.next().unwrap())
-                    } else {
-                        assert_eq!(pattern.len(), 3);
-                        evaluate(pattern.nth(1).unwrap())
-                    }
-                },
-                _ => {
-                    unreachable!();
-                },
-            }
-        },
+struct Evaluator;
+
+impl<'i> SemanticActions<'i> for Evaluator {
+    type Value = f64;
+
+    fn shift(&mut self, token: Spanned<Token>, slice: &'i str) -> f64 {
+        match token.deref() {
+            Token::Regex(regex_token) if regex_token.as_str() == "f" => slice.parse().unwrap(),
+            // Values of the other tokens (e.g., operators and parentheses) are not used.
+            _ => f64::NAN,
+        }
+    }
+
+    fn reduce(&mut self, _rule_index: usize, rule: &Rule, values: Vec<f64>) -> f64 {
+        match (rule.pattern(), values.as_slice()) {
+            // Expr -> Expr ('+' | '-') Factor, Factor -> Factor ('*' | '/') Exponent,
+            // Exponent -> Term '^' Exponent
+            ([_, AtomicPattern::Token(Token::Constant(operator)), _], [lhs, _, rhs]) => {
+                match operator.as_str() {
+                    "+" => lhs + rhs,
+                    "-" => lhs - rhs,
+                    "*" => lhs * rhs,
+                    "/" => lhs / rhs,
+                    "^" => lhs.powf(*rhs),
+                    _ => panic!("rule {} is not supported", rule),
+                }
+            },
+            // Term -> '(' Expr ')'
+            ([_, AtomicPattern::Symbol(_), _], [_, value, _]) => *value,
+            // Expr -> Factor, Factor -> Exponent, Exponent -> Term, Term -> %f
+            (_, [value]) => *value,
+            _ => panic!("rule {} is not supported", rule),
+        }
     }
 }
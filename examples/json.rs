@@ -1,15 +1,23 @@
 use {
     dotlr::{
+        AtomicPattern,
         Grammar,
         Parser,
+        Rule,
+        SemanticActions,
+        Spanned,
         Token,
-        Tree,
     },
     indexmap::IndexMap,
+    std::{
+        mem::take,
+        ops::Deref,
+    },
 };
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub enum Value {
+    #[default]
     Null,
     Bool(bool),
     Number(f64),
@@ -18,123 +26,69 @@ pub enum Value {
     Object(IndexMap<String, Value>),
 }
 
-impl From<Tree<'_>> for Value {
-    fn from(tree: Tree) -> Value {
-        match tree {
-            Tree::Terminal { token, slice, .. } => {
-                match token {
-                    Token::Constant(constant_token) => {
-                        match constant_token.as_str() {
-                            "null" => Value::Null,
-                            "true" => Value::Bool(true),
-                            "false" => Value::Bool(false),
-                            _ => unreachable!(),
-                        }
-                    },
-                    Token::Regex(regex_token) => {
-                        match regex_token.as_str() {
-                            "f" => Value::Number(slice.parse().unwrap()),
-                            "s" => Value::String(slice.trim_matches('"').to_owned()),
-                            _ => unreachable!(),
-                        }
-                    },
-                    Token::Eof | Token::Empty => {
-                        unreachable!();
-                    },
-                }
-            },
-            Tree::NonTerminal { symbol, pattern } => {
-                let mut pattern = pattern.into_iter();
-                match symbol.as_str() {
-                    "Json" | "Value" | "Null" | "Boolean" | "Number" | "String" => {
-                        assert_eq!(pattern.len(), 1);
-                        Value::from(pattern.next().unwrap())
-                    },
-
-                    "Array" => {
-                        if pattern.len() == 2 {
-                            // Array -> '[' ']'
-                            Value::Array(Vec::new())
-                        } else {
-                            // Array -> '[' ArrayElements ']'
-                            fn collect(elements: Tree, values: &mut Vec<Value>) {
-                                match elements {
-                                    Tree::NonTerminal { symbol, pattern }
-                                        if symbol.as_str() == "ArrayElements" =>
-                                    {
-                                        let mut pattern = pattern.into_iter();
-                                        if pattern.len() == 1 {
-                                            // ArrayElements -> Value
-                                            values.push(Value::from(pattern.next().unwrap()));
-                                        } else {
-                                            // ArrayElements -> ArrayElements ',' Value
-                                            assert_eq!(pattern.len(), 3);
-                                            collect(pattern.next().unwrap(), values);
-                                            let _comma = pattern.next().unwrap();
-                                            values.push(Value::from(pattern.next().unwrap()));
-                                        }
-                                    },
-                                    _ => {
-                                        unreachable!();
-                                    },
-                                }
-                            }
-
-                            let mut values = Vec::new();
-                            collect(pattern.nth(1).unwrap(), &mut values);
-
-                            Value::Array(values)
-                        }
-                    },
-                    "Object" => {
-                        if pattern.len() == 2 {
-                            // Object -> '{' '}'
-                            Value::Object(IndexMap::new())
-                        } else {
-                            // Object -> '{' ObjectElements '}'
-                            fn collect(elements: Tree, entries: &mut IndexMap<String, Value>) {
-                                match elements {
-                                    Tree::NonTerminal { symbol, pattern }
-                                        if symbol.as_str() == "ObjectElements" =>
-                                    {
-                                        let mut pattern = pattern.into_iter();
+struct Builder;
 
-                                        if pattern.len() == 5 {
-                                            // ObjectElements -> ObjectElements ',' String ':' Value
-                                            collect(pattern.next().unwrap(), entries);
-                                            let _comma = pattern.next().unwrap();
-                                        }
+impl<'i> SemanticActions<'i> for Builder {
+    type Value = Value;
 
-                                        // ObjectElements -> String ':' Value
-                                        assert_eq!(pattern.len(), 3);
-
-                                        let key = match Value::from(pattern.next().unwrap()) {
-                                            Value::String(key) => key,
-                                            _ => unreachable!(),
-                                        };
-                                        let _colon = pattern.next().unwrap();
-                                        let value = Value::from(pattern.next().unwrap());
-
-                                        entries.insert(key, value);
-                                    },
-                                    _ => {
-                                        unreachable!();
-                                    },
-                                }
-                            }
-
-                            let mut entries = IndexMap::new();
-                            collect(pattern.nth(1).unwrap(), &mut entries);
+    fn shift(&mut self, token: Spanned<Token>, slice: &'i str) -> Value {
+        match token.deref() {
+            Token::Regex(regex_token) if regex_token.as_str() == "f" => {
+                Value::Number(slice.parse().unwrap())
+            },
+            Token::Regex(regex_token) if regex_token.as_str() == "s" => {
+                Value::String(slice.trim_matches('"').to_owned())
+            },
+            // Values of the other tokens (e.g., keywords and punctuation) are not used.
+            _ => Value::Null,
+        }
+    }
 
-                            Value::Object(entries)
-                        }
-                    },
+    fn reduce(&mut self, _rule_index: usize, rule: &Rule, mut values: Vec<Value>) -> Value {
+        match (rule.symbol().as_str(), values.as_mut_slice()) {
+            // Json -> Value, Value -> Null | Boolean | Number | String | Array | Object
+            ("Json" | "Value", [value]) => take(value),
+
+            // Null -> 'null'
+            ("Null", [_]) => Value::Null,
+            // Boolean -> 'true' | 'false'
+            ("Boolean", [_]) => {
+                Value::Bool(matches!(
+                    rule.pattern(),
+                    [AtomicPattern::Token(Token::Constant(constant_token))]
+                        if constant_token.as_str() == "true"
+                ))
+            },
+            // Number -> %f, String -> %s
+            ("Number" | "String", [value]) => take(value),
+
+            // Array -> '[' ']'
+            ("Array", [_, _]) => Value::Array(Vec::new()),
+            // Array -> '[' ArrayElements ']'
+            ("Array", [_, elements, _]) => take(elements),
+            // ArrayElements -> Value
+            ("ArrayElements", [value]) => Value::Array(vec![take(value)]),
+            // ArrayElements -> ArrayElements ',' Value
+            ("ArrayElements", [Value::Array(elements), _, value]) => {
+                elements.push(take(value));
+                Value::Array(take(elements))
+            },
 
-                    _ => {
-                        unreachable!();
-                    },
-                }
+            // Object -> '{' '}'
+            ("Object", [_, _]) => Value::Object(IndexMap::new()),
+            // Object -> '{' ObjectElements '}'
+            ("Object", [_, entries, _]) => take(entries),
+            // ObjectElements -> String ':' Value
+            ("ObjectElements", [Value::String(key), _, value]) => {
+                Value::Object(IndexMap::from([(take(key), take(value))]))
+            },
+            // ObjectElements -> ObjectElements ',' String ':' Value
+            ("ObjectElements", [Value::Object(entries), _, Value::String(key), _, value]) => {
+                entries.insert(take(key), take(value));
+                Value::Object(take(entries))
             },
+
+            _ => panic!("rule {} is not supported", rule),
         }
     }
 }
@@ -148,9 +102,6 @@ fn main() {
     let input = include_str!("../assets/data/sample.json");
     let tokens = parser.tokenize(input).expect("tokenization failed");
 
-    let parse_tree = parser.parse(tokens).expect("parsing failed");
-    parse_tree.dump();
-
-    let json = Value::from(parse_tree);
-    println!("\n{:#?}", json);
+    let json = parser.parse_with(tokens, &mut Builder).expect("parsing failed");
+    println!("{:#?}", json);
 }
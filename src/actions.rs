@@ -0,0 +1,23 @@
+use crate::prelude::*;
+
+
+/// Semantic actions of a parse, which build the values of the input while it's parsed
+/// (e.g., the result of an expression or a user defined syntax tree) instead of a [Tree].
+///
+/// Values of the tokens are created as they are shifted, and values of the symbols are created
+/// from the values of their patterns as they are reduced, so the whole input is processed in a
+/// single pass using [Parser::parse_with].
+pub trait SemanticActions<'i> {
+    /// Type of the values of the tokens and symbols.
+    type Value;
+
+    /// Creates the value of a token that is shifted, along with its corresponding input slice.
+    fn shift(&mut self, token: Spanned<Token>, slice: &'i str) -> Self::Value;
+
+    /// Creates the value of a rule that is reduced, from the values of its pattern in order.
+    ///
+    /// Values are empty for empty patterns (e.g., `Symbol -> ε`). Repetitions are reduced
+    /// using their left recursive rules (e.g., `Value* -> Value* Value`), which can be
+    /// identified using [Grammar::repetition_symbols].
+    fn reduce(&mut self, rule_index: usize, rule: &Rule, values: Vec<Self::Value>) -> Self::Value;
+}
@@ -1,7 +1,9 @@
 use crate::prelude::*;
 
 
-/// Item of a state of an LR(1) or an LALR(1) automaton.
+/// Item of a state of an LR(0), an LR(1) or an LALR(1) automaton.
+///
+/// Items of an LR(0) automaton don't have lookaheads.
 #[cfg_attr(feature = "serde", derive(Serialize))]
 #[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -60,7 +62,7 @@ impl Display for Item {
 }
 
 
-/// State of an LR(1) or an LALR(1) automaton.
+/// State of an LR(0), an LR(1) or an LALR(1) automaton.
 #[derive(Clone, Debug, Default, Eq)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
 #[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
@@ -100,7 +102,9 @@ impl State {
 
 impl State {
     /// Computes the closure of the state.
-    fn compute_closure(&mut self, grammar: &Grammar, first_table: &FirstTable) {
+    ///
+    /// Lookaheads of the new items are only computed if the first table is provided.
+    fn compute_closure(&mut self, grammar: &Grammar, first_table: Option<&FirstTable>) {
         loop {
             let mut new_items = vec![];
             for item in self.items.iter() {
@@ -108,16 +112,18 @@ impl State {
                     continue;
                 }
                 if let AtomicPattern::Symbol(symbol) = &item.rule.pattern()[item.dot] {
-                    let lookahead = if item.dot == item.rule.pattern().len() - 1 {
-                        item.lookahead.clone()
-                    } else {
-                        let next_atomic_pattern = &item.rule.pattern()[item.dot + 1];
-                        match next_atomic_pattern {
-                            AtomicPattern::Symbol(symbol) => {
-                                first_table.get(symbol).cloned().unwrap_or_default()
-                            },
-                            AtomicPattern::Token(token) => IndexSet::from([token.clone()]),
-                        }
+                    let lookahead = match first_table {
+                        None => IndexSet::new(),
+                        Some(first_table) => {
+                            // If the rest of the pattern can be empty,
+                            // lookahead of the item can follow the symbol as well.
+                            let rest_of_pattern = &item.rule.pattern()[item.dot + 1..];
+                            let mut lookahead = first_table.first_set_of(rest_of_pattern);
+                            if lookahead.shift_remove(&Token::Empty) {
+                                lookahead.extend(item.lookahead.iter().cloned());
+                            }
+                            lookahead
+                        },
                     };
                     for rule in grammar.rules().iter().filter(|rule| rule.symbol() == symbol) {
                         let new_item =
@@ -164,6 +170,11 @@ impl State {
             }
 
             let atomic_pattern_after_dot = &item.rule.pattern()[item.dot];
+            if *atomic_pattern_after_dot == AtomicPattern::Token(Token::Eof) {
+                // End of file is never shifted, it's accepted instead.
+                continue;
+            }
+
             let state_after_transition =
                 transitions.entry(atomic_pattern_after_dot.clone()).or_insert_with(|| {
                     let id = *state_counter;
@@ -184,7 +195,7 @@ impl PartialEq for State {
 }
 
 
-/// LR(1) or LALR(1) automaton of a grammar.
+/// LR(0), LR(1) or LALR(1) automaton of a grammar.
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
 #[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
@@ -196,16 +207,23 @@ pub struct Automaton {
 impl Automaton {
     /// Constructs the LR(1) automaton of a grammar.
     pub fn construct(grammar: &Grammar, first_table: &FirstTable) -> Automaton {
+        Automaton::construct_internal(grammar, Some(first_table))
+    }
+
+    /// Constructs the LR(0) automaton of a grammar.
+    pub fn construct_lr0(grammar: &Grammar) -> Automaton {
+        Automaton::construct_internal(grammar, None)
+    }
+}
+
+impl Automaton {
+    /// Internal construction logic, which omits the lookaheads without a first table.
+    fn construct_internal(grammar: &Grammar, first_table: Option<&FirstTable>) -> Automaton {
+        let lookahead =
+            if first_table.is_some() { IndexSet::from([Token::Eof]) } else { IndexSet::new() };
         let first_state = State {
             id: 0,
-            items: grammar
-                .rules()
-                .iter()
-                .filter(|rule| rule.symbol() == grammar.start_symbol())
-                .map(|rule| {
-                    Item { rule: rule.clone(), dot: 0, lookahead: IndexSet::from([Token::Eof]) }
-                })
-                .collect(),
+            items: smallvec![Item { rule: grammar.augmented_rule(), dot: 0, lookahead }],
             transitions: IndexMap::new(),
         };
 
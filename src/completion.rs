@@ -0,0 +1,66 @@
+use crate::prelude::*;
+
+
+/// Completion of an input prefix, which is a token that can follow the prefix.
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Completion {
+    /// A constant token can follow (e.g., `'true'`).
+    Constant(ConstantToken),
+    /// A regular expression token can follow (e.g., `%f`).
+    Regex(RegexToken),
+}
+
+impl Completion {
+    /// Gets the text of the completion, which is the text of constant tokens (e.g., `true`)
+    /// and the name of regular expression tokens (e.g., `f`).
+    pub fn text(&self) -> &str {
+        match self {
+            Completion::Constant(constant_token) => constant_token.as_str(),
+            Completion::Regex(regex_token) => regex_token.as_str(),
+        }
+    }
+}
+
+impl Display for Completion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Completion::Constant(constant_token) => write!(f, "{}", constant_token),
+            Completion::Regex(regex_token) => write!(f, "{}", regex_token),
+        }
+    }
+}
+
+
+/// Completes an input prefix, by feeding its tokens to a parse session.
+pub(crate) fn complete(parser: &Parser, prefix: &str) -> Result<Vec<Completion>, ParsingError> {
+    let mut session = parser.session();
+    for token in parser.token_stream(prefix) {
+        let (token, slice) = token?;
+
+        // Layout tokens that are synthesized at the end of the prefix are not in the prefix,
+        // since the input may continue after it.
+        if *token == Token::Eof
+            || (token.is_layout() && slice.is_empty() && token.span().offset == prefix.len())
+        {
+            break;
+        }
+
+        session.feed(token, slice)?;
+    }
+
+    let completions = session
+        .expected_tokens()
+        .into_iter()
+        .filter_map(|token| {
+            match token {
+                Token::Constant(constant_token) => Some(Completion::Constant(constant_token)),
+                Token::Regex(regex_token) => Some(Completion::Regex(regex_token)),
+                _ => None,
+            }
+        })
+        .collect();
+    Ok(completions)
+}
@@ -0,0 +1,621 @@
+use crate::prelude::*;
+
+
+/// Maximum number of derivation pairs to explore while trying to unify a counterexample.
+const UNIFICATION_LIMIT: usize = 10_000;
+
+/// Maximum number of expansions to apply while trying to unify a counterexample.
+const UNIFICATION_DEPTH: usize = 8;
+
+/// Maximum number of expansions to apply while bringing the conflict token after the dot.
+const EXPANSION_LIMIT: usize = 16;
+
+
+/// Derivation (e.g., `E -> [ E -> [ E '+' E • ] '+' E ]`) of a counterexample.
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Derivation {
+    /// Atomic pattern that is not derived any further.
+    Leaf(AtomicPattern),
+    /// Symbol that is derived using one of its rules.
+    Node {
+        /// Symbol that is derived.
+        symbol: Symbol,
+        /// Derivations of the atomic patterns of the rule.
+        derivations: Vec<Derivation>,
+    },
+    /// Position of the conflict.
+    Dot,
+}
+
+impl Derivation {
+    /// Gets the example (e.g., `E '+' E • '+' E`) of the derivation.
+    pub fn example(&self) -> String {
+        let mut leaves = vec![];
+        self.collect_leaves(&mut leaves);
+        leaves
+            .into_iter()
+            .filter(|leaf| *leaf != Some(&AtomicPattern::Token(Token::Empty)))
+            .map(|leaf| {
+                match leaf {
+                    Some(atomic_pattern) => atomic_pattern.to_string(),
+                    None => "•".to_owned(),
+                }
+            })
+            .join(" ")
+    }
+}
+
+impl Derivation {
+    /// Collects the leaves of the derivation, with `None` representing the dot.
+    fn collect_leaves<'d>(&'d self, leaves: &mut Vec<Option<&'d AtomicPattern>>) {
+        match self {
+            Derivation::Leaf(atomic_pattern) => leaves.push(Some(atomic_pattern)),
+            Derivation::Node { derivations, .. } => {
+                for derivation in derivations {
+                    derivation.collect_leaves(leaves);
+                }
+            },
+            Derivation::Dot => leaves.push(None),
+        }
+    }
+
+    /// Gets the non-empty atomic patterns after the dot, along with their leaf indices.
+    fn remainder(&self) -> Vec<(usize, AtomicPattern)> {
+        let mut leaves = vec![];
+        self.collect_leaves(&mut leaves);
+
+        let mut remainder = vec![];
+        let mut index = 0;
+        let mut after_dot = false;
+        for leaf in leaves {
+            match leaf {
+                Some(atomic_pattern) => {
+                    if after_dot && *atomic_pattern != AtomicPattern::Token(Token::Empty) {
+                        remainder.push((index, atomic_pattern.clone()));
+                    }
+                    index += 1;
+                },
+                None => after_dot = true,
+            }
+        }
+        remainder
+    }
+
+    /// Derives the leaf at an index using a rule.
+    fn expand(&mut self, index: &mut usize, rule: &Rule) -> bool {
+        match self {
+            Derivation::Leaf(_) => {
+                if *index == 0 {
+                    *self = Derivation::Node {
+                        symbol: rule.symbol().clone(),
+                        derivations: rule.pattern().iter().cloned().map(Derivation::Leaf).collect(),
+                    };
+                    return true;
+                }
+                *index -= 1;
+                false
+            },
+            Derivation::Node { derivations, .. } => {
+                derivations.iter_mut().any(|derivation| derivation.expand(index, rule))
+            },
+            Derivation::Dot => false,
+        }
+    }
+}
+
+impl Display for Derivation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Derivation::Leaf(atomic_pattern) => write!(f, "{}", atomic_pattern),
+            Derivation::Node { symbol, derivations } => {
+                write!(f, "{} -> [", symbol)?;
+                for derivation in derivations {
+                    write!(f, " {}", derivation)?;
+                }
+                write!(f, " ]")
+            },
+            Derivation::Dot => write!(f, "•"),
+        }
+    }
+}
+
+
+/// Counterexample of a conflict, showing how its first two competing actions can be reached.
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Counterexample {
+    state: usize,
+    token: Token,
+    prefix: Vec<AtomicPattern>,
+    unifying: bool,
+    derivations: Vec<(Action, Derivation)>,
+}
+
+impl Counterexample {
+    /// Constructs the counterexample of a conflict, if one can be found.
+    pub fn construct(
+        grammar: &Grammar,
+        first_table: &FirstTable,
+        automaton: &Automaton,
+        conflict: &Conflict,
+    ) -> Option<Counterexample> {
+        let state = &automaton.states()[conflict.state()];
+        let token = conflict.token();
+
+        let mut targets = vec![];
+        for action in conflict.actions().iter().take(2) {
+            let item = match action {
+                Action::Shift { .. } | Action::Accept => {
+                    conflict.items().iter().find(|item| {
+                        item.dot() < item.rule().pattern().len() && !item.rule().is_empty_pattern()
+                    })?
+                },
+                Action::Reduce { rule_index } => {
+                    let rule = &grammar.rules()[*rule_index];
+                    conflict.items().iter().find(|item| {
+                        item.rule() == rule
+                            && (item.dot() == rule.pattern().len() || rule.is_empty_pattern())
+                    })?
+                },
+            };
+            let index = state.items().iter().position(|candidate| {
+                candidate.rule() == item.rule() && candidate.dot() == item.dot()
+            })?;
+            let lookahead = match action {
+                Action::Shift { .. } | Action::Accept => None,
+                Action::Reduce { .. } => Some(token.clone()),
+            };
+            targets.push((*action, index, lookahead));
+        }
+        if targets.len() != 2 {
+            return None;
+        }
+
+        // Reductions are searched first, as they are the ones that constrain the prefix.
+        let (first, second) = if targets[0].2.is_none() { (1, 0) } else { (0, 1) };
+
+        let searcher = Searcher::new(grammar, first_table, automaton);
+        let first_path = {
+            let (_, index, lookahead) = &targets[first];
+            searcher.search(None, conflict.state(), *index, lookahead.clone())?
+        };
+        let (second_path, same_prefix) = {
+            let (_, index, lookahead) = &targets[second];
+            let states = Some(first_path.states.as_slice());
+            let position = first_path.states.len() - 1;
+            // Paths with the same prefix may not exist (e.g., in LALR(1) conflicts caused by
+            // merging states), so the path is searched without the prefix of the first path,
+            // but the conflict token is still required to follow the reductions.
+            match searcher.search(states, position, *index, lookahead.clone()) {
+                Some(path) => (path, true),
+                None => {
+                    (searcher.search(None, conflict.state(), *index, lookahead.clone())?, false)
+                },
+            }
+        };
+
+        let prefix = first_path
+            .edges
+            .iter()
+            .filter_map(|edge| {
+                match edge {
+                    Edge::Transition(atomic_pattern) => Some(atomic_pattern.clone()),
+                    Edge::Production => None,
+                }
+            })
+            .collect();
+
+        let mut derivations = [(first, first_path.derive()), (second, second_path.derive())];
+        derivations.sort_by_key(|(index, _)| *index);
+
+        let [(_, mut first_derivation), (_, mut second_derivation)] = derivations;
+        for (derivation, (action, ..)) in
+            [&mut first_derivation, &mut second_derivation].into_iter().zip(targets.iter())
+        {
+            if matches!(action, Action::Reduce { .. }) {
+                searcher.expand_to_token(derivation, token);
+            }
+        }
+
+        // Derivations with different prefixes cannot derive the same sentential form.
+        let unified = if same_prefix {
+            searcher.unify(first_derivation.clone(), second_derivation.clone())
+        } else {
+            None
+        };
+        let (unifying, first_derivation, second_derivation) = match unified {
+            Some((first_derivation, second_derivation)) => {
+                (true, first_derivation, second_derivation)
+            },
+            None => (false, first_derivation, second_derivation),
+        };
+
+        Some(Counterexample {
+            state: conflict.state(),
+            token: token.clone(),
+            prefix,
+            unifying,
+            derivations: vec![(targets[0].0, first_derivation), (targets[1].0, second_derivation)],
+        })
+    }
+}
+
+impl Counterexample {
+    /// Gets the state of the conflict of the counterexample.
+    pub fn state(&self) -> usize {
+        self.state
+    }
+
+    /// Gets the token of the conflict of the counterexample.
+    pub fn token(&self) -> &Token {
+        &self.token
+    }
+
+    /// Gets the shortest prefix of atomic patterns that reaches the state of the conflict.
+    pub fn prefix(&self) -> &[AtomicPattern] {
+        &self.prefix
+    }
+
+    /// Gets whether the derivations of the counterexample derive the same sentential form.
+    pub fn is_unifying(&self) -> bool {
+        self.unifying
+    }
+
+    /// Gets the competing actions of the counterexample, along with their derivations.
+    pub fn derivations(&self) -> &[(Action, Derivation)] {
+        &self.derivations
+    }
+}
+
+impl Display for Counterexample {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = if self.unifying { "unifying" } else { "non-unifying" };
+        write!(f, "{} at state {} on {}", kind, self.state, self.token)?;
+
+        writeln!(f)?;
+        if self.prefix.is_empty() {
+            write!(f, "  prefix: {}", Token::Empty)?;
+        } else {
+            write!(f, "  prefix: {}", self.prefix.iter().join(" "))?;
+        }
+
+        for (action, derivation) in self.derivations.iter() {
+            writeln!(f)?;
+            match action {
+                Action::Shift { next_state } => write!(f, "  shift {}", next_state)?,
+                Action::Reduce { rule_index } => write!(f, "  reduce {}", rule_index + 1)?,
+                Action::Accept => write!(f, "  accept")?,
+            }
+            writeln!(f)?;
+            write!(f, "    example: {}", derivation.example())?;
+            writeln!(f)?;
+            write!(f, "    derivation: {}", derivation)?;
+        }
+        Ok(())
+    }
+}
+
+
+/// Edge of the state-item graph of an automaton.
+#[derive(Clone, Debug)]
+enum Edge {
+    /// Edge between an item and the item with its dot moved to the right.
+    Transition(AtomicPattern),
+    /// Edge between an item and an item of the symbol after its dot.
+    Production,
+}
+
+
+/// Node of the state-item graph of an automaton.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+struct Node {
+    /// State of the node, or its position in the prefix for constrained searches.
+    location: usize,
+    /// Index of the item of the node in its state.
+    item: usize,
+    /// Token that needs to follow the reduced symbols, if any.
+    lookahead: Option<Token>,
+}
+
+
+/// Path in the state-item graph from the start of an automaton to a conflicting item.
+struct Path {
+    items: Vec<Item>,
+    edges: Vec<Edge>,
+    states: Vec<usize>,
+}
+
+impl Path {
+    /// Builds the derivation of the path.
+    fn derive(&self) -> Derivation {
+        let mut frames = vec![(self.items[0].rule().clone(), vec![])];
+        for (edge, item) in self.edges.iter().zip(self.items.iter().skip(1)) {
+            match edge {
+                Edge::Transition(atomic_pattern) => {
+                    let (_, derivations) = frames.last_mut().unwrap();
+                    derivations.push(Derivation::Leaf(atomic_pattern.clone()));
+                },
+                Edge::Production => {
+                    frames.push((item.rule().clone(), vec![]));
+                },
+            }
+        }
+
+        let mut derivation = None;
+        while let Some((rule, mut derivations)) = frames.pop() {
+            let dot = derivations.len();
+            let rest = match derivation.take() {
+                Some(child) => {
+                    derivations.push(child);
+                    dot + 1
+                },
+                None => {
+                    if rule.is_empty_pattern() {
+                        derivations.push(Derivation::Leaf(Token::Empty.into()));
+                        derivations.push(Derivation::Dot);
+                        rule.pattern().len()
+                    } else {
+                        derivations.push(Derivation::Dot);
+                        dot
+                    }
+                },
+            };
+            derivations.extend(rule.pattern()[rest..].iter().cloned().map(Derivation::Leaf));
+            derivation = Some(Derivation::Node { symbol: rule.symbol().clone(), derivations });
+        }
+        derivation.unwrap()
+    }
+}
+
+
+/// Searcher of counterexamples in an automaton.
+struct Searcher<'a> {
+    grammar: &'a Grammar,
+    first_table: &'a FirstTable,
+    automaton: &'a Automaton,
+    predecessors: Vec<Vec<(usize, AtomicPattern)>>,
+}
+
+impl<'a> Searcher<'a> {
+    /// Creates a new searcher.
+    fn new(
+        grammar: &'a Grammar,
+        first_table: &'a FirstTable,
+        automaton: &'a Automaton,
+    ) -> Searcher<'a> {
+        let mut predecessors = vec![vec![]; automaton.states().len()];
+        for state in automaton.states() {
+            for (atomic_pattern, next_state) in state.transitions() {
+                predecessors[*next_state].push((state.id(), atomic_pattern.clone()));
+            }
+        }
+        Searcher { grammar, first_table, automaton, predecessors }
+    }
+}
+
+impl Searcher<'_> {
+    /// Searches the shortest path from the start of the automaton to an item.
+    ///
+    /// If `states` is provided, the path is constrained to go through them in order,
+    /// and locations of the nodes are positions in `states` instead of states.
+    fn search(
+        &self,
+        states: Option<&[usize]>,
+        location: usize,
+        item: usize,
+        lookahead: Option<Token>,
+    ) -> Option<Path> {
+        let state_of =
+            |node: &Node| states.map(|states| states[node.location]).unwrap_or(node.location);
+
+        let target = Node { location, item, lookahead };
+        let mut successors = IndexMap::<Node, Option<(Node, Edge)>>::new();
+        successors.insert(target.clone(), None);
+
+        let mut queue = VecDeque::from([target]);
+        while let Some(node) = queue.pop_front() {
+            let state = &self.automaton.states()[state_of(&node)];
+            let item = &state.items()[node.item];
+
+            let is_start = node.location == 0
+                && item.dot() == 0
+                && ((item.rule().symbol() == self.grammar.start_symbol()
+                    && matches!(node.lookahead, None | Some(Token::Eof)))
+                    || *item.rule() == self.grammar.augmented_rule());
+            if is_start {
+                let mut items = vec![item.clone()];
+                let mut edges = vec![];
+                let mut path_states = vec![state.id()];
+
+                let mut current = node;
+                while let Some((next, edge)) = successors[&current].clone() {
+                    let next_state = &self.automaton.states()[state_of(&next)];
+                    if let Edge::Transition(_) = edge {
+                        path_states.push(next_state.id());
+                    }
+                    items.push(next_state.items()[next.item].clone());
+                    edges.push(edge);
+                    current = next;
+                }
+
+                return Some(Path { items, edges, states: path_states });
+            }
+
+            let mut predecessors = vec![];
+            if item.dot() > 0 {
+                let atomic_pattern = &item.rule().pattern()[item.dot() - 1];
+                let candidates = match states {
+                    Some(states) => {
+                        if node.location == 0 {
+                            vec![]
+                        } else {
+                            vec![(node.location - 1, states[node.location - 1])]
+                        }
+                    },
+                    None => {
+                        self.predecessors[state.id()]
+                            .iter()
+                            .filter(|(_, candidate)| candidate == atomic_pattern)
+                            .map(|(predecessor, _)| (*predecessor, *predecessor))
+                            .collect()
+                    },
+                };
+                for (location, predecessor) in candidates {
+                    let predecessor = &self.automaton.states()[predecessor];
+                    if predecessor.transitions().get(atomic_pattern) != Some(&state.id()) {
+                        continue;
+                    }
+                    if let Some(index) = predecessor.items().iter().position(|candidate| {
+                        candidate.rule() == item.rule() && candidate.dot() == item.dot() - 1
+                    }) {
+                        let predecessor =
+                            Node { location, item: index, lookahead: node.lookahead.clone() };
+                        predecessors.push((predecessor, Edge::Transition(atomic_pattern.clone())));
+                    }
+                }
+            } else {
+                let symbol = AtomicPattern::Symbol(item.rule().symbol().clone());
+                for (index, candidate) in state.items().iter().enumerate() {
+                    let pattern = candidate.rule().pattern();
+                    if candidate.dot() >= pattern.len() || pattern[candidate.dot()] != symbol {
+                        continue;
+                    }
+                    let lookahead = match &node.lookahead {
+                        Some(token) => {
+                            match self.follows(&pattern[candidate.dot() + 1..], token) {
+                                Some(satisfied) => {
+                                    if satisfied {
+                                        None
+                                    } else {
+                                        Some(token.clone())
+                                    }
+                                },
+                                None => continue,
+                            }
+                        },
+                        None => None,
+                    };
+                    let predecessor = Node { location: node.location, item: index, lookahead };
+                    predecessors.push((predecessor, Edge::Production));
+                }
+            }
+
+            for (predecessor, edge) in predecessors {
+                if !successors.contains_key(&predecessor) {
+                    successors.insert(predecessor.clone(), Some((node.clone(), edge)));
+                    queue.push_back(predecessor);
+                }
+            }
+        }
+        None
+    }
+
+    /// Checks whether a token can follow a sequence of atomic patterns.
+    ///
+    /// Returns `Some(true)` if the token can be the first token of the sequence,
+    /// `Some(false)` if the sequence can be empty, and `None` otherwise.
+    fn follows(&self, atomic_patterns: &[AtomicPattern], token: &Token) -> Option<bool> {
+        for atomic_pattern in atomic_patterns {
+            match atomic_pattern {
+                AtomicPattern::Symbol(symbol) => {
+                    let first_tokens = self.first_table.get(symbol)?;
+                    if first_tokens.contains(token) {
+                        return Some(true);
+                    }
+                    if !first_tokens.contains(&Token::Empty) {
+                        return None;
+                    }
+                },
+                AtomicPattern::Token(Token::Empty) => {},
+                AtomicPattern::Token(candidate) => {
+                    return if candidate == token { Some(true) } else { None };
+                },
+            }
+        }
+        Some(false)
+    }
+
+    /// Expands the symbols after the dot of a derivation until a token follows the dot.
+    fn expand_to_token(&self, derivation: &mut Derivation, token: &Token) {
+        for _ in 0..EXPANSION_LIMIT {
+            let (mut index, symbol) = match derivation.remainder().into_iter().next() {
+                Some((index, AtomicPattern::Symbol(symbol))) => (index, symbol),
+                _ => return,
+            };
+
+            let can_start_with_token = self
+                .first_table
+                .get(&symbol)
+                .map(|first_tokens| first_tokens.contains(token))
+                .unwrap_or(false);
+
+            let rules = self.grammar.rules().iter().filter(|rule| *rule.symbol() == symbol);
+            let rule = if can_start_with_token {
+                rules
+                    .filter(|rule| {
+                        match rule.pattern().first() {
+                            Some(AtomicPattern::Token(first_token)) => first_token == token,
+                            Some(AtomicPattern::Symbol(first_symbol)) => {
+                                *first_symbol != symbol
+                                    && self.follows(&rule.pattern()[..1], token) == Some(true)
+                            },
+                            None => false,
+                        }
+                    })
+                    .min_by_key(|rule| rule.pattern().len())
+            } else {
+                rules.into_iter().find(|rule| rule.is_empty_pattern())
+            };
+
+            match rule {
+                Some(rule) => {
+                    derivation.expand(&mut index, rule);
+                },
+                None => return,
+            }
+        }
+    }
+
+    /// Tries to make two derivations derive the same sentential form.
+    fn unify(&self, first: Derivation, second: Derivation) -> Option<(Derivation, Derivation)> {
+        let mut queue = VecDeque::from([(first, second, 0)]);
+        let mut explored = 0;
+        while let Some((first, second, depth)) = queue.pop_front() {
+            explored += 1;
+            if explored > UNIFICATION_LIMIT {
+                break;
+            }
+
+            let remainders = [first.remainder(), second.remainder()];
+            let mismatch = remainders[0]
+                .iter()
+                .zip(remainders[1].iter())
+                .position(|((_, a), (_, b))| a != b)
+                .unwrap_or(remainders[0].len().min(remainders[1].len()));
+
+            if mismatch == remainders[0].len() && mismatch == remainders[1].len() {
+                return Some((first, second));
+            }
+            if depth == UNIFICATION_DEPTH {
+                continue;
+            }
+
+            for (side, remainder) in remainders.iter().enumerate() {
+                if let Some((index, AtomicPattern::Symbol(symbol))) = remainder.get(mismatch) {
+                    for rule in self.grammar.rules().iter().filter(|rule| rule.symbol() == symbol) {
+                        let mut derivations = [first.clone(), second.clone()];
+                        derivations[side].expand(&mut index.clone(), rule);
+
+                        let [first, second] = derivations;
+                        queue.push_back((first, second, depth + 1));
+                    }
+                }
+            }
+        }
+        None
+    }
+}
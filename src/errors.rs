@@ -75,13 +75,51 @@ pub enum ParserError {
     )]
     UndefinedRegexToken { regex_token: RegexToken, rule: Rule },
 
-    /// A conflict has been detected.
+    /// An undefined lexer mode is used in a lexer mode declaration.
+    #[error("lexer mode {} is not defined", format_smolstr!("{}", mode).green())]
+    UndefinedLexerMode { mode: SmolStr },
+
+    /// An undefined precedence is used in a rule.
     #[error(
-        "conflict at state {} on {}",
-        format_smolstr!("{}", state).green(),
-        format_smolstr!("{}", token).green(),
+        "precedence {} in rule {} is not defined",
+        format_smolstr!("{}", precedence).green(),
+        format_smolstr!("{}", rule).green(),
+    )]
+    UndefinedPrecedence { precedence: AtomicPattern, rule: Rule },
+
+    /// Conflicts have been detected.
+    #[error(
+        "{} at {}",
+        if conflicts.len() == 1 { "conflict" } else { "conflicts" },
+        conflicts
+            .iter()
+            .map(|conflict| {
+                format!(
+                    "state {} on {}",
+                    format_smolstr!("{}", conflict.state()).green(),
+                    format_smolstr!("{}", conflict.token()).green(),
+                )
+            })
+            .join(", "),
     )]
-    Conflict { parser: Box<Parser>, state: usize, token: Token },
+    Conflicts { parser: Box<Parser>, conflicts: Vec<Conflict> },
+
+    /// Conflicts have been detected in the predictive parsing table of an LL(1) parser.
+    #[error(
+        "{} at {}",
+        if conflicts.len() == 1 { "conflict" } else { "conflicts" },
+        conflicts
+            .iter()
+            .map(|conflict| {
+                format!(
+                    "{} on {}",
+                    format_smolstr!("{}", conflict.symbol()).green(),
+                    format_smolstr!("{}", conflict.token()).green(),
+                )
+            })
+            .join(", "),
+    )]
+    LlConflicts { parser: Box<LlParser>, conflicts: Vec<LlConflict> },
 }
 
 
@@ -89,19 +127,42 @@ pub enum ParserError {
 #[cfg_attr(feature = "serde", derive(Serialize))]
 #[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
 #[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
-#[derive(Debug, Error)]
+#[derive(Clone, Debug, Error)]
 pub enum ParsingError {
     /// An unknown token has been encountered.
+    ///
+    /// Mode is the active lexer mode, if the grammar has lexer modes.
     #[error(
-        "unknown token {} at {}",
+        "unknown token {} at {}{}",
         format_smolstr!("{}", token).green(),
-        format_smolstr!("{}:{}", span.line, span.column).cyan()
+        format_smolstr!("{}:{}", span.line, span.column).cyan(),
+        match mode {
+            Some(mode) => format!(" in {} mode", format_smolstr!("{}", mode).green()),
+            None => String::new(),
+        },
     )]
-    UnknownToken { token: SmolStr, span: Span },
+    UnknownToken { token: SmolStr, span: Span, mode: Option<SmolStr> },
+
+    /// An inconsistent dedent has been encountered in an indentation sensitive input.
+    ///
+    /// Span is the indentation of the line, which doesn't match any of the enclosing lines.
+    #[error(
+        "inconsistent dedent at {} (indentation doesn't match any outer indentation level)",
+        format_smolstr!("{}:{}", span.line, span.column).cyan(),
+    )]
+    InconsistentDedent { span: Span },
+
+    /// A token that is not defined in the grammar has been lexed by an external lexer.
+    #[error(
+        "undefined token {} at {}",
+        format_smolstr!("{}", token).green(),
+        format_smolstr!("{}:{}", span.line, span.column).cyan(),
+    )]
+    UndefinedToken { token: Token, span: Span },
 
     /// An unexpected token has been encountered.
     #[error(
-        "unexpected token {} at {} (expected {})",
+        "unexpected token {} at {} (expected {}){}",
         format_smolstr!("{}", token).green(),
         format_smolstr!("{}:{}", span.line, span.column).cyan(),
         if expected.len() == 1 {
@@ -112,12 +173,22 @@ pub enum ParsingError {
                 expected.iter().map(|token| format_smolstr!("{}", token).green()).join(", "),
             )
         },
+        if repairs.is_empty() {
+            String::new()
+        } else {
+            format!(", try to {}", repairs.iter().join(" or "))
+        },
     )]
-    UnexpectedToken { token: SmolStr, expected: SmallVec<[Token; 2]>, span: Span },
+    UnexpectedToken {
+        token: SmolStr,
+        expected: SmallVec<[Token; 2]>,
+        span: Span,
+        repairs: Vec<Repair>,
+    },
 
     /// An unexpected end of input has been encountered.
     #[error(
-        "unexpected end of input at {} (expected {})",
+        "unexpected end of input at {} (expected {}){}",
         format_smolstr!("{}:{}", span.line, span.column).cyan(),
         if expected.len() == 1 {
             format!("{}", format_smolstr!("{}", expected[0]).green())
@@ -127,8 +198,13 @@ pub enum ParsingError {
                 expected.iter().map(|token| format_smolstr!("{}", token).green()).join(", "),
             )
         },
+        if repairs.is_empty() {
+            String::new()
+        } else {
+            format!(", try to {}", repairs.iter().join(" or "))
+        },
     )]
-    UnexpectedEof { expected: SmallVec<[Token; 2]>, span: Span },
+    UnexpectedEof { expected: SmallVec<[Token; 2]>, span: Span, repairs: Vec<Repair> },
 }
 
 
@@ -150,11 +226,19 @@ impl WasmParserError {
         serde_wasm_bindgen::to_value(&self.0).map_err(JsValue::from)
     }
 
-    /// Converts the parser error to the conflicted parser if error was a conflict error.
+    /// Converts the parser error to the conflicted parser if error was a conflicts error.
     pub fn into_conflict_parser(self) -> Result<Parser, JsValue> {
         match self.0 {
-            ParserError::Conflict { parser, .. } => Ok(*parser),
-            _ => Err(JsValue::from("ParserError is not a `Conflict` error")),
+            ParserError::Conflicts { parser, .. } => Ok(*parser),
+            _ => Err(JsValue::from("ParserError is not a `Conflicts` error")),
+        }
+    }
+
+    /// Converts the parser error to the conflicted LL(1) parser if error was an LL conflicts error.
+    pub fn into_ll_conflict_parser(self) -> Result<LlParser, JsValue> {
+        match self.0 {
+            ParserError::LlConflicts { parser, .. } => Ok(*parser),
+            _ => Err(JsValue::from("ParserError is not an `LlConflicts` error")),
         }
     }
 }
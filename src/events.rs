@@ -0,0 +1,115 @@
+use crate::prelude::*;
+
+
+/// Event of a parse, which is emitted as the parser takes an action.
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
+#[derive(Clone, Debug)]
+pub enum Event<'i> {
+    /// A token has been shifted.
+    Shift { token: Token, span: Span, slice: &'i str },
+    /// A rule has been reduced, from the last child count events that are not consumed yet
+    /// (i.e., shifted tokens and reduced rules).
+    Reduce { rule_index: usize, child_count: usize },
+    /// The input has been accepted.
+    Accept,
+    /// The input has been rejected.
+    Error { error: ParsingError },
+}
+
+
+/// Events of a parse, which are emitted lazily as the tokens are consumed.
+///
+/// Only the state stack of the parser is kept during the parse, so memory usage is bounded by
+/// the depth of the stack instead of the size of the input. Events end after [Event::Accept]
+/// or [Event::Error].
+pub struct Events<'p, 'i, I: Iterator<Item = Result<(Spanned<Token>, &'i str), ParsingError>>> {
+    parser: &'p Parser,
+    state_stack: Vec<usize>,
+    remaining_tokens: UpcomingTokens<'i, CheckedLexer<I>>,
+    current_token: Option<(Spanned<Token>, &'i str)>,
+    finished: bool,
+}
+
+impl<'p, 'i, I: Iterator<Item = Result<(Spanned<Token>, &'i str), ParsingError>>>
+    Events<'p, 'i, I>
+{
+    /// Creates the events of parsing a stream of tokens using a parser.
+    pub(crate) fn new(parser: &'p Parser, tokens: I) -> Events<'p, 'i, I> {
+        Events {
+            parser,
+            state_stack: vec![0],
+            remaining_tokens: UpcomingTokens::new(CheckedLexer::new(parser.grammar(), tokens)),
+            current_token: None,
+            finished: false,
+        }
+    }
+
+    /// Internal event emission logic, which must not be called after the events end
+    /// (i.e., after [Event::Accept] or [Event::Error]).
+    pub(crate) fn next_internal(&mut self) -> Event<'i> {
+        // Tokens are consumed only when they are needed, so tokenizing and parsing are interleaved.
+        let (current_token, current_slice) = match self.current_token.take() {
+            Some(token) => token,
+            None => {
+                match self.remaining_tokens.next(None) {
+                    Ok(token) => token,
+                    Err(error) => {
+                        self.finished = true;
+                        return Event::Error { error };
+                    },
+                }
+            },
+        };
+
+        let action_to_take = match self.parser.action_internal(&self.state_stack, &current_token) {
+            Some(action) => action,
+            None => {
+                self.finished = true;
+                let error = self.parser.error_internal(
+                    &self.state_stack,
+                    &current_token,
+                    current_slice,
+                    self.remaining_tokens.peek(UPCOMING_TOKENS_TO_SEARCH - 1),
+                );
+                return Event::Error { error };
+            },
+        };
+
+        self.parser.step_internal(&mut self.state_stack, action_to_take);
+        match action_to_take {
+            Action::Accept => {
+                self.finished = true;
+                Event::Accept
+            },
+            Action::Shift { .. } => {
+                let (token, span) = current_token.into_components();
+                Event::Shift { token, span, slice: current_slice }
+            },
+            Action::Reduce { rule_index } => {
+                self.current_token = Some((current_token, current_slice));
+                let child_count = self.parser.pattern_length_internal(rule_index);
+                Event::Reduce { rule_index, child_count }
+            },
+        }
+    }
+}
+
+impl<'i, I: Iterator<Item = Result<(Spanned<Token>, &'i str), ParsingError>>> Iterator
+    for Events<'_, 'i, I>
+{
+    type Item = Event<'i>;
+
+    fn next(&mut self) -> Option<Event<'i>> {
+        if self.finished {
+            return None;
+        }
+        Some(self.next_internal())
+    }
+}
+
+impl<'i, I: Iterator<Item = Result<(Spanned<Token>, &'i str), ParsingError>>>
+    std::iter::FusedIterator for Events<'_, 'i, I>
+{
+}
@@ -0,0 +1,485 @@
+use crate::prelude::*;
+
+
+/// Alternative derivation (i.e., packed node) of a symbol node in a parse forest.
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Alternative {
+    rule_index: usize,
+    children: Vec<usize>,
+}
+
+impl Alternative {
+    /// Creates a new alternative.
+    pub fn new(rule_index: usize, children: impl Into<Vec<usize>>) -> Alternative {
+        Alternative { rule_index, children: children.into() }
+    }
+}
+
+impl Alternative {
+    /// Gets the index of the rule of the alternative.
+    pub fn rule_index(&self) -> usize {
+        self.rule_index
+    }
+
+    /// Gets the indices of the nodes matching the pattern of the rule of the alternative.
+    pub fn children(&self) -> &[usize] {
+        &self.children
+    }
+}
+
+
+/// Node of a parse forest.
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
+#[derive(Clone, Debug)]
+pub enum ForestNode<'i> {
+    /// Terminal node.
+    Terminal {
+        /// Matching token.
+        token: Token,
+        /// Matching span.
+        span: Span,
+        /// Matching slice.
+        slice: &'i str,
+    },
+    /// Non-terminal node, shared by every derivation of a symbol over the same tokens.
+    NonTerminal {
+        /// Matching symbol.
+        symbol: Symbol,
+        /// Index of the first token matched by the symbol.
+        start: usize,
+        /// Index of the token after the last token matched by the symbol.
+        end: usize,
+        /// Alternative derivations of the symbol.
+        alternatives: Vec<Alternative>,
+    },
+}
+
+
+/// Shared packed parse forest of a parsed input, containing every parse tree of the input.
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[derive(Clone, Debug)]
+pub struct Forest<'i> {
+    nodes: Vec<ForestNode<'i>>,
+    root: usize,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    repetition_symbols: IndexSet<Symbol>,
+}
+
+impl<'i> Forest<'i> {
+    /// Constructs the parse forest of a tokenized input by following every action
+    /// in the parsing tables using a graph-structured stack (i.e., GLR parsing).
+    pub fn construct(
+        grammar: &Grammar,
+        parsing_tables: &ParsingTables,
+        tokens: Vec<(Spanned<Token>, &'i str)>,
+    ) -> Result<Forest<'i>, ParsingError> {
+        let action_table = parsing_tables.action_table();
+        let goto_table = parsing_tables.goto_table();
+
+        let mut stack = GraphStructuredStack::default();
+        let mut nodes = Vec::<ForestNode<'i>>::new();
+        let mut non_terminal_nodes = IndexMap::<(Symbol, usize, usize), usize>::new();
+
+        let mut tokens = tokens.into_iter();
+        let mut end_of_input = Span { offset: 0, length: 0, line: 1, column: 1 };
+
+        let mut frontier = vec![stack.push(0, 0)];
+        let mut position = 0;
+        loop {
+            // End of input is assumed to be right after the last token if tokens don't end with it.
+            let (current_token, current_slice) = match tokens.next() {
+                Some(token) => token,
+                None => (Spanned::new(Token::Eof, end_of_input.clone()), "\0"),
+            };
+            end_of_input = utils::position_after(current_token.span(), current_slice);
+
+            // Reductions are repeated until nothing changes, as a new edge to an existing stack node
+            // can make new reductions possible through it (e.g., with empty symbols).
+            loop {
+                let mut changed = false;
+
+                let mut index = 0;
+                while index < frontier.len() {
+                    let stack_node = frontier[index];
+                    index += 1;
+
+                    let state = stack.nodes[stack_node].state;
+                    let actions = match action_table[state].get(current_token.deref()) {
+                        Some(actions) => actions,
+                        None => continue,
+                    };
+                    for action in actions.iter() {
+                        let rule_index = match action {
+                            Action::Reduce { rule_index } => *rule_index,
+                            _ => continue,
+                        };
+
+                        let rule = &grammar.rules()[rule_index];
+                        let pattern_length =
+                            if rule.is_empty_pattern() { 0 } else { rule.pattern().len() };
+
+                        for (origin, children) in stack.paths(stack_node, pattern_length) {
+                            let start = stack.nodes[origin].level;
+                            let key = (rule.symbol().clone(), start, position);
+
+                            let forest_node = *non_terminal_nodes.entry(key).or_insert_with(|| {
+                                nodes.push(ForestNode::NonTerminal {
+                                    symbol: rule.symbol().clone(),
+                                    start,
+                                    end: position,
+                                    alternatives: vec![],
+                                });
+                                nodes.len() - 1
+                            });
+                            if let ForestNode::NonTerminal { alternatives, .. } =
+                                &mut nodes[forest_node]
+                            {
+                                let alternative = Alternative { rule_index, children };
+                                if !alternatives.contains(&alternative) {
+                                    alternatives.push(alternative);
+                                }
+                            }
+
+                            let next_state =
+                                match goto_table[stack.nodes[origin].state].get(rule.symbol()) {
+                                    Some(next_state) => *next_state,
+                                    None => unreachable!(),
+                                };
+                            match frontier
+                                .iter()
+                                .find(|&&stack_node| stack.nodes[stack_node].state == next_state)
+                            {
+                                Some(&existing_stack_node) => {
+                                    let edge = (origin, forest_node);
+                                    let edges = &mut stack.nodes[existing_stack_node].edges;
+                                    if !edges.contains(&edge) {
+                                        edges.push(edge);
+                                        changed = true;
+                                    }
+                                },
+                                None => {
+                                    let new_stack_node = stack.push(next_state, position);
+                                    stack.nodes[new_stack_node].edges.push((origin, forest_node));
+                                    frontier.push(new_stack_node);
+                                    changed = true;
+                                },
+                            }
+                        }
+                    }
+                }
+
+                if !changed {
+                    break;
+                }
+            }
+
+            let mut next_frontier = Vec::<usize>::new();
+            let mut terminal_node = None;
+            for &stack_node in frontier.iter() {
+                let state = stack.nodes[stack_node].state;
+                let actions = match action_table[state].get(current_token.deref()) {
+                    Some(actions) => actions,
+                    None => continue,
+                };
+                for action in actions.iter() {
+                    match action {
+                        Action::Accept => {
+                            // As explained in `Parser::accept_internal`, the start symbol is
+                            // the only one in the stack, so it's the only edge of the stack node.
+                            let (_, root) = stack.nodes[stack_node].edges[0];
+                            let repetition_symbols = grammar.repetition_symbols().clone();
+                            return Ok(Forest { nodes, root, repetition_symbols });
+                        },
+                        Action::Shift { next_state } => {
+                            let terminal_node = *terminal_node.get_or_insert_with(|| {
+                                let (token, span) = current_token.clone().into_components();
+                                nodes.push(ForestNode::Terminal {
+                                    token,
+                                    span,
+                                    slice: current_slice,
+                                });
+                                nodes.len() - 1
+                            });
+
+                            let edge = (stack_node, terminal_node);
+                            match next_frontier
+                                .iter()
+                                .find(|&&stack_node| stack.nodes[stack_node].state == *next_state)
+                            {
+                                Some(&existing_stack_node) => {
+                                    stack.nodes[existing_stack_node].edges.push(edge);
+                                },
+                                None => {
+                                    let new_stack_node = stack.push(*next_state, position + 1);
+                                    stack.nodes[new_stack_node].edges.push(edge);
+                                    next_frontier.push(new_stack_node);
+                                },
+                            }
+                        },
+                        Action::Reduce { .. } => {},
+                    }
+                }
+            }
+
+            if next_frontier.is_empty() {
+                let mut expected = SmallVec::<[Token; 2]>::new();
+                for &stack_node in frontier.iter() {
+                    for (token, _) in action_table[stack.nodes[stack_node].state].iter() {
+                        if !expected.contains(token) {
+                            expected.push(token.clone());
+                        }
+                    }
+                }
+
+                // Repairs are not searched, as the search follows a single stack of states.
+                return Err(if *current_token == Token::Eof {
+                    ParsingError::UnexpectedEof {
+                        expected,
+                        span: current_token.span().clone(),
+                        repairs: vec![],
+                    }
+                } else {
+                    ParsingError::UnexpectedToken {
+                        token: current_token.describe(current_slice),
+                        expected,
+                        span: current_token.span().clone(),
+                        repairs: vec![],
+                    }
+                });
+            }
+            frontier = next_frontier;
+            position += 1;
+        }
+    }
+}
+
+impl<'i> Forest<'i> {
+    /// Gets the nodes of the forest.
+    pub fn nodes(&self) -> &[ForestNode<'i>] {
+        &self.nodes
+    }
+
+    /// Gets the index of the root node of the forest.
+    pub fn root(&self) -> usize {
+        self.root
+    }
+}
+
+impl<'i> Forest<'i> {
+    /// Gets whether the forest contains more than one parse tree.
+    pub fn is_ambiguous(&self) -> bool {
+        self.reachable_nodes_internal().into_iter().any(|node| {
+            matches!(
+                &self.nodes[node],
+                ForestNode::NonTerminal { alternatives, .. } if alternatives.len() > 1
+            )
+        })
+    }
+
+    /// Extracts the parse trees in the forest, up to a limit.
+    ///
+    /// Derivations that cycle back to the same node (e.g., `A -> A`) are skipped,
+    /// as they would result in infinitely many parse trees.
+    pub fn trees(&self, limit: usize) -> Vec<Tree<'i>> {
+        let mut visiting = vec![false; self.nodes.len()];
+        let mut cache = IndexMap::new();
+        let (trees, _) = self.trees_internal(self.root, limit, &mut visiting, &mut cache);
+        trees
+    }
+}
+
+impl<'i> Forest<'i> {
+    /// Internal reachable nodes computation logic.
+    fn reachable_nodes_internal(&self) -> IndexSet<usize> {
+        let mut reachable_nodes = IndexSet::new();
+        let mut nodes_to_visit = vec![self.root];
+        while let Some(node) = nodes_to_visit.pop() {
+            if !reachable_nodes.insert(node) {
+                continue;
+            }
+            if let ForestNode::NonTerminal { alternatives, .. } = &self.nodes[node] {
+                for alternative in alternatives {
+                    nodes_to_visit.extend(alternative.children.iter().copied());
+                }
+            }
+        }
+        reachable_nodes.sort();
+        reachable_nodes
+    }
+
+    /// Internal tree extraction logic.
+    ///
+    /// Returns the trees of the node, along with whether they are complete.
+    /// Trees are incomplete if a cycle is skipped, and they're not cached in that case,
+    /// since the same node might have more trees when it's reached from elsewhere.
+    fn trees_internal(
+        &self,
+        node: usize,
+        limit: usize,
+        visiting: &mut Vec<bool>,
+        cache: &mut IndexMap<usize, Vec<Tree<'i>>>,
+    ) -> (Vec<Tree<'i>>, bool) {
+        if let Some(trees) = cache.get(&node) {
+            return (trees.clone(), true);
+        }
+
+        let (symbol, alternatives) = match &self.nodes[node] {
+            ForestNode::Terminal { token, span, slice } => {
+                let tree = Tree::Terminal {
+                    token: token.clone(),
+                    span: span.clone(),
+                    slice,
+                    trivia: None,
+                };
+                return (vec![tree], true);
+            },
+            ForestNode::NonTerminal { symbol, alternatives, .. } => (symbol, alternatives),
+        };
+        if visiting[node] {
+            return (vec![], false);
+        }
+        visiting[node] = true;
+
+        let mut trees = vec![];
+        let mut complete = true;
+        for alternative in alternatives {
+            if trees.len() >= limit {
+                break;
+            }
+
+            let mut patterns = vec![vec![]];
+            for &child in alternative.children.iter() {
+                let (child_trees, child_complete) =
+                    self.trees_internal(child, limit, visiting, cache);
+                complete &= child_complete;
+
+                let mut new_patterns = vec![];
+                'outer: for pattern in patterns.iter() {
+                    for child_tree in child_trees.iter() {
+                        if new_patterns.len() >= limit {
+                            break 'outer;
+                        }
+                        let mut new_pattern = pattern.clone();
+                        new_pattern.push(child_tree.clone());
+                        new_patterns.push(new_pattern);
+                    }
+                }
+                patterns = new_patterns;
+
+                if patterns.is_empty() {
+                    break;
+                }
+            }
+
+            for pattern in patterns {
+                if trees.len() >= limit {
+                    break;
+                }
+                trees.push(Tree::reduced(&self.repetition_symbols, symbol.clone(), pattern));
+            }
+        }
+
+        visiting[node] = false;
+        if complete {
+            cache.insert(node, trees.clone());
+        }
+        (trees, complete)
+    }
+}
+
+impl Forest<'_> {
+    /// Dumps the reachable nodes of the parse forest to stdout.
+    pub fn dump(&self, grammar: &Grammar) {
+        let mut pretty_forest = Table::new();
+        pretty_forest
+            .add_row(row![cbFy->"Node", cbFy->"Label", cbFy->"Tokens", cbFy->"Alternatives"]);
+        for node in self.reachable_nodes_internal() {
+            let node_formatted =
+                if node == self.root { format!("{} (root)", node) } else { node.to_string() };
+            match &self.nodes[node] {
+                ForestNode::Terminal { token, slice, .. } => {
+                    pretty_forest.add_row(row![
+                        node_formatted,
+                        token,
+                        "",
+                        token.describe(slice).green().bold()
+                    ]);
+                },
+                ForestNode::NonTerminal { symbol, start, end, alternatives } => {
+                    let alternatives_formatted = alternatives
+                        .iter()
+                        .map(|alternative| {
+                            let rule = &grammar.rules()[alternative.rule_index];
+                            if alternative.children.is_empty() {
+                                format!("{}", rule)
+                            } else {
+                                format!("{}  [{}]", rule, alternative.children.iter().join(" "))
+                            }
+                        })
+                        .join("\n");
+                    let tokens_formatted = format!("{}..{}", start, end);
+                    pretty_forest.add_row(row![
+                        node_formatted,
+                        symbol,
+                        tokens_formatted,
+                        alternatives_formatted,
+                    ]);
+                },
+            }
+        }
+        pretty_forest.printstd();
+    }
+}
+
+
+/// Node of a graph-structured stack.
+#[derive(Debug)]
+struct StackNode {
+    /// State of the node.
+    state: usize,
+    /// Number of tokens consumed before the node is pushed.
+    level: usize,
+    /// Previous nodes in the stack, along with the forest node between them.
+    edges: Vec<(usize, usize)>,
+}
+
+
+/// Graph-structured stack, which merges the stacks of every parse alternative.
+#[derive(Debug, Default)]
+struct GraphStructuredStack {
+    nodes: Vec<StackNode>,
+}
+
+impl GraphStructuredStack {
+    /// Pushes a new node without any edges to the stack.
+    fn push(&mut self, state: usize, level: usize) -> usize {
+        self.nodes.push(StackNode { state, level, edges: vec![] });
+        self.nodes.len() - 1
+    }
+
+    /// Computes every path of a length from a node,
+    /// along with the forest nodes along the path in order.
+    fn paths(&self, from: usize, length: usize) -> Vec<(usize, Vec<usize>)> {
+        let mut paths = vec![(from, vec![])];
+        for _ in 0..length {
+            let mut new_paths = vec![];
+            for (node, forest_nodes) in paths {
+                for &(previous_node, forest_node) in self.nodes[node].edges.iter() {
+                    let mut new_forest_nodes = forest_nodes.clone();
+                    new_forest_nodes.push(forest_node);
+                    new_paths.push((previous_node, new_forest_nodes));
+                }
+            }
+            paths = new_paths;
+        }
+        for (_, forest_nodes) in paths.iter_mut() {
+            forest_nodes.reverse();
+        }
+        paths
+    }
+}
@@ -82,7 +82,7 @@ impl<T: Into<SmolStr>> From<T> for RegexToken {
 }
 
 
-/// Token (e.g., `'+'`, `%f`, `$`) in a grammar.
+/// Token (e.g., `'+'`, `%f`, `%error`, `%indent`, `$`) in a grammar.
 #[cfg_attr(feature = "serde", derive(Serialize))]
 #[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
 #[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
@@ -94,6 +94,14 @@ pub enum Token {
     Constant(ConstantToken),
     /// Regular expression token.
     Regex(RegexToken),
+    /// Error token, which matches the erroneous parts of the input during error recovery.
+    Error,
+    /// Indentation token, which is synthesized when the indentation of a line increases.
+    Indent,
+    /// Dedentation token, which is synthesized for each indentation level a line closes.
+    Dedent,
+    /// Newline token, which is synthesized at the end of each non-blank line.
+    Newline,
     /// End of file token.
     Eof,
 }
@@ -110,6 +118,18 @@ impl Display for Token {
             Token::Regex(regex_token) => {
                 write!(f, "{}", regex_token)
             },
+            Token::Error => {
+                write!(f, "%error")
+            },
+            Token::Indent => {
+                write!(f, "%indent")
+            },
+            Token::Dedent => {
+                write!(f, "%dedent")
+            },
+            Token::Newline => {
+                write!(f, "%newline")
+            },
             Token::Eof => {
                 write!(f, "$")
             },
@@ -117,6 +137,24 @@ impl Display for Token {
     }
 }
 
+impl Token {
+    /// Gets whether the token is synthesized from the layout of the input
+    /// (i.e., `%indent`, `%dedent` and `%newline` in indentation sensitive grammars).
+    pub fn is_layout(&self) -> bool {
+        matches!(self, Token::Indent | Token::Dedent | Token::Newline)
+    }
+
+    /// Describes a scanned token, using its slice or its name if it's a layout token
+    /// or the end of input token.
+    pub(crate) fn describe(&self, slice: &str) -> SmolStr {
+        if self.is_layout() || *self == Token::Eof {
+            format_smolstr!("{}", self)
+        } else {
+            slice.into()
+        }
+    }
+}
+
 impl From<ConstantToken> for Token {
     fn from(constant_token: ConstantToken) -> Token {
         Token::Constant(constant_token)
@@ -183,6 +221,7 @@ impl From<RegexToken> for AtomicPattern {
 pub struct Rule {
     symbol: Symbol,
     pattern: SmallVec<[AtomicPattern; 3]>,
+    precedence: Option<AtomicPattern>,
 }
 
 impl Rule {
@@ -191,7 +230,20 @@ impl Rule {
         symbol: impl Into<Symbol>,
         pattern: impl IntoIterator<Item = AtomicPattern>,
     ) -> Rule {
-        Rule { symbol: symbol.into(), pattern: pattern.into_iter().collect() }
+        Rule { symbol: symbol.into(), pattern: pattern.into_iter().collect(), precedence: None }
+    }
+
+    /// Creates a new rule with an explicit precedence (e.g., `E -> '-' E %prec UMINUS`).
+    pub fn with_precedence(
+        symbol: impl Into<Symbol>,
+        pattern: impl IntoIterator<Item = AtomicPattern>,
+        precedence: impl Into<AtomicPattern>,
+    ) -> Rule {
+        Rule {
+            symbol: symbol.into(),
+            pattern: pattern.into_iter().collect(),
+            precedence: Some(precedence.into()),
+        }
     }
 }
 
@@ -206,6 +258,11 @@ impl Rule {
         &self.pattern
     }
 
+    /// Gets the explicit precedence of the rule (e.g., `UMINUS` in `E -> '-' E %prec UMINUS`).
+    pub fn precedence(&self) -> Option<&AtomicPattern> {
+        self.precedence.as_ref()
+    }
+
     /// Gets whether the rule is `S -> ''`.
     pub fn is_empty_pattern(&self) -> bool {
         self.pattern.len() == 1 && self.pattern[0] == AtomicPattern::Token(Token::Empty)
@@ -218,11 +275,131 @@ impl Display for Rule {
         for atomic_pattern in self.pattern.iter() {
             write!(f, " {}", atomic_pattern)?;
         }
+        if let Some(precedence) = &self.precedence {
+            write!(f, " %prec {}", precedence)?;
+        }
         Ok(())
     }
 }
 
 
+/// Associativity (e.g., `%left`, `%right`, `%nonassoc`) of a precedence level in a grammar.
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Associativity {
+    /// Left associative (e.g., `1 - 2 - 3` is `(1 - 2) - 3`).
+    Left,
+    /// Right associative (e.g., `1 ^ 2 ^ 3` is `1 ^ (2 ^ 3)`).
+    Right,
+    /// Non associative (e.g., `1 < 2 < 3` is a syntax error).
+    NonAssociative,
+}
+
+impl Display for Associativity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Associativity::Left => write!(f, "%left"),
+            Associativity::Right => write!(f, "%right"),
+            Associativity::NonAssociative => write!(f, "%nonassoc"),
+        }
+    }
+}
+
+
+/// Precedence (e.g., `%left '+' '-'`) of a token in a grammar.
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Precedence {
+    level: usize,
+    associativity: Associativity,
+}
+
+impl Precedence {
+    /// Creates a new precedence.
+    pub fn new(level: usize, associativity: Associativity) -> Precedence {
+        Precedence { level, associativity }
+    }
+}
+
+impl Precedence {
+    /// Gets the level of the precedence, later declarations have higher levels.
+    pub fn level(&self) -> usize {
+        self.level
+    }
+
+    /// Gets the associativity of the precedence.
+    pub fn associativity(&self) -> Associativity {
+        self.associativity
+    }
+}
+
+
+/// Name of the lexer mode that is active at the start of the input.
+pub const DEFAULT_LEXER_MODE: &str = "Default";
+
+
+/// Transition of a lexer mode, which is triggered after scanning a token in the mode.
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LexerModeTransition {
+    /// Activates a lexer mode, until it's popped.
+    Push(SmolStr),
+    /// Goes back to the previously active lexer mode.
+    Pop,
+}
+
+impl Display for LexerModeTransition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LexerModeTransition::Push(mode) => write!(f, "push {}", mode),
+            LexerModeTransition::Pop => write!(f, "pop"),
+        }
+    }
+}
+
+
+/// Lexer mode of a grammar (e.g., `String`), which determines the tokens to scan in a context.
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[derive(Clone, Debug)]
+pub struct LexerMode {
+    tokens: Vec<Token>,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "utils::serialize_regexes"))]
+    ignored_regular_expressions: Vec<Regex>,
+    transitions: IndexMap<Token, LexerModeTransition>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    scanner: Scanner,
+}
+
+impl LexerMode {
+    /// Gets the tokens that are scanned in the lexer mode.
+    pub fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+
+    /// Gets the regular expressions that are skipped in the lexer mode.
+    pub fn ignored_regular_expressions(&self) -> &[Regex] {
+        &self.ignored_regular_expressions
+    }
+
+    /// Gets the transitions of the lexer mode.
+    pub fn transitions(&self) -> &IndexMap<Token, LexerModeTransition> {
+        &self.transitions
+    }
+}
+
+impl LexerMode {
+    /// Gets the scanner of the tokens of the lexer mode.
+    pub(crate) fn scanner(&self) -> &Scanner {
+        &self.scanner
+    }
+}
+
+
 /// Grammar of a language.
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
@@ -238,6 +415,15 @@ pub struct Grammar {
         serde(serialize_with = "utils::serialize_regex_token_to_regex_map")
     )]
     regular_expressions: IndexMap<RegexToken, Regex>,
+    priorities: IndexMap<RegexToken, usize>,
+    keywords: IndexSet<ConstantToken>,
+    reserved_words: IndexMap<RegexToken, IndexSet<ConstantToken>>,
+    significant_whitespace: bool,
+    indentation_sensitive: bool,
+    lexer_modes: IndexMap<SmolStr, LexerMode>,
+    precedences: IndexMap<AtomicPattern, Precedence>,
+    generated_symbols: IndexSet<Symbol>,
+    repetition_symbols: IndexSet<Symbol>,
     rules: Vec<Rule>,
 }
 
@@ -274,12 +460,106 @@ impl Grammar {
         &self.regular_expressions
     }
 
+    /// Gets the priorities of the regular expression tokens of the grammar.
+    ///
+    /// Priorities break the ties between the longest matches (e.g., `%priority %hex`),
+    /// and the tokens without a priority have priority `0`.
+    pub fn priorities(&self) -> &IndexMap<RegexToken, usize> {
+        &self.priorities
+    }
+
+    /// Gets the keywords of the grammar, which are only scanned at word boundaries
+    /// (e.g., `'if'` is not scanned at the start of `iffy`).
+    pub fn keywords(&self) -> &IndexSet<ConstantToken> {
+        &self.keywords
+    }
+
+    /// Gets the reserved words of the regular expression tokens of the grammar,
+    /// which are scanned as constant tokens even if the regular expression tokens match them
+    /// (e.g., `if` is never scanned as an `%identifier`).
+    pub fn reserved_words(&self) -> &IndexMap<RegexToken, IndexSet<ConstantToken>> {
+        &self.reserved_words
+    }
+
+    /// Gets the regular expressions of the input to skip between the tokens (e.g., comments).
+    ///
+    /// If the grammar has lexer modes, these are the ignored regular expressions of the default mode.
+    pub fn ignored_regular_expressions(&self) -> &[Regex] {
+        self.lexer_modes[0].ignored_regular_expressions()
+    }
+
+    /// Gets whether whitespace is significant in the input (i.e., not skipped between the tokens).
+    pub fn is_whitespace_significant(&self) -> bool {
+        self.significant_whitespace
+    }
+
+    /// Gets whether the input is indentation sensitive (i.e., layout tokens are synthesized).
+    pub fn is_indentation_sensitive(&self) -> bool {
+        self.indentation_sensitive
+    }
+
+    /// Gets the lexer modes of the grammar, starting with the default mode.
+    ///
+    /// Grammars without lexer mode declarations have only the default mode,
+    /// which scans all the tokens of the grammar.
+    pub fn lexer_modes(&self) -> &IndexMap<SmolStr, LexerMode> {
+        &self.lexer_modes
+    }
+
+    /// Gets the precedences of the tokens (and precedence names) of the grammar.
+    pub fn precedences(&self) -> &IndexMap<AtomicPattern, Precedence> {
+        &self.precedences
+    }
+
+    /// Gets the symbols generated for groups, optionals and repetitions
+    /// (e.g., `('+' | '-')`, `Value?`, `(',' Value)*`) in the grammar.
+    pub fn generated_symbols(&self) -> &IndexSet<Symbol> {
+        &self.generated_symbols
+    }
+
+    /// Gets the symbols generated for repetitions (e.g., `Value*`, `(',' Value)+`) in the grammar.
+    pub fn repetition_symbols(&self) -> &IndexSet<Symbol> {
+        &self.repetition_symbols
+    }
+
     /// Gets the rules of the grammar.
     pub fn rules(&self) -> &[Rule] {
         &self.rules
     }
 }
 
+impl Grammar {
+    /// Gets the augmented rule (e.g., `S' -> S $`) of the grammar.
+    ///
+    /// It's not one of the rules of the grammar, it's only used by the automaton
+    /// to accept the input once the start symbol is followed by the end of file.
+    pub fn augmented_rule(&self) -> Rule {
+        Rule::new(
+            format_smolstr!("{}'", self.start_symbol),
+            [AtomicPattern::Symbol(self.start_symbol.clone()), AtomicPattern::Token(Token::Eof)],
+        )
+    }
+
+    /// Gets the precedence of a rule of the grammar.
+    ///
+    /// It's the precedence declared with `%prec` if there is one,
+    /// otherwise it's the precedence of the last token in the pattern of the rule.
+    pub fn rule_precedence(&self, rule: &Rule) -> Option<Precedence> {
+        match rule.precedence() {
+            Some(precedence) => self.precedences.get(precedence).copied(),
+            None => {
+                let last_token = rule.pattern().iter().rev().find(|atomic_pattern| {
+                    matches!(
+                        atomic_pattern,
+                        AtomicPattern::Token(Token::Constant(_) | Token::Regex(_))
+                    )
+                })?;
+                self.precedences.get(last_token).copied()
+            },
+        }
+    }
+}
+
 #[cfg(feature = "wasm")]
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
 impl Grammar {
@@ -330,6 +610,48 @@ impl Grammar {
         Ok(serde_wasm_bindgen::to_value(&index_map)?)
     }
 
+    /// Gets the ignored regular expressions of the grammar (WASM).
+    pub fn ignored_regular_expressions_wasm(&self) -> Result<JsValue, JsValue> {
+        let regexes: Vec<String> =
+            self.ignored_regular_expressions().iter().map(|regex| regex.to_string()).collect();
+        Ok(serde_wasm_bindgen::to_value(&regexes)?)
+    }
+
+    /// Gets the lexer modes of the grammar (WASM).
+    pub fn lexer_modes_wasm(&self) -> Result<JsValue, JsValue> {
+        Ok(serde_wasm_bindgen::to_value(&self.lexer_modes)?)
+    }
+
+    /// Gets the generated symbols of the grammar (WASM).
+    pub fn generated_symbols_wasm(&self) -> Result<JsValue, JsValue> {
+        Ok(serde_wasm_bindgen::to_value(&self.generated_symbols)?)
+    }
+
+    /// Gets the repetition symbols of the grammar (WASM).
+    pub fn repetition_symbols_wasm(&self) -> Result<JsValue, JsValue> {
+        Ok(serde_wasm_bindgen::to_value(&self.repetition_symbols)?)
+    }
+
+    /// Gets the priorities of the regular expression tokens of the grammar (WASM).
+    pub fn priorities_wasm(&self) -> Result<JsValue, JsValue> {
+        Ok(serde_wasm_bindgen::to_value(&self.priorities)?)
+    }
+
+    /// Gets the keywords of the grammar (WASM).
+    pub fn keywords_wasm(&self) -> Result<JsValue, JsValue> {
+        Ok(serde_wasm_bindgen::to_value(&self.keywords)?)
+    }
+
+    /// Gets the reserved words of the regular expression tokens of the grammar (WASM).
+    pub fn reserved_words_wasm(&self) -> Result<JsValue, JsValue> {
+        Ok(serde_wasm_bindgen::to_value(&self.reserved_words)?)
+    }
+
+    /// Gets the precedences of the grammar (WASM).
+    pub fn precedences_wasm(&self) -> Result<JsValue, JsValue> {
+        Ok(serde_wasm_bindgen::to_value(&self.precedences)?)
+    }
+
     /// Clones the grammar (WASM).
     pub fn clone_wasm(&self) -> Grammar {
         self.clone()
@@ -338,8 +660,48 @@ impl Grammar {
 
 impl Display for Grammar {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for rule in self.rules.iter() {
-            writeln!(f, "{}", rule)?;
+        // Rules of the generated symbols are not formatted, as the generated symbols are named
+        // after their patterns (e.g., `Array -> '[' (Value (',' Value)*)? ']'`), so parsing
+        // the formatted grammar generates the same rules.
+        let mut index = 0;
+        while index < self.rules.len() {
+            let rule = &self.rules[index];
+            if self.generated_symbols.contains(rule.symbol()) {
+                index += 1;
+                continue;
+            }
+
+            // Rules of the generated symbols come after all the alternatives of the rule that
+            // generates them, so alternatives are formatted on a single line in that case
+            // (e.g., `Statement -> 'let' %id ('=' Expression)? ';' | Expression ';'`).
+            let mut end = index + 1;
+            while end < self.rules.len()
+                && self.rules[end].symbol() == rule.symbol()
+                && self.rules[end - 1].precedence().is_none()
+            {
+                end += 1;
+            }
+            let generates = self
+                .rules
+                .get(end)
+                .is_some_and(|next_rule| self.generated_symbols.contains(next_rule.symbol()));
+            if !generates {
+                end = index + 1;
+            }
+
+            write!(f, "{}", rule)?;
+            for alternative in self.rules[index + 1..end].iter() {
+                write!(f, " |")?;
+                for atomic_pattern in alternative.pattern().iter() {
+                    write!(f, " {}", atomic_pattern)?;
+                }
+                if let Some(precedence) = alternative.precedence() {
+                    write!(f, " %prec {}", precedence)?;
+                }
+            }
+            writeln!(f)?;
+
+            index = end;
         }
         if !self.regular_expressions.is_empty() {
             writeln!(f)?;
@@ -347,10 +709,172 @@ impl Display for Grammar {
         for (regex_token, regex) in self.regular_expressions.iter() {
             writeln!(f, "{} -> /{}/", regex_token, regex)?;
         }
+        let token_declarations = self.token_declarations();
+        if !token_declarations.is_empty() {
+            writeln!(f)?;
+        }
+        for declaration in token_declarations {
+            writeln!(f, "{}", declaration)?;
+        }
+        if !self.ignored_regular_expressions().is_empty()
+            || self.significant_whitespace
+            || self.indentation_sensitive
+        {
+            writeln!(f)?;
+        }
+        for regex in self.ignored_regular_expressions().iter() {
+            writeln!(f, "%ignore -> /{}/", regex)?;
+        }
+        if self.significant_whitespace {
+            writeln!(f, "%significant_whitespace")?;
+        }
+        if self.indentation_sensitive {
+            writeln!(f, "%indentation")?;
+        }
+        let lexer_mode_declarations = self.lexer_mode_declarations();
+        if !lexer_mode_declarations.is_empty() {
+            writeln!(f)?;
+        }
+        for declaration in lexer_mode_declarations {
+            writeln!(f, "{}", declaration)?;
+        }
+        let precedence_declarations = self.precedence_declarations();
+        if !precedence_declarations.is_empty() {
+            writeln!(f)?;
+        }
+        for declaration in precedence_declarations {
+            writeln!(f, "{}", declaration)?;
+        }
         Ok(())
     }
 }
 
+impl Grammar {
+    /// Gets the scanner of the tokens of the default lexer mode of the grammar.
+    pub(crate) fn scanner(&self) -> &Scanner {
+        self.lexer_modes[0].scanner()
+    }
+
+    /// Gets all the tokens that can be in the input of the grammar.
+    ///
+    /// It's the constant tokens, the regular expression tokens, the layout tokens and
+    /// the error token if they are used in the rules, and the end of file token, in that order.
+    pub(crate) fn input_tokens(&self) -> Vec<Token> {
+        let is_used = |token: &Token| {
+            self.rules
+                .iter()
+                .any(|rule| rule.pattern().contains(&AtomicPattern::Token(token.clone())))
+        };
+
+        self.constant_tokens
+            .iter()
+            .cloned()
+            .map(Token::Constant)
+            .chain(self.regular_expressions.keys().cloned().map(Token::Regex))
+            .chain(
+                [Token::Indent, Token::Dedent, Token::Newline, Token::Error]
+                    .into_iter()
+                    .filter(is_used),
+            )
+            .chain(std::iter::once(Token::Eof))
+            .collect()
+    }
+
+    /// Formats the priority, keyword and reserved word declarations of the grammar
+    /// (e.g., `%priority %hex`, `%keyword 'if'`, `%reserved %identifier -> 'if'`) in order.
+    pub(crate) fn token_declarations(&self) -> Vec<String> {
+        // Tokens are grouped by their priorities, as they are not necessarily adjacent.
+        let mut priorities = BTreeMap::<usize, Vec<&RegexToken>>::new();
+        for (regex_token, priority) in self.priorities.iter() {
+            priorities.entry(*priority).or_default().push(regex_token);
+        }
+        let mut declarations = priorities
+            .into_values()
+            .map(|regex_tokens| format!("%priority {}", regex_tokens.iter().join(" ")))
+            .collect::<Vec<_>>();
+        if !self.keywords.is_empty() {
+            declarations.push(format!("%keyword {}", self.keywords.iter().join(" ")));
+        }
+        for (regex_token, reserved_words) in self.reserved_words.iter() {
+            declarations.push(format!(
+                "%reserved {} -> {}",
+                regex_token,
+                reserved_words.iter().join(" ")
+            ));
+        }
+        declarations
+    }
+
+    /// Formats the lexer mode declarations of the grammar (e.g., `%mode String -> %chars '"'`).
+    ///
+    /// Tokens of the default mode are only declared if they are scanned in other modes as well,
+    /// as the default mode scans the tokens that are not declared in any mode.
+    pub(crate) fn lexer_mode_declarations(&self) -> Vec<String> {
+        let mut declarations = vec![];
+        if self.lexer_modes.len() == 1 {
+            return declarations;
+        }
+
+        for (mode_index, (mode, lexer_mode)) in self.lexer_modes.iter().enumerate() {
+            let tokens = if mode_index == 0 {
+                lexer_mode
+                    .tokens()
+                    .iter()
+                    .filter(|token| {
+                        self.lexer_modes
+                            .values()
+                            .skip(1)
+                            .any(|other| other.tokens().contains(token))
+                    })
+                    .collect::<Vec<_>>()
+            } else {
+                lexer_mode.tokens().iter().collect()
+            };
+            if !tokens.is_empty() {
+                declarations.push(format!("%mode {} -> {}", mode, tokens.iter().join(" ")));
+            }
+        }
+        for (mode, lexer_mode) in self.lexer_modes.iter().skip(1) {
+            for regex in lexer_mode.ignored_regular_expressions() {
+                declarations.push(format!("%ignore {} -> /{}/", mode, regex));
+            }
+        }
+        for (mode, lexer_mode) in self.lexer_modes.iter() {
+            for (token, transition) in lexer_mode.transitions() {
+                declarations.push(match transition {
+                    LexerModeTransition::Push(next_mode) => {
+                        format!("%push {} {} -> {}", mode, token, next_mode)
+                    },
+                    LexerModeTransition::Pop => format!("%pop {} {}", mode, token),
+                });
+            }
+        }
+
+        declarations
+    }
+
+    /// Formats the precedence declarations of the grammar (e.g., `%left '+' '-'`) in order.
+    ///
+    /// Tokens are grouped by their levels, as the tokens of a level are not necessarily adjacent
+    /// (e.g., when a token is declared again in a later level).
+    pub(crate) fn precedence_declarations(&self) -> Vec<String> {
+        let mut levels = BTreeMap::<usize, (Associativity, Vec<&AtomicPattern>)>::new();
+        for (atomic_pattern, precedence) in self.precedences.iter() {
+            levels
+                .entry(precedence.level())
+                .or_insert_with(|| (precedence.associativity(), vec![]))
+                .1
+                .push(atomic_pattern);
+        }
+        levels
+            .into_values()
+            .map(|(associativity, atomic_patterns)| {
+                format!("{} {}", associativity, atomic_patterns.iter().join(" "))
+            })
+            .collect()
+    }
+}
+
 
 /// Internal module to parse grammar strings.
 mod grammar_parser {
@@ -368,16 +892,106 @@ mod grammar_parser {
         #[token("->")]
         Arrow,
 
-        /// '+', '-', ',', ...
+        /// '+', '-', ',', ... (and ε, which is how empty patterns are formatted, same as '')
         #[regex(r#"'([^'])*'"#, |lexer| ConstantToken::from(lexer.slice().trim_matches('\'')))]
+        #[token("ε", |_| ConstantToken::from(""))]
         ConstantToken(ConstantToken),
 
         /// %d, %f, %s, ...
         #[regex("%[a-zA-Z0-9]+", |lexer| RegexToken::from(&lexer.slice()[1..]))]
         RegexToken(RegexToken),
 
-        /// /\[0-9]+/, /\[a-z]+/, ...
-        #[regex(r#"/([^/])*/"#, |lexer| SmolStr::from(lexer.slice().trim_matches('/')))]
+        /// %left, %right, %nonassoc
+        #[token("%left", |_| Associativity::Left)]
+        #[token("%right", |_| Associativity::Right)]
+        #[token("%nonassoc", |_| Associativity::NonAssociative)]
+        Associativity(Associativity),
+
+        /// %prec
+        #[token("%prec")]
+        Prec,
+
+        /// %error
+        #[token("%error")]
+        Error,
+
+        /// %ignore
+        #[token("%ignore")]
+        Ignore,
+
+        /// %significant_whitespace
+        #[token("%significant_whitespace")]
+        SignificantWhitespace,
+
+        /// %indentation
+        #[token("%indentation")]
+        Indentation,
+
+        /// %indent
+        #[token("%indent")]
+        Indent,
+
+        /// %dedent
+        #[token("%dedent")]
+        Dedent,
+
+        /// %newline
+        #[token("%newline")]
+        Newline,
+
+        /// %priority
+        #[token("%priority")]
+        Priority,
+
+        /// %keyword
+        #[token("%keyword")]
+        Keyword,
+
+        /// %reserved
+        #[token("%reserved")]
+        Reserved,
+
+        /// %mode
+        #[token("%mode")]
+        Mode,
+
+        /// %push
+        #[token("%push")]
+        Push,
+
+        /// %pop
+        #[token("%pop")]
+        Pop,
+
+        /// |
+        #[token("|")]
+        Alternative,
+
+        /// (
+        #[token("(")]
+        OpenParenthesis,
+
+        /// )
+        #[token(")")]
+        CloseParenthesis,
+
+        /// ?
+        #[token("?")]
+        Optional,
+
+        /// *
+        #[token("*")]
+        ZeroOrMore,
+
+        /// +
+        #[token("+")]
+        OneOrMore,
+
+        /// /\[0-9]+/, /\[a-z]+/, /\/\/.*/, ...
+        #[regex(r#"/([^/\\]|\\.)*/"#, |lexer| {
+            let slice = lexer.slice();
+            SmolStr::from(&slice[1..slice.len() - 1])
+        })]
         Regex(SmolStr),
 
         /// \n
@@ -389,20 +1003,76 @@ mod grammar_parser {
         Comment,
     }
 
+    /// Pattern of a rule before desugaring (e.g., `Value`, `(',' Value)*`, `'a' | 'b'`).
+    #[derive(Debug)]
+    enum Pattern {
+        Atomic(AtomicPattern),
+        Group(Alternatives),
+        Optional(Box<Pattern>),
+        ZeroOrMore(Box<Pattern>),
+        OneOrMore(Box<Pattern>),
+    }
+
+    impl Display for Pattern {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Pattern::Atomic(atomic_pattern) => write!(f, "{}", atomic_pattern),
+                Pattern::Group(alternatives) => {
+                    if alternatives.len() == 1 && alternatives[0].len() == 1 {
+                        return write!(f, "{}", alternatives[0][0]);
+                    }
+                    write!(
+                        f,
+                        "({})",
+                        alternatives.iter().map(|sequence| sequence.iter().join(" ")).join(" | "),
+                    )
+                },
+                Pattern::Optional(pattern) => write!(f, "{}?", pattern),
+                Pattern::ZeroOrMore(pattern) => write!(f, "{}*", pattern),
+                Pattern::OneOrMore(pattern) => write!(f, "{}+", pattern),
+            }
+        }
+    }
+
+    /// Alternatives of a rule or a group, each of which is a sequence of patterns.
+    type Alternatives = Vec<Vec<Pattern>>;
+
     #[derive(Debug)]
     #[allow(clippy::enum_variant_names)]
     enum GrammarParsingState {
         AwaitingSymbolOrRegexToken,
 
         AwaitingArrowForRule { symbol: Symbol },
-        AwaitingAtomicPatterns { symbol: Symbol, pattern: SmallVec<[AtomicPattern; 3]> },
+        AwaitingAtomicPatterns { symbol: Symbol, groups: Vec<Alternatives> },
+        AwaitingRulePrecedence { symbol: Symbol, alternatives: Alternatives },
+        AwaitingEndOfRule { symbol: Symbol, alternatives: Alternatives, precedence: AtomicPattern },
+
+        AwaitingPrecedenceTokens { associativity: Associativity, tokens: Vec<AtomicPattern> },
 
         AwaitingArrowForRegex { regex_token: RegexToken },
         AwaitingRegex { regex_token: RegexToken },
+
+        AwaitingPriorityTokens { tokens: Vec<RegexToken> },
+        AwaitingKeywords { keywords: Vec<ConstantToken> },
+        AwaitingRegexTokenForReservedWords,
+        AwaitingArrowForReservedWords { regex_token: RegexToken },
+        AwaitingReservedWords { regex_token: RegexToken, words: Vec<ConstantToken> },
+
+        AwaitingArrowForIgnoredRegex { mode: Option<SmolStr> },
+        AwaitingIgnoredRegex { mode: Option<SmolStr> },
+
+        AwaitingLexerMode,
+        AwaitingArrowForLexerMode { mode: SmolStr },
+        AwaitingLexerModeTokens { mode: SmolStr, tokens: Vec<Token> },
+
+        AwaitingLexerModeForTransition { push: bool },
+        AwaitingTokenForTransition { push: bool, mode: SmolStr },
+        AwaitingArrowForPushTransition { mode: SmolStr, token: Token },
+        AwaitingPushedLexerMode { mode: SmolStr, token: Token },
     }
 
     impl GrammarParsingState {
-        pub fn unexpected_token(&self, lexer: Lexer<GrammarToken>) -> GrammarError {
+        pub fn unexpected_token(&self, lexer: LogosLexer<GrammarToken>) -> GrammarError {
             let (line, column) = lexer.extras;
             let slice = lexer.slice();
             let token = if slice == "\n" { "\\n".into() } else { slice.into() };
@@ -420,19 +1090,79 @@ mod grammar_parser {
                 GrammarParsingState::AwaitingSymbolOrRegexToken => {
                     expected.push("symbol".into());
                     expected.push("regular expression token".into());
+                    expected.push("precedence declaration".into());
+                    expected.push("ignore declaration".into());
+                    expected.push("priority declaration".into());
+                    expected.push("keyword declaration".into());
+                    expected.push("reserved words declaration".into());
+                    expected.push("lexer mode declaration".into());
                 },
 
                 GrammarParsingState::AwaitingArrowForRule { .. }
-                | GrammarParsingState::AwaitingArrowForRegex { .. } => {
+                | GrammarParsingState::AwaitingArrowForRegex { .. }
+                | GrammarParsingState::AwaitingArrowForIgnoredRegex { mode: Some(_) }
+                | GrammarParsingState::AwaitingArrowForReservedWords { .. }
+                | GrammarParsingState::AwaitingArrowForLexerMode { .. }
+                | GrammarParsingState::AwaitingArrowForPushTransition { .. } => {
+                    expected.push("'->'".into());
+                },
+                GrammarParsingState::AwaitingArrowForIgnoredRegex { mode: None } => {
+                    expected.push("lexer mode".into());
                     expected.push("'->'".into());
                 },
+                GrammarParsingState::AwaitingLexerMode
+                | GrammarParsingState::AwaitingLexerModeForTransition { .. }
+                | GrammarParsingState::AwaitingPushedLexerMode { .. } => {
+                    expected.push("lexer mode".into());
+                },
+                GrammarParsingState::AwaitingLexerModeTokens { tokens, .. } => {
+                    expected.push("constant token".into());
+                    expected.push("regular expression token".into());
+                    if !tokens.is_empty() {
+                        expected.push("\\n".into());
+                    }
+                },
+                GrammarParsingState::AwaitingPriorityTokens { tokens } => {
+                    expected.push("regular expression token".into());
+                    if !tokens.is_empty() {
+                        expected.push("\\n".into());
+                    }
+                },
+                GrammarParsingState::AwaitingKeywords { keywords: words }
+                | GrammarParsingState::AwaitingReservedWords { words, .. } => {
+                    expected.push("constant token".into());
+                    if !words.is_empty() {
+                        expected.push("\\n".into());
+                    }
+                },
+                GrammarParsingState::AwaitingRegexTokenForReservedWords => {
+                    expected.push("regular expression token".into());
+                },
+                GrammarParsingState::AwaitingTokenForTransition { .. } => {
+                    expected.push("constant token".into());
+                    expected.push("regular expression token".into());
+                },
 
-                GrammarParsingState::AwaitingAtomicPatterns { .. } => {
+                GrammarParsingState::AwaitingAtomicPatterns { groups, .. } => {
                     expected.push("symbol".into());
                     expected.push("constant token".into());
                     expected.push("regular expression token".into());
+                    expected.push("'('".into());
+                    if groups.len() > 1 {
+                        expected.push("')'".into());
+                    }
+                },
+                GrammarParsingState::AwaitingRulePrecedence { .. }
+                | GrammarParsingState::AwaitingPrecedenceTokens { .. } => {
+                    expected.push("symbol".into());
+                    expected.push("constant token".into());
+                    expected.push("regular expression token".into());
+                },
+                GrammarParsingState::AwaitingEndOfRule { .. } => {
+                    expected.push("\\n".into());
                 },
-                GrammarParsingState::AwaitingRegex { .. } => {
+                GrammarParsingState::AwaitingRegex { .. }
+                | GrammarParsingState::AwaitingIgnoredRegex { .. } => {
                     expected.push("regular expression".into());
                 },
             };
@@ -440,7 +1170,7 @@ mod grammar_parser {
             GrammarError::UnexpectedEof { expected }
         }
 
-        pub fn unexpected_regex(&self, lexer: Lexer<GrammarToken>) -> GrammarError {
+        pub fn unexpected_regex(&self, lexer: LogosLexer<GrammarToken>) -> GrammarError {
             let (line, column) = lexer.extras;
             let slice = lexer.slice();
             let regex = slice.into();
@@ -458,6 +1188,20 @@ mod grammar_parser {
         let mut empty_symbols = IndexSet::new();
         let mut constant_tokens = IndexSet::new();
         let mut regular_expressions = IndexMap::new();
+        let mut priorities = IndexMap::new();
+        let mut priority_level = 0;
+        let mut keywords = IndexSet::new();
+        let mut reserved_words = IndexMap::<RegexToken, IndexSet<ConstantToken>>::new();
+        let mut significant_whitespace = false;
+        let mut indentation_sensitive = false;
+        let mut lexer_mode_tokens = IndexMap::<SmolStr, Vec<Token>>::new();
+        let mut lexer_mode_ignored_regular_expressions = IndexMap::<SmolStr, Vec<Regex>>::new();
+        let mut lexer_mode_transitions =
+            IndexMap::<SmolStr, IndexMap<Token, LexerModeTransition>>::new();
+        let mut precedences = IndexMap::new();
+        let mut precedence_level = 0;
+        let mut generated_symbols = IndexSet::new();
+        let mut repetition_symbols = IndexSet::new();
         let mut rules = Vec::new();
 
         let mut line = 1;
@@ -494,6 +1238,39 @@ mod grammar_parser {
                         GrammarToken::RegexToken(regex_token) => {
                             state = GrammarParsingState::AwaitingArrowForRegex { regex_token };
                         },
+                        GrammarToken::Associativity(associativity) => {
+                            state = GrammarParsingState::AwaitingPrecedenceTokens {
+                                associativity,
+                                tokens: vec![],
+                            };
+                        },
+                        GrammarToken::Ignore => {
+                            state =
+                                GrammarParsingState::AwaitingArrowForIgnoredRegex { mode: None };
+                        },
+                        GrammarToken::SignificantWhitespace => {
+                            significant_whitespace = true;
+                        },
+                        GrammarToken::Indentation => {
+                            indentation_sensitive = true;
+                        },
+                        GrammarToken::Priority => {
+                            state = GrammarParsingState::AwaitingPriorityTokens { tokens: vec![] };
+                        },
+                        GrammarToken::Keyword => {
+                            state = GrammarParsingState::AwaitingKeywords { keywords: vec![] };
+                        },
+                        GrammarToken::Reserved => {
+                            state = GrammarParsingState::AwaitingRegexTokenForReservedWords;
+                        },
+                        GrammarToken::Mode => {
+                            state = GrammarParsingState::AwaitingLexerMode;
+                        },
+                        GrammarToken::Push | GrammarToken::Pop => {
+                            state = GrammarParsingState::AwaitingLexerModeForTransition {
+                                push: token == GrammarToken::Push,
+                            };
+                        },
                         _ => {
                             return Err(state.unexpected_token(lexer));
                         },
@@ -505,7 +1282,7 @@ mod grammar_parser {
                         GrammarToken::Arrow => {
                             state = GrammarParsingState::AwaitingAtomicPatterns {
                                 symbol: symbol.clone(),
-                                pattern: smallvec![],
+                                groups: vec![vec![vec![]]],
                             };
                         },
                         _ => {
@@ -525,38 +1302,302 @@ mod grammar_parser {
                         },
                     }
                 },
+                GrammarParsingState::AwaitingArrowForIgnoredRegex { mode } => {
+                    match token {
+                        GrammarToken::Symbol(symbol) if mode.is_none() => {
+                            *mode = Some(SmolStr::from(symbol.as_str()));
+                        },
+                        GrammarToken::Arrow => {
+                            state = GrammarParsingState::AwaitingIgnoredRegex { mode: mode.take() };
+                        },
+                        _ => {
+                            return Err(state.unexpected_token(lexer));
+                        },
+                    }
+                },
+                GrammarParsingState::AwaitingArrowForReservedWords { regex_token } => {
+                    match token {
+                        GrammarToken::Arrow => {
+                            state = GrammarParsingState::AwaitingReservedWords {
+                                regex_token: regex_token.clone(),
+                                words: vec![],
+                            };
+                        },
+                        _ => {
+                            return Err(state.unexpected_token(lexer));
+                        },
+                    }
+                },
+                GrammarParsingState::AwaitingArrowForLexerMode { mode } => {
+                    match token {
+                        GrammarToken::Arrow => {
+                            state = GrammarParsingState::AwaitingLexerModeTokens {
+                                mode: std::mem::take(mode),
+                                tokens: vec![],
+                            };
+                        },
+                        _ => {
+                            return Err(state.unexpected_token(lexer));
+                        },
+                    }
+                },
+                GrammarParsingState::AwaitingArrowForPushTransition { mode, token: pushing } => {
+                    match token {
+                        GrammarToken::Arrow => {
+                            state = GrammarParsingState::AwaitingPushedLexerMode {
+                                mode: std::mem::take(mode),
+                                token: pushing.clone(),
+                            };
+                        },
+                        _ => {
+                            return Err(state.unexpected_token(lexer));
+                        },
+                    }
+                },
 
-                GrammarParsingState::AwaitingAtomicPatterns { symbol, pattern } => {
+                GrammarParsingState::AwaitingAtomicPatterns { symbol, groups } => {
+                    let in_group = groups.len() > 1;
+                    let alternatives = groups.last_mut().unwrap();
+                    let sequence = alternatives.last_mut().unwrap();
                     match token {
                         GrammarToken::Comment => {},
                         GrammarToken::Symbol(symbol) => {
-                            pattern.push(AtomicPattern::Symbol(symbol));
+                            sequence.push(Pattern::Atomic(AtomicPattern::Symbol(symbol)));
                         },
                         GrammarToken::ConstantToken(constant_token) => {
                             if constant_token.is_empty() {
-                                pattern.push(AtomicPattern::Token(Token::Empty));
+                                sequence.push(Pattern::Atomic(AtomicPattern::Token(Token::Empty)));
                             } else {
                                 constant_tokens.insert(constant_token.clone());
-                                pattern.push(AtomicPattern::Token(Token::Constant(constant_token)));
+                                sequence.push(Pattern::Atomic(AtomicPattern::Token(
+                                    Token::Constant(constant_token),
+                                )));
                             }
                         },
                         GrammarToken::RegexToken(regex_token) => {
-                            pattern.push(AtomicPattern::Token(Token::Regex(regex_token)));
+                            sequence.push(Pattern::Atomic(AtomicPattern::Token(Token::Regex(
+                                regex_token,
+                            ))));
+                        },
+                        GrammarToken::Error => {
+                            sequence.push(Pattern::Atomic(AtomicPattern::Token(Token::Error)));
+                        },
+                        GrammarToken::Indent => {
+                            sequence.push(Pattern::Atomic(AtomicPattern::Token(Token::Indent)));
+                        },
+                        GrammarToken::Dedent => {
+                            sequence.push(Pattern::Atomic(AtomicPattern::Token(Token::Dedent)));
+                        },
+                        GrammarToken::Newline => {
+                            sequence.push(Pattern::Atomic(AtomicPattern::Token(Token::Newline)));
+                        },
+                        GrammarToken::Alternative => {
+                            if sequence.is_empty() {
+                                return Err(state.unexpected_token(lexer));
+                            }
+                            alternatives.push(vec![]);
+                        },
+                        GrammarToken::OpenParenthesis => {
+                            groups.push(vec![vec![]]);
+                        },
+                        GrammarToken::CloseParenthesis => {
+                            if !in_group || sequence.is_empty() {
+                                return Err(state.unexpected_token(lexer));
+                            }
+                            let group = groups.pop().unwrap();
+                            groups
+                                .last_mut()
+                                .unwrap()
+                                .last_mut()
+                                .unwrap()
+                                .push(Pattern::Group(group));
+                        },
+                        GrammarToken::Optional
+                        | GrammarToken::ZeroOrMore
+                        | GrammarToken::OneOrMore => {
+                            let pattern = match sequence.pop() {
+                                Some(pattern) => Box::new(pattern),
+                                None => return Err(state.unexpected_token(lexer)),
+                            };
+                            sequence.push(match token {
+                                GrammarToken::Optional => Pattern::Optional(pattern),
+                                GrammarToken::ZeroOrMore => Pattern::ZeroOrMore(pattern),
+                                _ => Pattern::OneOrMore(pattern),
+                            });
                         },
                         GrammarToken::NewLine => {
-                            if pattern.is_empty() {
+                            if in_group || sequence.is_empty() {
                                 return Err(state.unexpected_token(lexer));
                             }
 
-                            let rule = Rule {
+                            desugar(
+                                std::mem::replace(symbol, Symbol::from("")),
+                                groups.pop().unwrap(),
+                                None,
+                                &mut symbols,
+                                &mut generated_symbols,
+                                &mut repetition_symbols,
+                                &mut rules,
+                            );
+
+                            state = GrammarParsingState::AwaitingSymbolOrRegexToken;
+                        },
+                        GrammarToken::Prec => {
+                            if in_group || sequence.is_empty() {
+                                return Err(state.unexpected_token(lexer));
+                            }
+
+                            state = GrammarParsingState::AwaitingRulePrecedence {
                                 symbol: std::mem::replace(symbol, Symbol::from("")),
-                                pattern: std::mem::take(pattern),
+                                alternatives: groups.pop().unwrap(),
                             };
-                            rules.push(rule);
+                        },
+
+                        _ => {
+                            return Err(state.unexpected_token(lexer));
+                        },
+                    }
+                },
+                GrammarParsingState::AwaitingRulePrecedence { symbol, alternatives } => {
+                    let precedence = match token {
+                        GrammarToken::Symbol(symbol) => AtomicPattern::Symbol(symbol),
+                        GrammarToken::ConstantToken(constant_token) => {
+                            AtomicPattern::Token(Token::Constant(constant_token))
+                        },
+                        GrammarToken::RegexToken(regex_token) => {
+                            AtomicPattern::Token(Token::Regex(regex_token))
+                        },
+                        _ => {
+                            return Err(state.unexpected_token(lexer));
+                        },
+                    };
+                    state = GrammarParsingState::AwaitingEndOfRule {
+                        symbol: std::mem::replace(symbol, Symbol::from("")),
+                        alternatives: std::mem::take(alternatives),
+                        precedence,
+                    };
+                },
+                GrammarParsingState::AwaitingEndOfRule { symbol, alternatives, precedence } => {
+                    match token {
+                        GrammarToken::Comment => {},
+                        GrammarToken::NewLine => {
+                            desugar(
+                                std::mem::replace(symbol, Symbol::from("")),
+                                std::mem::take(alternatives),
+                                Some(precedence.clone()),
+                                &mut symbols,
+                                &mut generated_symbols,
+                                &mut repetition_symbols,
+                                &mut rules,
+                            );
+                            state = GrammarParsingState::AwaitingSymbolOrRegexToken;
+                        },
+                        _ => {
+                            return Err(state.unexpected_token(lexer));
+                        },
+                    }
+                },
+
+                GrammarParsingState::AwaitingPrecedenceTokens { associativity, tokens } => {
+                    match token {
+                        GrammarToken::Comment => {},
+                        GrammarToken::Symbol(symbol) => {
+                            tokens.push(AtomicPattern::Symbol(symbol));
+                        },
+                        GrammarToken::ConstantToken(constant_token)
+                            if !constant_token.is_empty() =>
+                        {
+                            tokens.push(AtomicPattern::Token(Token::Constant(constant_token)));
+                        },
+                        GrammarToken::RegexToken(regex_token) => {
+                            tokens.push(AtomicPattern::Token(Token::Regex(regex_token)));
+                        },
+                        GrammarToken::NewLine => {
+                            if tokens.is_empty() {
+                                return Err(state.unexpected_token(lexer));
+                            }
+
+                            precedence_level += 1;
+                            let precedence = Precedence {
+                                level: precedence_level,
+                                associativity: *associativity,
+                            };
+                            for atomic_pattern in tokens.drain(..) {
+                                precedences.insert(atomic_pattern, precedence);
+                            }
+
+                            state = GrammarParsingState::AwaitingSymbolOrRegexToken;
+                        },
+                        _ => {
+                            return Err(state.unexpected_token(lexer));
+                        },
+                    }
+                },
+                GrammarParsingState::AwaitingPriorityTokens { tokens } => {
+                    match token {
+                        GrammarToken::Comment => {},
+                        GrammarToken::RegexToken(regex_token) => {
+                            tokens.push(regex_token);
+                        },
+                        GrammarToken::NewLine => {
+                            if tokens.is_empty() {
+                                return Err(state.unexpected_token(lexer));
+                            }
+
+                            priority_level += 1;
+                            for regex_token in tokens.drain(..) {
+                                priorities.insert(regex_token, priority_level);
+                            }
 
                             state = GrammarParsingState::AwaitingSymbolOrRegexToken;
                         },
+                        _ => {
+                            return Err(state.unexpected_token(lexer));
+                        },
+                    }
+                },
+                GrammarParsingState::AwaitingKeywords { keywords: words }
+                | GrammarParsingState::AwaitingReservedWords { words, .. } => {
+                    match token {
+                        GrammarToken::Comment => {},
+                        GrammarToken::ConstantToken(constant_token)
+                            if !constant_token.is_empty() =>
+                        {
+                            words.push(constant_token);
+                        },
+                        GrammarToken::NewLine => {
+                            if words.is_empty() {
+                                return Err(state.unexpected_token(lexer));
+                            }
 
+                            // Keywords and reserved words are scanned even if they are not in the rules.
+                            let words = std::mem::take(words);
+                            constant_tokens.extend(words.iter().cloned());
+                            match &state {
+                                GrammarParsingState::AwaitingReservedWords {
+                                    regex_token, ..
+                                } => {
+                                    reserved_words
+                                        .entry(regex_token.clone())
+                                        .or_default()
+                                        .extend(words);
+                                },
+                                _ => keywords.extend(words),
+                            }
+
+                            state = GrammarParsingState::AwaitingSymbolOrRegexToken;
+                        },
+                        _ => {
+                            return Err(state.unexpected_token(lexer));
+                        },
+                    }
+                },
+                GrammarParsingState::AwaitingRegexTokenForReservedWords => {
+                    match token {
+                        GrammarToken::RegexToken(regex_token) => {
+                            state =
+                                GrammarParsingState::AwaitingArrowForReservedWords { regex_token };
+                        },
                         _ => {
                             return Err(state.unexpected_token(lexer));
                         },
@@ -580,6 +1621,125 @@ mod grammar_parser {
                         },
                     }
                 },
+                GrammarParsingState::AwaitingIgnoredRegex { mode } => {
+                    match token {
+                        GrammarToken::Regex(regex_string) => {
+                            let regex =
+                                match Regex::new(format_smolstr!("^{}", regex_string).as_str()) {
+                                    Ok(regex) => regex,
+                                    Err(_) => {
+                                        return Err(state.unexpected_regex(lexer));
+                                    },
+                                };
+                            let mode = mode.take().unwrap_or(SmolStr::from(DEFAULT_LEXER_MODE));
+                            lexer_mode_ignored_regular_expressions
+                                .entry(mode)
+                                .or_default()
+                                .push(regex);
+                            state = GrammarParsingState::AwaitingSymbolOrRegexToken;
+                        },
+                        _ => {
+                            return Err(state.unexpected_token(lexer));
+                        },
+                    }
+                },
+
+                GrammarParsingState::AwaitingLexerMode => {
+                    match token {
+                        GrammarToken::Symbol(symbol) => {
+                            state = GrammarParsingState::AwaitingArrowForLexerMode {
+                                mode: SmolStr::from(symbol.as_str()),
+                            };
+                        },
+                        _ => {
+                            return Err(state.unexpected_token(lexer));
+                        },
+                    }
+                },
+                GrammarParsingState::AwaitingLexerModeTokens { mode, tokens } => {
+                    match token {
+                        GrammarToken::Comment => {},
+                        GrammarToken::ConstantToken(constant_token)
+                            if !constant_token.is_empty() =>
+                        {
+                            tokens.push(Token::Constant(constant_token));
+                        },
+                        GrammarToken::RegexToken(regex_token) => {
+                            tokens.push(Token::Regex(regex_token));
+                        },
+                        GrammarToken::NewLine => {
+                            if tokens.is_empty() {
+                                return Err(state.unexpected_token(lexer));
+                            }
+
+                            let declared_tokens =
+                                lexer_mode_tokens.entry(std::mem::take(mode)).or_default();
+                            for token in tokens.drain(..) {
+                                if !declared_tokens.contains(&token) {
+                                    declared_tokens.push(token);
+                                }
+                            }
+
+                            state = GrammarParsingState::AwaitingSymbolOrRegexToken;
+                        },
+                        _ => {
+                            return Err(state.unexpected_token(lexer));
+                        },
+                    }
+                },
+
+                GrammarParsingState::AwaitingLexerModeForTransition { push } => {
+                    match token {
+                        GrammarToken::Symbol(symbol) => {
+                            state = GrammarParsingState::AwaitingTokenForTransition {
+                                push: *push,
+                                mode: SmolStr::from(symbol.as_str()),
+                            };
+                        },
+                        _ => {
+                            return Err(state.unexpected_token(lexer));
+                        },
+                    }
+                },
+                GrammarParsingState::AwaitingTokenForTransition { push, mode } => {
+                    let token = match token {
+                        GrammarToken::ConstantToken(constant_token)
+                            if !constant_token.is_empty() =>
+                        {
+                            Token::Constant(constant_token)
+                        },
+                        GrammarToken::RegexToken(regex_token) => Token::Regex(regex_token),
+                        _ => {
+                            return Err(state.unexpected_token(lexer));
+                        },
+                    };
+                    if *push {
+                        state = GrammarParsingState::AwaitingArrowForPushTransition {
+                            mode: std::mem::take(mode),
+                            token,
+                        };
+                    } else {
+                        lexer_mode_transitions
+                            .entry(std::mem::take(mode))
+                            .or_default()
+                            .insert(token, LexerModeTransition::Pop);
+                        state = GrammarParsingState::AwaitingSymbolOrRegexToken;
+                    }
+                },
+                GrammarParsingState::AwaitingPushedLexerMode { mode, token: pushing } => {
+                    match token {
+                        GrammarToken::Symbol(symbol) => {
+                            lexer_mode_transitions.entry(std::mem::take(mode)).or_default().insert(
+                                pushing.clone(),
+                                LexerModeTransition::Push(SmolStr::from(symbol.as_str())),
+                            );
+                            state = GrammarParsingState::AwaitingSymbolOrRegexToken;
+                        },
+                        _ => {
+                            return Err(state.unexpected_token(lexer));
+                        },
+                    }
+                },
             }
 
             if newline {
@@ -591,16 +1751,68 @@ mod grammar_parser {
 
         match &mut state {
             GrammarParsingState::AwaitingSymbolOrRegexToken => {},
-            GrammarParsingState::AwaitingAtomicPatterns { symbol, pattern } => {
-                if pattern.is_empty() {
+            GrammarParsingState::AwaitingAtomicPatterns { symbol, groups } => {
+                if groups.len() != 1 || groups[0].last().unwrap().is_empty() {
                     return Err(state.unexpected_eof());
                 }
 
-                let rule = Rule {
-                    symbol: std::mem::replace(symbol, Symbol::from("")),
-                    pattern: std::mem::take(pattern),
-                };
-                rules.push(rule);
+                desugar(
+                    std::mem::replace(symbol, Symbol::from("")),
+                    groups.pop().unwrap(),
+                    None,
+                    &mut symbols,
+                    &mut generated_symbols,
+                    &mut repetition_symbols,
+                    &mut rules,
+                );
+            },
+            GrammarParsingState::AwaitingEndOfRule { symbol, alternatives, precedence } => {
+                desugar(
+                    std::mem::replace(symbol, Symbol::from("")),
+                    std::mem::take(alternatives),
+                    Some(precedence.clone()),
+                    &mut symbols,
+                    &mut generated_symbols,
+                    &mut repetition_symbols,
+                    &mut rules,
+                );
+            },
+            GrammarParsingState::AwaitingPrecedenceTokens { associativity, tokens } => {
+                if tokens.is_empty() {
+                    return Err(state.unexpected_eof());
+                }
+
+                precedence_level += 1;
+                let precedence =
+                    Precedence { level: precedence_level, associativity: *associativity };
+                for atomic_pattern in tokens.drain(..) {
+                    precedences.insert(atomic_pattern, precedence);
+                }
+            },
+            GrammarParsingState::AwaitingPriorityTokens { tokens } => {
+                if tokens.is_empty() {
+                    return Err(state.unexpected_eof());
+                }
+
+                priority_level += 1;
+                for regex_token in tokens.drain(..) {
+                    priorities.insert(regex_token, priority_level);
+                }
+            },
+            GrammarParsingState::AwaitingKeywords { keywords: words }
+            | GrammarParsingState::AwaitingReservedWords { words, .. } => {
+                if words.is_empty() {
+                    return Err(state.unexpected_eof());
+                }
+
+                let words = std::mem::take(words);
+                constant_tokens.extend(words.iter().cloned());
+                match &state {
+                    GrammarParsingState::AwaitingReservedWords { regex_token, .. } => {
+                        reserved_words.entry(regex_token.clone()).or_default().extend(words);
+                    },
+                    _ => keywords.extend(words),
+                }
             },
             _ => {
                 return Err(state.unexpected_eof());
@@ -608,6 +1820,9 @@ mod grammar_parser {
         }
 
         for rule in rules.iter_mut() {
+            if rule.pattern.iter().all(|atomic_pattern| *atomic_pattern == Token::Empty.into()) {
+                rule.pattern = smallvec![AtomicPattern::Token(Token::Empty)];
+            }
             if rule.pattern.as_slice() == [AtomicPattern::Token(Token::Empty)] {
                 empty_symbols.insert(rule.symbol.clone());
                 continue;
@@ -619,13 +1834,244 @@ mod grammar_parser {
             }
         }
 
-        Ok(Grammar {
+        let mut grammar = Grammar {
             symbols,
             start_symbol: start_symbol.unwrap_or(Symbol::from("")),
             empty_symbols,
             constant_tokens,
             regular_expressions,
+            priorities,
+            keywords,
+            reserved_words,
+            significant_whitespace,
+            indentation_sensitive,
+            lexer_modes: IndexMap::new(),
+            precedences,
+            generated_symbols,
+            repetition_symbols,
             rules,
-        })
+        };
+        grammar.lexer_modes = construct_lexer_modes(
+            &grammar,
+            lexer_mode_tokens,
+            lexer_mode_ignored_regular_expressions,
+            lexer_mode_transitions,
+        );
+        Ok(grammar)
+    }
+
+    /// Constructs the lexer modes of a grammar, starting with the default mode.
+    ///
+    /// Default mode scans the tokens that are not declared in any other mode, along with the tokens
+    /// declared in it explicitly. Modes that are referred to but not declared have no tokens.
+    fn construct_lexer_modes(
+        grammar: &Grammar,
+        mut tokens: IndexMap<SmolStr, Vec<Token>>,
+        mut ignored_regular_expressions: IndexMap<SmolStr, Vec<Regex>>,
+        mut transitions: IndexMap<SmolStr, IndexMap<Token, LexerModeTransition>>,
+    ) -> IndexMap<SmolStr, LexerMode> {
+        let mut modes = IndexSet::new();
+        modes.insert(SmolStr::from(DEFAULT_LEXER_MODE));
+        for (mode, mode_transitions) in transitions.iter() {
+            modes.insert(mode.clone());
+            for transition in mode_transitions.values() {
+                if let LexerModeTransition::Push(next_mode) = transition {
+                    modes.insert(next_mode.clone());
+                }
+            }
+        }
+        modes.extend(tokens.keys().cloned());
+        modes.extend(ignored_regular_expressions.keys().cloned());
+
+        let all_tokens = grammar
+            .constant_tokens
+            .iter()
+            .cloned()
+            .map(Token::Constant)
+            .chain(grammar.regular_expressions.keys().cloned().map(Token::Regex));
+        let default_tokens = all_tokens
+            .filter(|token| {
+                tokens.get(DEFAULT_LEXER_MODE).is_some_and(|tokens| tokens.contains(token))
+                    || !tokens
+                        .iter()
+                        .any(|(mode, tokens)| mode != DEFAULT_LEXER_MODE && tokens.contains(token))
+            })
+            .collect::<Vec<_>>();
+        tokens.insert(SmolStr::from(DEFAULT_LEXER_MODE), default_tokens);
+
+        modes
+            .into_iter()
+            .map(|mode| {
+                let tokens = tokens.swap_remove(&mode).unwrap_or_default();
+                let ignored_regular_expressions =
+                    ignored_regular_expressions.swap_remove(&mode).unwrap_or_default();
+                let transitions = transitions.swap_remove(&mode).unwrap_or_default();
+
+                let mut mode_constant_tokens = IndexSet::new();
+                let mut mode_regular_expressions = IndexMap::new();
+                for token in tokens.iter() {
+                    match token {
+                        Token::Constant(constant_token) => {
+                            mode_constant_tokens.insert(constant_token.clone());
+                        },
+                        Token::Regex(regex_token) => {
+                            if let Some(regex) = grammar.regular_expressions.get(regex_token) {
+                                mode_regular_expressions.insert(regex_token.clone(), regex.clone());
+                            }
+                        },
+                        _ => {},
+                    }
+                }
+                let scanner = Scanner::construct(
+                    &mode_constant_tokens,
+                    &mode_regular_expressions,
+                    &ignored_regular_expressions,
+                    grammar,
+                );
+
+                (mode, LexerMode { tokens, ignored_regular_expressions, transitions, scanner })
+            })
+            .collect()
+    }
+
+    /// Desugars the alternatives of a rule into ordinary rules.
+    ///
+    /// Explicit precedence applies to the last alternative (e.g., `E -> E '+' E | '-' E %prec U`).
+    fn desugar(
+        symbol: Symbol,
+        alternatives: Alternatives,
+        mut precedence: Option<AtomicPattern>,
+        symbols: &mut IndexSet<Symbol>,
+        generated_symbols: &mut IndexSet<Symbol>,
+        repetition_symbols: &mut IndexSet<Symbol>,
+        rules: &mut Vec<Rule>,
+    ) {
+        let mut generated_rules = Vec::new();
+
+        let number_of_alternatives = alternatives.len();
+        for (alternative_index, sequence) in alternatives.into_iter().enumerate() {
+            let pattern = sequence
+                .into_iter()
+                .map(|pattern| {
+                    desugar_pattern(
+                        pattern,
+                        symbols,
+                        generated_symbols,
+                        repetition_symbols,
+                        &mut generated_rules,
+                    )
+                })
+                .collect();
+            let precedence = if alternative_index == number_of_alternatives - 1 {
+                precedence.take()
+            } else {
+                None
+            };
+            rules.push(Rule { symbol: symbol.clone(), pattern, precedence });
+        }
+
+        rules.extend(generated_rules);
+    }
+
+    /// Desugars a pattern into an atomic pattern, generating rules for new symbols if necessary.
+    ///
+    /// Generated symbols are named after the pattern they represent (e.g., `(',' Value)*`),
+    /// which can't clash with user symbols and allows identical patterns to share rules.
+    /// It also allows the rules that use them to be formatted as the original pattern.
+    fn desugar_pattern(
+        pattern: Pattern,
+        symbols: &mut IndexSet<Symbol>,
+        generated_symbols: &mut IndexSet<Symbol>,
+        repetition_symbols: &mut IndexSet<Symbol>,
+        generated_rules: &mut Vec<Rule>,
+    ) -> AtomicPattern {
+        let pattern = match pattern {
+            Pattern::Atomic(atomic_pattern) => return atomic_pattern,
+            Pattern::Group(mut alternatives)
+                if alternatives.len() == 1 && alternatives[0].len() == 1 =>
+            {
+                let pattern = alternatives.pop().unwrap().pop().unwrap();
+                return desugar_pattern(
+                    pattern,
+                    symbols,
+                    generated_symbols,
+                    repetition_symbols,
+                    generated_rules,
+                );
+            },
+            pattern => pattern,
+        };
+
+        let symbol = Symbol::from(pattern.to_string());
+        if !symbols.insert(symbol.clone()) {
+            return AtomicPattern::Symbol(symbol);
+        }
+        generated_symbols.insert(symbol.clone());
+
+        // Rules of the generated symbol come before the rules of its own generated symbols.
+        let position = generated_rules.len();
+
+        // Groups are inlined into the rules (e.g., `(',' Value)* -> (',' Value)* ',' Value`).
+        let mut desugar_alternatives = |pattern| -> Vec<SmallVec<[AtomicPattern; 3]>> {
+            let alternatives = match pattern {
+                Pattern::Group(alternatives) => alternatives,
+                pattern => vec![vec![pattern]],
+            };
+            alternatives
+                .into_iter()
+                .map(|sequence| {
+                    sequence
+                        .into_iter()
+                        .map(|pattern| {
+                            desugar_pattern(
+                                pattern,
+                                symbols,
+                                generated_symbols,
+                                repetition_symbols,
+                                generated_rules,
+                            )
+                        })
+                        .collect()
+                })
+                .collect()
+        };
+
+        let alternatives = match pattern {
+            Pattern::Atomic(_) => unreachable!(),
+            Pattern::Group(alternatives) => desugar_alternatives(Pattern::Group(alternatives)),
+            Pattern::Optional(pattern) => {
+                let mut alternatives = desugar_alternatives(*pattern);
+                alternatives.push(smallvec![AtomicPattern::Token(Token::Empty)]);
+                alternatives
+            },
+            Pattern::ZeroOrMore(pattern) => {
+                let mut alternatives = desugar_alternatives(*pattern);
+                for sequence in alternatives.iter_mut() {
+                    sequence.insert(0, AtomicPattern::Symbol(symbol.clone()));
+                }
+                alternatives.push(smallvec![AtomicPattern::Token(Token::Empty)]);
+                repetition_symbols.insert(symbol.clone());
+                alternatives
+            },
+            Pattern::OneOrMore(pattern) => {
+                let base_alternatives = desugar_alternatives(*pattern);
+                let mut alternatives = base_alternatives.clone();
+                for sequence in alternatives.iter_mut() {
+                    sequence.insert(0, AtomicPattern::Symbol(symbol.clone()));
+                }
+                alternatives.extend(base_alternatives);
+                repetition_symbols.insert(symbol.clone());
+                alternatives
+            },
+        };
+
+        generated_rules.splice(
+            position..position,
+            alternatives
+                .into_iter()
+                .map(|pattern| Rule { symbol: symbol.clone(), pattern, precedence: None }),
+        );
+
+        AtomicPattern::Symbol(symbol)
     }
 }
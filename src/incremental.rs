@@ -0,0 +1,492 @@
+use crate::prelude::*;
+
+
+/// Edit of a text, which replaces a byte range of the text with a replacement.
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TextEdit {
+    /// Byte range of the text to replace.
+    pub range: std::ops::Range<usize>,
+    /// Replacement of the range.
+    pub replacement: SmolStr,
+}
+
+impl TextEdit {
+    /// Creates a new text edit.
+    pub fn new(range: std::ops::Range<usize>, replacement: impl Into<SmolStr>) -> TextEdit {
+        TextEdit { range, replacement: replacement.into() }
+    }
+}
+
+impl TextEdit {
+    /// Applies the edit to a text, returning the edited text.
+    pub fn apply(&self, text: &str) -> String {
+        let mut edited =
+            String::with_capacity(text.len() - self.range.len() + self.replacement.len());
+        edited.push_str(&text[..self.range.start]);
+        edited.push_str(&self.replacement);
+        edited.push_str(&text[self.range.end..]);
+        edited
+    }
+}
+
+
+/// Tokens of an edited input, along with the parts that are unchanged by the edit.
+struct Relexed<'i> {
+    /// Tokens of the edited input.
+    tokens: Vec<(Spanned<Token>, &'i str)>,
+    /// Index of the first token that is tokenized again, as the tokens before it are unchanged.
+    relex_start: usize,
+    /// Number of tokens at the start, which are the same as the old tokens.
+    prefix_length: usize,
+    /// Index of the first token at the end, which are the same as the old tokens after shifting.
+    suffix_start: usize,
+    /// Index of the old token corresponding to the first token at the end.
+    old_suffix_start: usize,
+    /// Span of the first token at the end.
+    suffix_span: Span,
+    /// Span of the old token corresponding to the first token at the end.
+    old_suffix_span: Span,
+    /// Old tokens that are replaced by the tokens that are tokenized again.
+    replaced_tokens: Vec<(Spanned<Token>, &'i str)>,
+}
+
+impl Relexed<'_> {
+    /// Gets the number of old tokens.
+    fn old_length(&self) -> usize {
+        self.old_suffix_start + self.tokens.len() - self.suffix_start
+    }
+
+    /// Gets an old token, along with its offset in the old input.
+    fn old_token(&self, index: usize) -> (&Token, usize) {
+        if index < self.relex_start {
+            let (token, _) = &self.tokens[index];
+            (token, token.span().offset)
+        } else if index < self.old_suffix_start {
+            let (token, _) = &self.replaced_tokens[index - self.relex_start];
+            (token, token.span().offset)
+        } else {
+            let (token, _) = &self.tokens[index - self.old_suffix_start + self.suffix_start];
+            (token, token.span().offset - self.suffix_span.offset + self.old_suffix_span.offset)
+        }
+    }
+
+    /// Gets the number of old tokens that start before an offset of the old input,
+    /// which is at most the index of an old token after the offset (e.g., the end of a subtree).
+    ///
+    /// Old tokens are searched backward from the index, since the offset is usually close to it.
+    fn old_tokens_before(&self, offset: usize, end: usize) -> usize {
+        let (mut low, mut high) = (end, end);
+        let mut step = 1;
+        while low > 0 && self.old_token(low - 1).1 >= offset {
+            high = low - 1;
+            low = low.saturating_sub(step);
+            step *= 2;
+        }
+        while low < high {
+            let middle = low + (high - low) / 2;
+            if self.old_token(middle).1 < offset {
+                low = middle + 1;
+            } else {
+                high = middle;
+            }
+        }
+        low
+    }
+}
+
+/// Subtree of the old parse tree, which is pending to be reused or broken down.
+struct Pending<'i> {
+    /// Old subtree.
+    tree: Tree<'i>,
+    /// Index of the first old token of the subtree.
+    start: usize,
+    /// Index of the old token right after the subtree.
+    end: usize,
+    /// State of the old parse right before the subtree, if it's known.
+    state: Option<usize>,
+}
+
+
+/// Reparses an edited input incrementally, using the parse tree and the tokens of the old input.
+#[allow(clippy::type_complexity)]
+pub(crate) fn reparse<'i>(
+    parser: &Parser,
+    tree: Tree<'i>,
+    tokens: Vec<(Spanned<Token>, &'i str)>,
+    edit: &TextEdit,
+    input: &'i str,
+) -> Result<(Vec<(Spanned<Token>, &'i str)>, Tree<'i>), ParsingError> {
+    let old_length = match tokens.last() {
+        Some((token, _)) if **token == Token::Eof => token.span().offset,
+        _ => panic!("old tokens don't end with the end of input token"),
+    };
+    assert!(
+        edit.range.start <= edit.range.end && edit.range.end <= old_length,
+        "edit is outside of the old input",
+    );
+    assert_eq!(
+        input.len(),
+        old_length - edit.range.len() + edit.replacement.len(),
+        "input is not the result of the edit",
+    );
+
+    // Tokens depend on the state of the lexer before them in these grammars,
+    // so the input is tokenized and parsed from scratch.
+    let grammar = parser.grammar();
+    if grammar.lexer_modes().len() > 1 || grammar.is_indentation_sensitive() {
+        let tokens = parser.tokenize(input)?;
+        let tree = parser.parse(tokens.clone())?;
+        return Ok((tokens, tree));
+    }
+
+    let relexed = relex_internal(parser, tokens, edit, input)?;
+    let tree = reparse_internal(parser, tree, &relexed)?;
+    Ok((relexed.tokens, tree))
+}
+
+/// Internal logic to tokenize an edited input in the region affected by the edit.
+///
+/// Tokenization starts right after the last token that the scanner doesn't look ahead of into
+/// the edit (e.g., `1e+` followed by `5` is a single number, so `1` is tokenized again), or at
+/// the start of the input if the lookahead of the scanner is not bounded. It stops once a token
+/// after the edit is the same as an old token. Remaining tokens are the old tokens with shifted
+/// spans, as the input after them is the same.
+fn relex_internal<'i>(
+    parser: &Parser,
+    mut tokens: Vec<(Spanned<Token>, &'i str)>,
+    edit: &TextEdit,
+    input: &'i str,
+) -> Result<Relexed<'i>, ParsingError> {
+    let new_end = edit.range.start + edit.replacement.len();
+
+    let grammar = parser.grammar();
+    let relex_start = match grammar.lexer_modes()[0].scanner().lookahead() {
+        Some(lookahead) => {
+            tokens.partition_point(|(token, _)| {
+                token.span().offset + token.span().length + lookahead <= edit.range.start
+            })
+        },
+        None => 0,
+    };
+    let position = match relex_start.checked_sub(1) {
+        Some(index) => {
+            let (token, slice) = &tokens[index];
+            utils::position_after(token.span(), slice)
+        },
+        None => Span { offset: 0, length: 0, line: 1, column: 1 },
+    };
+
+    let mut relexed_tokens = vec![];
+    let mut synchronization = None;
+    for token in TokenStream::starting_at(grammar, input, &position) {
+        let (token, slice) = token?;
+        let (token, span) = token.into_components();
+
+        if span.offset >= new_end {
+            let old_offset = span.offset - new_end + edit.range.end;
+            let search = tokens[relex_start..]
+                .binary_search_by_key(&old_offset, |(token, _)| token.span().offset);
+            if let Ok(index) = search {
+                let (old_token, old_slice) = &tokens[relex_start + index];
+                if **old_token == token && *old_slice == slice {
+                    synchronization = Some((relex_start + index, span));
+                    break;
+                }
+            }
+        }
+
+        relexed_tokens.push((Spanned::new(token, span), slice));
+    }
+
+    // Tokens before the edit are the same as the old tokens, unless the edit changed how they end.
+    let prefix_length = relex_start
+        + relexed_tokens
+            .iter()
+            .zip(&tokens[relex_start..])
+            .take_while(|((token, slice), (old_token, old_slice))| {
+                **token == **old_token && token.span() == old_token.span() && slice == old_slice
+            })
+            .count();
+
+    // End of input token is always the same, so tokenization is synchronized at the latest there.
+    let (old_suffix_start, suffix_span) = synchronization.unwrap();
+    let old_suffix_span = tokens[old_suffix_start].0.span().clone();
+
+    let suffix_start = relex_start + relexed_tokens.len();
+    let replaced_tokens =
+        tokens.splice(relex_start..old_suffix_start, relexed_tokens).collect::<Vec<_>>();
+
+    // Slices of the old tokens are kept, since they are the same in the edited input.
+    for (token, _) in tokens[suffix_start..].iter_mut() {
+        *token.span_mut() = shift_span_internal(token.span(), &old_suffix_span, &suffix_span);
+    }
+
+    Ok(Relexed {
+        tokens,
+        relex_start,
+        prefix_length,
+        suffix_start,
+        old_suffix_start,
+        suffix_span,
+        old_suffix_span,
+        replaced_tokens,
+    })
+}
+
+/// Internal logic to parse the tokens of an edited input, reusing the subtrees of the old tree.
+///
+/// Subtrees are reused when their tokens and the token after them are unchanged, and the parser
+/// is in the state they were built in, since the parser would build the same subtree from there.
+/// Subtrees that cannot be reused are broken down into their children, until the tokens.
+fn reparse_internal<'i>(
+    parser: &Parser,
+    old_tree: Tree<'i>,
+    relexed: &Relexed<'i>,
+) -> Result<Tree<'i>, ParsingError> {
+    let tokens = &relexed.tokens;
+
+    let end = relexed.old_length() - 1;
+    let mut pending = vec![Pending { tree: old_tree, start: 0, end, state: Some(0) }];
+
+    let mut state_stack = vec![0];
+    let mut tree_stack = vec![];
+
+    let mut current = 0;
+    loop {
+        let (current_token, current_slice) = &tokens[current];
+
+        let current_state = *state_stack.last().unwrap();
+        let action_to_take = match parser.action_internal(&state_stack, current_token) {
+            Some(action) => action,
+            None => {
+                return Err(parser.error_internal(
+                    &state_stack,
+                    current_token,
+                    current_slice,
+                    tokens[current + 1..].iter().take(UPCOMING_TOKENS_TO_SEARCH - 1).cloned(),
+                ));
+            },
+        };
+
+        match action_to_take {
+            Action::Accept => {
+                return Ok(Parser::accept_internal(&mut tree_stack));
+            },
+            Action::Shift { .. } => {
+                let reused =
+                    next_reusable_internal(parser, relexed, &mut pending, current, current_state);
+                if let Some((next_state, tree, token_count)) = reused {
+                    state_stack.push(next_state);
+                    tree_stack.push(tree);
+                    current += token_count;
+                    continue;
+                }
+
+                let (token, span) = current_token.clone().into_components();
+                parser.step_internal(&mut state_stack, action_to_take);
+                tree_stack.push(Tree::Terminal { token, span, slice: current_slice, trivia: None });
+                current += 1;
+            },
+            Action::Reduce { rule_index } => {
+                parser.step_internal(&mut state_stack, action_to_take);
+                parser.reduce_internal(rule_index, &mut tree_stack);
+            },
+        }
+    }
+}
+
+/// Internal logic to find the subtree of the old tree to reuse at the current token,
+/// breaking down the pending subtrees as needed.
+///
+/// Result is the state after the subtree, the subtree moved out of the old tree,
+/// and the number of tokens in it.
+fn next_reusable_internal<'i>(
+    parser: &Parser,
+    relexed: &Relexed<'i>,
+    pending: &mut Vec<Pending<'i>>,
+    current: usize,
+    current_state: usize,
+) -> Option<(usize, Tree<'i>, usize)> {
+    // Tokens that are changed by the edit don't have subtrees to reuse.
+    let old = if current < relexed.prefix_length {
+        current
+    } else if current >= relexed.suffix_start {
+        current - relexed.suffix_start + relexed.old_suffix_start
+    } else {
+        return None;
+    };
+
+    while let Some(candidate) = pending.last() {
+        // Empty subtrees are built by the reductions of the parser.
+        if candidate.start == candidate.end || candidate.end <= old {
+            pending.pop();
+            continue;
+        }
+        if candidate.start > old {
+            return None;
+        }
+
+        if candidate.start == old {
+            let symbol = match &candidate.tree {
+                Tree::NonTerminal { symbol, .. } => symbol,
+                _ => return None,
+            };
+
+            let unchanged = candidate.end < relexed.prefix_length
+                || candidate.start >= relexed.old_suffix_start;
+            if unchanged && candidate.state == Some(current_state) {
+                let next_state = parser.goto_table()[current_state][symbol];
+                let candidate = pending.pop().unwrap();
+
+                // Subtrees after the edit are moved along with their tokens.
+                let mut tree = candidate.tree;
+                if candidate.start >= relexed.old_suffix_start {
+                    shift_tree_internal(&mut tree, relexed);
+                }
+                return Some((next_state, tree, candidate.end - candidate.start));
+            }
+        }
+
+        let candidate = pending.pop().unwrap();
+        breakdown_internal(parser, relexed, candidate, pending);
+    }
+    None
+}
+
+/// Internal logic to break down a subtree of the old tree into its children.
+///
+/// States of the old parse before the children are recovered from the state before the subtree,
+/// by following the parsing tables over the children. Repetitions are flattened in the tree
+/// (e.g., `Value* -> Value* Value`), so the reductions between their elements are followed as well.
+fn breakdown_internal<'i>(
+    parser: &Parser,
+    relexed: &Relexed<'i>,
+    subtree: Pending<'i>,
+    pending: &mut Vec<Pending<'i>>,
+) {
+    // States before the children of error nodes are not known,
+    // since the trees before the error token are discarded as well.
+    let (symbol, children, mut state_stack) = match subtree.tree {
+        Tree::NonTerminal { symbol, pattern } => {
+            (Some(symbol), pattern, subtree.state.into_iter().collect::<Vec<_>>())
+        },
+        Tree::Error { discarded } => (None, discarded, vec![]),
+        Tree::Terminal { .. } => return,
+    };
+    let repetition = symbol
+        .as_ref()
+        .is_some_and(|symbol| parser.grammar().repetition_symbols().contains(symbol));
+
+    // Children start at their first terminal, which is found from the old tokens using its offset,
+    // except the first child, which starts with the subtree (e.g., a long left recursive list).
+    let mut ends = Vec::with_capacity(children.len());
+    let mut end = subtree.end;
+    for child in children.iter().skip(1).rev() {
+        ends.push(end);
+        if let Some(offset) = first_offset_internal(child) {
+            end = relexed.old_tokens_before(offset, end);
+        }
+    }
+    ends.push(end);
+    ends.reverse();
+
+    let mut start = subtree.start;
+    let mut broken_down = Vec::with_capacity(children.len());
+    for (child, end) in children.into_iter().zip(ends) {
+        if repetition {
+            while let Some(state) = state_stack.last() {
+                let (lookahead, _) = relexed.old_token(start);
+                let rule_index = match parser.action_table()[*state].get(lookahead) {
+                    Some(actions) => {
+                        match actions.first() {
+                            Some(Action::Reduce { rule_index }) => *rule_index,
+                            _ => break,
+                        }
+                    },
+                    None => break,
+                };
+
+                let rule = &parser.grammar().rules()[rule_index];
+                if Some(rule.symbol()) != symbol.as_ref() {
+                    break;
+                }
+
+                let pattern_length = parser.pattern_length_internal(rule_index);
+                state_stack.truncate(state_stack.len().saturating_sub(pattern_length));
+                match state_stack
+                    .last()
+                    .and_then(|state| parser.goto_table()[*state].get(rule.symbol()))
+                {
+                    Some(next_state) => state_stack.push(*next_state),
+                    None => state_stack.clear(),
+                }
+            }
+        }
+
+        let state = state_stack.last().copied();
+        let next_state = state.and_then(|state| {
+            match &child {
+                Tree::Terminal { token, .. } => {
+                    match parser.action_table()[state]
+                        .get(token)
+                        .and_then(|actions| actions.first())
+                    {
+                        Some(Action::Shift { next_state }) => Some(*next_state),
+                        _ => None,
+                    }
+                },
+                Tree::NonTerminal { symbol, .. } => parser.goto_table()[state].get(symbol).copied(),
+                Tree::Error { .. } => None,
+            }
+        });
+        match next_state {
+            Some(next_state) => state_stack.push(next_state),
+            None => state_stack.clear(),
+        }
+
+        broken_down.push(Pending { tree: child, start, end, state });
+        start = end;
+    }
+
+    // Pending subtrees are popped from the end, so the leftmost child is pushed last.
+    pending.extend(broken_down.into_iter().rev());
+}
+
+/// Internal logic to find the offset of the first terminal of a subtree of the old tree.
+fn first_offset_internal(tree: &Tree<'_>) -> Option<usize> {
+    match tree {
+        Tree::Terminal { span, .. } => Some(span.offset),
+        Tree::NonTerminal { pattern: children, .. } | Tree::Error { discarded: children } => {
+            children.iter().find_map(first_offset_internal)
+        },
+    }
+}
+
+/// Internal logic to shift the spans of a subtree of the old tree after the edit to the edited input.
+fn shift_tree_internal(tree: &mut Tree<'_>, relexed: &Relexed<'_>) {
+    match tree {
+        Tree::Terminal { span, .. } => {
+            *span = shift_span_internal(span, &relexed.old_suffix_span, &relexed.suffix_span);
+        },
+        Tree::NonTerminal { pattern: children, .. } | Tree::Error { discarded: children } => {
+            for child in children.iter_mut() {
+                shift_tree_internal(child, relexed);
+            }
+        },
+    }
+}
+
+/// Internal logic to shift a span of the old input after the edit to the edited input,
+/// using the span of an old token after the edit before and after the shift.
+fn shift_span_internal(old_span: &Span, old_start: &Span, start: &Span) -> Span {
+    let offset = old_span.offset - old_start.offset + start.offset;
+    let line = old_span.line - old_start.line + start.line;
+    let column = if old_span.line == old_start.line {
+        old_span.column - old_start.column + start.column
+    } else {
+        old_span.column
+    };
+    Span { offset, length: old_span.length, line, column }
+}
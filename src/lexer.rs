@@ -0,0 +1,91 @@
+use crate::prelude::*;
+
+
+/// Lexer of the input of a grammar, which can be used instead of the tokenizer of the parsers
+/// (e.g., a hand-written lexer or a lexer derived using `logos`).
+///
+/// Lexed tokens refer to the tokens of the grammar by name (e.g., `ConstantToken::from("+")`
+/// for `'+'` and `RegexToken::from("f")` for `%f`), and they are checked against the grammar
+/// when they are collected using [Parser::tokenize_with] or [LlParser::tokenize_with],
+/// or parsed using [Parser::parse_iter] or [Parser::events].
+///
+/// Iterators of tokens and their slices are lexers, so existing lexers can be used
+/// by mapping their tokens to the tokens of the grammar.
+pub trait Lexer<'i> {
+    /// Lexes the next token of the input and its corresponding input slice.
+    ///
+    /// Lexing ends when [None] or the end of input token is returned. If the end of input token
+    /// is not returned, the end of input is assumed to be right after the last token.
+    fn next_token(&mut self) -> Option<Result<(Spanned<Token>, &'i str), ParsingError>>;
+
+    /// Lexes the next token of the input among the tokens that are acceptable by the parser.
+    ///
+    /// Parsers may call this instead of [Lexer::next_token] with the tokens that are acceptable
+    /// in their current state, so lexers can scan only those tokens (i.e., parser-directed
+    /// lexing). Acceptable tokens are ignored by default.
+    fn next_acceptable_token(
+        &mut self,
+        _acceptable: &dyn Fn(&Token) -> bool,
+    ) -> Option<Result<(Spanned<Token>, &'i str), ParsingError>> {
+        self.next_token()
+    }
+}
+
+impl<'i, I: Iterator<Item = Result<(Spanned<Token>, &'i str), ParsingError>>> Lexer<'i> for I {
+    fn next_token(&mut self) -> Option<Result<(Spanned<Token>, &'i str), ParsingError>> {
+        self.next()
+    }
+}
+
+
+/// Lexer that checks the tokens of another lexer against the tokens of a grammar.
+pub struct CheckedLexer<L> {
+    lexer: L,
+    input_tokens: IndexSet<Token>,
+}
+
+impl<L> CheckedLexer<L> {
+    /// Creates a lexer that checks the tokens of a lexer against the tokens of a grammar.
+    pub fn new(grammar: &Grammar, lexer: L) -> CheckedLexer<L> {
+        CheckedLexer { lexer, input_tokens: grammar.input_tokens().into_iter().collect() }
+    }
+}
+
+impl<'i, L: Lexer<'i>> Lexer<'i> for CheckedLexer<L> {
+    fn next_token(&mut self) -> Option<Result<(Spanned<Token>, &'i str), ParsingError>> {
+        let (token, slice) = match self.lexer.next_token()? {
+            Ok(token) => token,
+            Err(error) => return Some(Err(error)),
+        };
+
+        // Error token is only shifted by the parser during error recovery.
+        if *token != Token::Eof
+            && (*token == Token::Error || !self.input_tokens.contains(token.deref()))
+        {
+            let (token, span) = token.into_components();
+            return Some(Err(ParsingError::UndefinedToken { token, span }));
+        }
+
+        Some(Ok((token, slice)))
+    }
+}
+
+
+/// Collects the tokens of a lexer, checking them against the tokens of a grammar.
+pub(crate) fn tokenize_with<'i>(
+    grammar: &Grammar,
+    lexer: impl Lexer<'i>,
+) -> Result<Vec<(Spanned<Token>, &'i str)>, ParsingError> {
+    // Upcoming tokens end with the end of input token, even if the lexer doesn't return it.
+    let mut upcoming_tokens = UpcomingTokens::new(CheckedLexer::new(grammar, lexer));
+
+    let mut tokens = vec![];
+    loop {
+        let (token, slice) = upcoming_tokens.next(None)?;
+        let is_eof = *token == Token::Eof;
+        tokens.push((token, slice));
+        if is_eof {
+            return Ok(tokens);
+        }
+    }
+}
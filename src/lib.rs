@@ -1,51 +1,109 @@
-#![doc = include_str!("../README.md")]
+#![cfg_attr(not(doctest), doc = include_str!("../README.md"))]
 
+mod actions;
 mod automaton;
+mod completion;
+mod counterexample;
 mod errors;
+mod events;
+mod forest;
 mod grammar;
+mod incremental;
+mod lexer;
 mod parser;
+mod repair;
+mod scanner;
+mod session;
 mod span;
+mod stream;
 mod tables;
 mod trace;
 mod tree;
 mod utils;
 
 pub use {
+    actions::SemanticActions,
     automaton::{
         Automaton,
         Item,
         State,
     },
+    completion::Completion,
+    counterexample::{
+        Counterexample,
+        Derivation,
+    },
     errors::{
         GrammarError,
         ParserError,
         ParsingError,
     },
+    events::{
+        Event,
+        Events,
+    },
+    forest::{
+        Alternative,
+        Forest,
+        ForestNode,
+    },
     grammar::{
+        Associativity,
         AtomicPattern,
         ConstantToken,
+        DEFAULT_LEXER_MODE,
         Grammar,
+        LexerMode,
+        LexerModeTransition,
+        Precedence,
         RegexToken,
         Rule,
         Symbol,
         Token,
     },
-    parser::Parser,
+    incremental::TextEdit,
+    lexer::Lexer,
+    parser::{
+        LlParser,
+        Parser,
+        ParsingMethod,
+    },
+    repair::{
+        Edit,
+        Repair,
+    },
+    session::{
+        ParseSession,
+        Status,
+    },
     span::{
         Span,
         Spanned,
     },
+    stream::TokenStream,
     tables::{
         Action,
+        Conflict,
+        ConflictKind,
         FirstTable,
         FollowTable,
+        LlConflict,
+        LlConflictKind,
+        LlTable,
         ParsingTables,
+        ResolvedConflict,
     },
     trace::{
+        LlAction,
+        LlStep,
+        LlTrace,
         Step,
         Trace,
     },
-    tree::Tree,
+    tree::{
+        Tree,
+        Trivia,
+    },
 };
 
 mod prelude {
@@ -56,8 +114,9 @@ mod prelude {
             IndexSet,
         },
         itertools::Itertools,
+        lexer::CheckedLexer,
         logos::{
-            Lexer,
+            Lexer as LogosLexer,
             Logos,
         },
         prettytable::{
@@ -73,6 +132,8 @@ mod prelude {
         },
         ptree::TreeBuilder,
         regex::Regex,
+        repair::UPCOMING_TOKENS_TO_SEARCH,
+        scanner::Scanner,
         smallvec::{
             SmallVec,
             smallvec,
@@ -83,7 +144,10 @@ mod prelude {
         },
         std::{
             self,
-            collections::BTreeMap,
+            collections::{
+                BTreeMap,
+                VecDeque,
+            },
             fmt::{
                 self,
                 Debug,
@@ -92,6 +156,10 @@ mod prelude {
             io::BufWriter,
             ops::Deref,
         },
+        stream::{
+            ContextualTokenStream,
+            UpcomingTokens,
+        },
         thiserror::Error,
     };
 
@@ -1,14 +1,30 @@
 use {
-    clap::Parser as Clap,
+    clap::{
+        Parser as Clap,
+        ValueEnum,
+    },
     colored::Colorize,
     dotlr::{
+        Completion,
+        Conflict,
         Grammar,
+        LlConflict,
+        LlParser,
         Parser,
         ParserError,
+        ParsingError,
+        ParsingMethod,
     },
     rustyline::{
-        DefaultEditor,
+        Context,
+        Editor,
+        Helper,
+        completion::Completer,
         error::ReadlineError,
+        highlight::Highlighter,
+        hint::Hinter,
+        history::DefaultHistory,
+        validate::Validator,
     },
     std::{
         path::PathBuf,
@@ -16,12 +32,129 @@ use {
     },
 };
 
+#[derive(Clone, Copy, ValueEnum)]
+enum Method {
+    /// LR(0) parser.
+    Lr0,
+    /// SLR(1) parser.
+    Slr,
+    /// LALR(1) parser.
+    Lalr,
+    /// LR(1) parser.
+    Lr,
+    /// LL(1) parser.
+    Ll1,
+}
+
+enum AnyParser {
+    Lr(Parser),
+    Ll(LlParser),
+    Glr { parser: Parser, max_trees: usize },
+    Recovering(Parser),
+    Contextual(Parser),
+}
+
+impl AnyParser {
+    fn dump(&self) {
+        match self {
+            AnyParser::Lr(parser)
+            | AnyParser::Glr { parser, .. }
+            | AnyParser::Recovering(parser)
+            | AnyParser::Contextual(parser) => parser.dump(),
+            AnyParser::Ll(parser) => parser.dump(),
+        }
+    }
+
+    fn completing_parser(&self) -> Option<&Parser> {
+        match self {
+            AnyParser::Lr(parser)
+            | AnyParser::Recovering(parser)
+            | AnyParser::Contextual(parser) => Some(parser),
+            // Parsers with conflicts and LL(1) parsers cannot complete inputs.
+            AnyParser::Glr { .. } | AnyParser::Ll(_) => None,
+        }
+    }
+}
+
+struct ReplHelper<'p> {
+    parser: Option<&'p Parser>,
+}
+
+impl Completer for ReplHelper<'_> {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        // Word under the cursor is completed with the constant tokens that can follow
+        // the input before it.
+        let line = &line[..pos];
+        let word_start = line
+            .trim_end_matches(|character: char| character.is_alphanumeric() || character == '_')
+            .len();
+        let word = &line[word_start..];
+
+        let candidates = match self.parser.map(|parser| parser.complete(&line[..word_start])) {
+            Some(Ok(completions)) => {
+                completions
+                    .into_iter()
+                    .filter_map(|completion| {
+                        match completion {
+                            Completion::Constant(constant_token)
+                                if constant_token.as_str().starts_with(word) =>
+                            {
+                                Some(constant_token.as_str().to_owned())
+                            },
+                            _ => None,
+                        }
+                    })
+                    .collect()
+            },
+            _ => vec![],
+        };
+        Ok((word_start, candidates))
+    }
+}
+
+impl Hinter for ReplHelper<'_> {
+    type Hint = String;
+}
+
+impl Highlighter for ReplHelper<'_> {}
+
+impl Validator for ReplHelper<'_> {}
+
+impl Helper for ReplHelper<'_> {}
+
 #[derive(Clap)]
 struct Args {
-    /// Create an LALR(1) parser instead of an LR(1) parser.
-    #[arg(long)]
+    /// Method to create the parser with.
+    #[arg(long, value_enum, default_value_t = Method::Lr)]
+    method: Method,
+
+    /// Create an LALR(1) parser instead of an LR(1) parser (same as `--method lalr`).
+    #[arg(long, conflicts_with = "method")]
     lalr: bool,
 
+    /// Parse with a GLR parser, which follows every action of conflicts, into a parse forest.
+    #[arg(long)]
+    glr: bool,
+
+    /// Maximum number of parse trees to show from the parse forest of a GLR parser.
+    #[arg(long, default_value_t = 10, requires = "glr")]
+    max_trees: usize,
+
+    /// Recover from tokenization and syntax errors, and report all of them.
+    #[arg(long, conflicts_with = "glr")]
+    recover: bool,
+
+    /// Scan only the tokens that are acceptable in the current state of the parser.
+    #[arg(long, conflicts_with_all = ["glr", "recover"])]
+    contextual: bool,
+
     /// Grammar to parse.
     grammar: PathBuf,
 
@@ -46,30 +179,69 @@ fn main() -> ExitCode {
             return ExitCode::FAILURE;
         },
     };
-    let parser = {
-        if args.lalr {
-            match Parser::lalr(grammar) {
-                Ok(parser) => parser,
+    let method = match (args.lalr, args.method) {
+        (true, _) | (false, Method::Lalr) => Some(ParsingMethod::Lalr),
+        (false, Method::Lr0) => Some(ParsingMethod::Lr0),
+        (false, Method::Slr) => Some(ParsingMethod::Slr),
+        (false, Method::Lr) => Some(ParsingMethod::Lr),
+        (false, Method::Ll1) => None,
+    };
+    let parser = match method {
+        Some(method) if args.glr => {
+            match Parser::with_method(grammar, method) {
+                Ok(parser) => AnyParser::Glr { parser, max_trees: args.max_trees },
+                Err(ParserError::Conflicts { parser, conflicts }) => {
+                    println!();
+                    for conflict in conflicts {
+                        println!("{} {}", "conflict:".yellow().bold(), conflict);
+                        println!();
+                    }
+                    AnyParser::Glr { parser: *parser, max_trees: args.max_trees }
+                },
+                Err(error) => {
+                    eprintln!("{} {}", format!("{} parser error:", method).red().bold(), error);
+                    return ExitCode::FAILURE;
+                },
+            }
+        },
+        Some(method) => {
+            match Parser::with_method(grammar, method) {
+                Ok(parser) if args.recover => AnyParser::Recovering(parser),
+                Ok(parser) if args.contextual => AnyParser::Contextual(parser),
+                Ok(parser) => AnyParser::Lr(parser),
                 Err(error) => {
-                    eprintln!("{} {}", "lr parser error:".red().bold(), error);
-                    if let ParserError::Conflict { parser, .. } = error {
-                        parser.dump();
+                    eprintln!("{} {}", format!("{} parser error:", method).red().bold(), error);
+                    if let ParserError::Conflicts { parser, conflicts } = error {
+                        dump_conflicts(&parser, &conflicts);
                     }
                     return ExitCode::FAILURE;
                 },
             }
-        } else {
-            match Parser::lr(grammar) {
-                Ok(parser) => parser,
+        },
+        None if args.glr => {
+            eprintln!("{} GLR parsing requires an LR parser", "usage error:".red().bold());
+            return ExitCode::FAILURE;
+        },
+        None if args.recover => {
+            eprintln!("{} error recovery requires an LR parser", "usage error:".red().bold());
+            return ExitCode::FAILURE;
+        },
+        None if args.contextual => {
+            eprintln!("{} contextual lexing requires an LR parser", "usage error:".red().bold());
+            return ExitCode::FAILURE;
+        },
+        None => {
+            match LlParser::new(grammar) {
+                Ok(parser) => AnyParser::Ll(parser),
                 Err(error) => {
-                    eprintln!("{} {}", "lalr parser error:".red().bold(), error);
-                    if let ParserError::Conflict { parser, .. } = error {
-                        parser.dump();
+                    eprintln!("{} {}", "LL(1) parser error:".red().bold(), error);
+                    if let ParserError::LlConflicts { parser, conflicts } = error {
+                        dump_ll_conflicts(&parser, &conflicts);
                     }
                     return ExitCode::FAILURE;
                 },
             }
-        }
+        },
     };
 
     println!();
@@ -85,14 +257,39 @@ fn main() -> ExitCode {
     }
 }
 
-fn repl(parser: &Parser) -> ExitCode {
-    let mut editor = match DefaultEditor::new() {
+fn dump_conflicts(parser: &Parser, conflicts: &[Conflict]) {
+    println!();
+    parser.dump();
+    for conflict in conflicts {
+        println!();
+        println!("{} {}", "conflict:".red().bold(), conflict);
+        if let Some(counterexample) = parser.counterexample(conflict) {
+            println!();
+            println!("{} {}", "counterexample:".yellow().bold(), counterexample);
+        }
+    }
+    println!();
+}
+
+fn dump_ll_conflicts(parser: &LlParser, conflicts: &[LlConflict]) {
+    println!();
+    parser.dump();
+    for conflict in conflicts {
+        println!();
+        println!("{} {}", "conflict:".red().bold(), conflict);
+    }
+    println!();
+}
+
+fn repl(parser: &AnyParser) -> ExitCode {
+    let mut editor = match Editor::<ReplHelper, DefaultHistory>::new() {
         Ok(editor) => editor,
         Err(error) => {
             eprintln!("{} editor cannot be created ({})", "repl error:".red().bold(), error);
             return ExitCode::FAILURE;
         },
     };
+    editor.set_helper(Some(ReplHelper { parser: parser.completing_parser() }));
 
     let history_file = dirs::data_dir().map(|dir| dir.join("dotlr")).map(|dir| {
         if !dir.exists() {
@@ -131,7 +328,46 @@ fn repl(parser: &Parser) -> ExitCode {
     }
 }
 
-fn parse(parser: &Parser, input: &str) -> ExitCode {
+fn parse(parser: &AnyParser, input: &str) -> ExitCode {
+    match parser {
+        AnyParser::Lr(parser) => parse_lr(parser, input),
+        AnyParser::Ll(parser) => parse_ll(parser, input),
+        AnyParser::Glr { parser, max_trees } => parse_glr(parser, *max_trees, input),
+        AnyParser::Recovering(parser) => parse_with_recovery(parser, input),
+        AnyParser::Contextual(parser) => parse_contextual(parser, input),
+    }
+}
+
+fn parse_lr(parser: &Parser, input: &str) -> ExitCode {
+    let tokens = match parser.tokenize(input) {
+        Ok(tokens) => tokens,
+        Err(error) => {
+            println!();
+            eprintln!("{} {}", "tokenization error:".red().bold(), error);
+            println!();
+            return ExitCode::FAILURE;
+        },
+    };
+    match parser.trace(tokens) {
+        Ok((parse_trace, parse_tree)) => {
+            println!();
+            parse_tree.dump();
+            println!();
+            parse_trace.dump(parser.grammar());
+            println!();
+            ExitCode::SUCCESS
+        },
+        Err(error) => {
+            println!();
+            eprintln!("{} {}", "syntax error:".red().bold(), error);
+            println!();
+            ExitCode::FAILURE
+        },
+    }
+}
+
+
+fn parse_ll(parser: &LlParser, input: &str) -> ExitCode {
     let tokens = match parser.tokenize(input) {
         Ok(tokens) => tokens,
         Err(error) => {
@@ -158,3 +394,83 @@ fn parse(parser: &Parser, input: &str) -> ExitCode {
         },
     }
 }
+
+fn parse_glr(parser: &Parser, max_trees: usize, input: &str) -> ExitCode {
+    let tokens = match parser.tokenize(input) {
+        Ok(tokens) => tokens,
+        Err(error) => {
+            println!();
+            eprintln!("{} {}", "tokenization error:".red().bold(), error);
+            println!();
+            return ExitCode::FAILURE;
+        },
+    };
+    match parser.parse_forest(tokens) {
+        Ok(parse_forest) => {
+            println!();
+            parse_forest.dump(parser.grammar());
+            for parse_tree in parse_forest.trees(max_trees) {
+                println!();
+                parse_tree.dump();
+            }
+            println!();
+            ExitCode::SUCCESS
+        },
+        Err(error) => {
+            println!();
+            eprintln!("{} {}", "syntax error:".red().bold(), error);
+            println!();
+            ExitCode::FAILURE
+        },
+    }
+}
+
+fn parse_with_recovery(parser: &Parser, input: &str) -> ExitCode {
+    let (tokens, tokenization_errors) = parser.tokenize_with_recovery(input);
+    if !tokenization_errors.is_empty() {
+        println!();
+        for error in tokenization_errors.iter() {
+            eprintln!("{} {}", "tokenization error:".red().bold(), error);
+        }
+    }
+    let (parse_tree, errors) = parser.parse_with_recovery(tokens);
+    if !errors.is_empty() {
+        println!();
+        for error in errors.iter() {
+            eprintln!("{} {}", "syntax error:".red().bold(), error);
+        }
+    }
+    println!();
+    parse_tree.dump();
+    println!();
+    if errors.is_empty() && tokenization_errors.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn parse_contextual(parser: &Parser, input: &str) -> ExitCode {
+    match parser.parse_contextual(input) {
+        Ok(parse_tree) => {
+            println!();
+            parse_tree.dump();
+            println!();
+            ExitCode::SUCCESS
+        },
+        Err(
+            error @ (ParsingError::UnknownToken { .. } | ParsingError::InconsistentDedent { .. }),
+        ) => {
+            println!();
+            eprintln!("{} {}", "tokenization error:".red().bold(), error);
+            println!();
+            ExitCode::FAILURE
+        },
+        Err(error) => {
+            println!();
+            eprintln!("{} {}", "syntax error:".red().bold(), error);
+            println!();
+            ExitCode::FAILURE
+        },
+    }
+}
@@ -1,12 +1,40 @@
 use crate::prelude::*;
 
 
-/// LR(1) or LALR(1) parser of a grammar.
+/// Method (e.g., `LR(0)`, `SLR(1)`, `LALR(1)`, `LR(1)`) used to construct a parser.
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ParsingMethod {
+    /// Reductions are done on every token, using the LR(0) automaton.
+    Lr0,
+    /// Reductions are done on the follow set of the symbol, using the LR(0) automaton.
+    Slr,
+    /// Reductions are done on the lookahead of the item, using the LALR(1) automaton.
+    Lalr,
+    /// Reductions are done on the lookahead of the item, using the LR(1) automaton.
+    Lr,
+}
+
+impl Display for ParsingMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParsingMethod::Lr0 => write!(f, "LR(0)"),
+            ParsingMethod::Slr => write!(f, "SLR(1)"),
+            ParsingMethod::Lalr => write!(f, "LALR(1)"),
+            ParsingMethod::Lr => write!(f, "LR(1)"),
+        }
+    }
+}
+
+
+/// LR(0), SLR(1), LR(1) or LALR(1) parser of a grammar.
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
 #[cfg_attr(feature = "serde", derive(Serialize))]
 #[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
 #[derive(Debug)]
 pub struct Parser {
+    method: ParsingMethod,
     grammar: Grammar,
     first_table: FirstTable,
     follow_table: FollowTable,
@@ -15,34 +43,38 @@ pub struct Parser {
 }
 
 impl Parser {
-    /// Crates an LR(1) parser of a grammar.
-    pub fn lr(grammar: Grammar) -> Result<Parser, ParserError> {
-        Parser::check_grammar_internal(&grammar)?;
+    /// Creates an LR(0) parser of a grammar.
+    pub fn lr0(grammar: Grammar) -> Result<Parser, ParserError> {
+        Parser::construct_internal(grammar, ParsingMethod::Lr0)
+    }
 
-        let first_table = FirstTable::construct(&grammar);
-        let follow_table = FollowTable::construct(&grammar, &first_table);
-        let automaton = Automaton::construct(&grammar, &first_table);
-        let parsing_tables = ParsingTables::construct(&grammar, &follow_table, &automaton)?;
+    /// Creates an SLR(1) parser of a grammar.
+    pub fn slr(grammar: Grammar) -> Result<Parser, ParserError> {
+        Parser::construct_internal(grammar, ParsingMethod::Slr)
+    }
 
-        let parser = Parser { grammar, first_table, follow_table, automaton, parsing_tables };
-        parser.check_conflicts_internal()
+    /// Crates an LR(1) parser of a grammar.
+    pub fn lr(grammar: Grammar) -> Result<Parser, ParserError> {
+        Parser::construct_internal(grammar, ParsingMethod::Lr)
     }
 
     /// Crates an LALR(1) parser of a grammar.
     pub fn lalr(grammar: Grammar) -> Result<Parser, ParserError> {
-        Parser::check_grammar_internal(&grammar)?;
-
-        let first_table = FirstTable::construct(&grammar);
-        let follow_table = FollowTable::construct(&grammar, &first_table);
-        let automaton = Automaton::construct(&grammar, &first_table).to_lalr();
-        let parsing_tables = ParsingTables::construct(&grammar, &follow_table, &automaton)?;
+        Parser::construct_internal(grammar, ParsingMethod::Lalr)
+    }
 
-        let parser = Parser { grammar, first_table, follow_table, automaton, parsing_tables };
-        parser.check_conflicts_internal()
+    /// Creates a parser of a grammar using a parsing method.
+    pub fn with_method(grammar: Grammar, method: ParsingMethod) -> Result<Parser, ParserError> {
+        Parser::construct_internal(grammar, method)
     }
 }
 
 impl Parser {
+    /// Gets the parsing method of the parser.
+    pub fn method(&self) -> ParsingMethod {
+        self.method
+    }
+
     /// Gets the grammar of the parser.
     pub fn grammar(&self) -> &Grammar {
         &self.grammar
@@ -79,94 +111,148 @@ impl Parser {
     }
 }
 
+impl Parser {
+    /// Searches a counterexample of a conflict in the automaton of the parser.
+    pub fn counterexample(&self, conflict: &Conflict) -> Option<Counterexample> {
+        Counterexample::construct(&self.grammar, &self.first_table, &self.automaton, conflict)
+    }
+}
+
 impl Parser {
     /// Tokenizes an input into a stream of tokens and their corresponding input slices.
     pub fn tokenize<'i>(
         &self,
         input: &'i str,
     ) -> Result<Vec<(Spanned<Token>, &'i str)>, ParsingError> {
-        let mut tokens: Vec<(Spanned<Token>, &'i str)> = Vec::new();
-
-        let mut ordered_constant_tokens = self.grammar.constant_tokens().iter().collect::<Vec<_>>();
-        ordered_constant_tokens.sort_by_key(|token| token.len());
-
-        let mut remaining_input = input.trim_start();
-        let mut offset = input.len() - remaining_input.len();
-        let (initial_new_lines, initial_newline_offset) = utils::count_new_lines(&input[..offset]);
-        let mut line = initial_new_lines + 1;
-        let mut last_newline_offset = initial_newline_offset.unwrap_or(0);
-        let mut column = input[last_newline_offset..offset].chars().count() + 1;
-        while !remaining_input.is_empty() {
-            let mut matching_token = None;
-            let mut matching_slice = "";
-
-            for token in ordered_constant_tokens.iter().rev() {
-                if remaining_input.starts_with(token.as_str()) {
-                    matching_token = Some(Token::Constant((*token).clone()));
-                    matching_slice = &remaining_input[..token.len()];
-                    break;
-                }
-            }
+        self.token_stream(input).collect()
+    }
 
-            for (regex_token, regex) in self.grammar.regular_expressions() {
-                if let Some(match_info) = regex.find(remaining_input) {
-                    if match_info.len() > matching_slice.len() {
-                        matching_token = Some(Token::Regex(regex_token.clone()));
-                        matching_slice = &remaining_input[..match_info.end()];
-                    }
-                }
-            }
+    /// Tokenizes an input, recovering from tokenization errors.
+    ///
+    /// Each run of unknown characters is reported as a single error and skipped,
+    /// so all of the tokenization errors of the input are reported at once.
+    pub fn tokenize_with_recovery<'i>(
+        &self,
+        input: &'i str,
+    ) -> (Vec<(Spanned<Token>, &'i str)>, Vec<ParsingError>) {
+        TokenStream::with_recovery(&self.grammar, input).partition_result()
+    }
 
-            if matching_token.is_none() {
-                let span = Span { offset, length: 1, line, column };
-                return Err(ParsingError::UnknownToken {
-                    token: format_smolstr!("{}", remaining_input.chars().next().unwrap()),
-                    span,
-                });
-            }
+    /// Tokenizes an input using an external lexer, checking the lexed tokens against the grammar.
+    ///
+    /// Tokens are collected into the same form as [Parser::tokenize],
+    /// so they can be parsed or traced the same way.
+    pub fn tokenize_with<'i>(
+        &self,
+        lexer: impl Lexer<'i>,
+    ) -> Result<Vec<(Spanned<Token>, &'i str)>, ParsingError> {
+        lexer::tokenize_with(&self.grammar, lexer)
+    }
 
-            let token = Spanned::new(matching_token.unwrap(), Span {
-                offset,
-                length: matching_slice.len(),
-                line,
-                column,
-            });
+    /// Creates a lazy stream of the tokens of an input and their corresponding input slices.
+    pub fn token_stream<'i>(&self, input: &'i str) -> TokenStream<'_, 'i> {
+        TokenStream::new(&self.grammar, input)
+    }
 
-            let (slice_lines, slice_newline_offset) = utils::count_new_lines(matching_slice);
-            line += slice_lines;
+    /// Parses a tokenized input.
+    pub fn parse<'i>(
+        &self,
+        tokens: Vec<(Spanned<Token>, &'i str)>,
+    ) -> Result<Tree<'i>, ParsingError> {
+        self.parse_and_trace_internal(tokens.into_iter().map(Ok), false).map(|(_, tree)| tree)
+    }
 
-            if let Some(slice_newline_offset) = slice_newline_offset {
-                last_newline_offset = offset + slice_newline_offset
-            }
+    /// Parses an input into a lossless parse tree, which keeps the trivia of its terminals.
+    ///
+    /// Trivia are the parts of the input that are skipped during tokenization
+    /// (e.g., whitespace and comments), so [Tree::text] of the parse tree is the input.
+    /// If the parse tree has no terminals, the trivia is kept in an end of input terminal.
+    pub fn parse_lossless<'i>(&self, input: &'i str) -> Result<Tree<'i>, ParsingError> {
+        let mut tree = self.parse(self.tokenize(input)?)?;
+        tree.attach_trivia(&self.grammar, input);
+        Ok(tree)
+    }
 
-            tokens.push((token, matching_slice));
-            remaining_input = remaining_input[matching_slice.len()..].trim_start();
+    /// Parses an input using parser-directed lexing.
+    ///
+    /// Each token is scanned among the tokens that are acceptable in the current state of the parser
+    /// (i.e., the tokens in [Parser::action_table] of the state), so tokens that cannot follow
+    /// are not scanned (e.g., a keyword in place of an identifier that matches the same text).
+    /// If none of the acceptable tokens can be scanned, all tokens are tried to report the error.
+    pub fn parse_contextual<'i>(&self, input: &'i str) -> Result<Tree<'i>, ParsingError> {
+        let tokens = ContextualTokenStream(self.token_stream(input));
+        self.parse_and_trace_internal(tokens, false).map(|(_, tree)| tree)
+    }
 
-            // add back to the offset the whitespace that was trimmed
-            let old_offset = offset;
-            offset = input.len() - remaining_input.len();
-            let whitespace = &input[old_offset..offset];
-            let (whitespace_lines, whitespace_newline_offset) = utils::count_new_lines(whitespace);
-            line += whitespace_lines;
+    /// Parses a stream of tokens, consuming them only as they are needed.
+    ///
+    /// Parsing stops at the first error, without consuming the rest of the stream,
+    /// so tokenizing and parsing can be interleaved using [Parser::token_stream].
+    /// If the stream doesn't end with the end of input token, it's assumed to be
+    /// right after the last token of the stream. Tokens are checked against the grammar
+    /// the same way as [Parser::tokenize_with], as they may come from an external lexer.
+    pub fn parse_iter<'i>(
+        &self,
+        tokens: impl IntoIterator<Item = Result<(Spanned<Token>, &'i str), ParsingError>>,
+    ) -> Result<Tree<'i>, ParsingError> {
+        let tokens = CheckedLexer::new(&self.grammar, tokens.into_iter());
+        self.parse_and_trace_internal(tokens, false).map(|(_, tree)| tree)
+    }
 
-            if let Some(whitespace_newline_offset) = whitespace_newline_offset {
-                last_newline_offset = old_offset + whitespace_newline_offset;
-            }
-            // skip the newline character
-            column = input[last_newline_offset..offset].chars().count() + 1;
-        }
-        let eof = Spanned::new(Token::Eof, Span { offset, length: 0, line, column });
-        tokens.push((eof, "\0"));
+    /// Parses a stream of tokens into a lazy stream of events, without building a parse tree.
+    ///
+    /// Tokens are consumed and checked against the grammar the same way as [Parser::parse_iter],
+    /// so an input can be processed with memory bounded by the depth of the parse.
+    pub fn events<'i, I: IntoIterator<Item = Result<(Spanned<Token>, &'i str), ParsingError>>>(
+        &self,
+        tokens: I,
+    ) -> Events<'_, 'i, I::IntoIter> {
+        Events::new(self, tokens.into_iter())
+    }
 
-        Ok(tokens)
+    /// Completes an input prefix with the tokens that can follow it (e.g., for autocompletion).
+    ///
+    /// Tokens of the prefix are fed to a [ParseSession], so the completions are the tokens that
+    /// are eventually shifted after the reductions they cause, not just the tokens in
+    /// [Parser::action_table] of the last state. Only constant and regex tokens are completed,
+    /// and the last token of the prefix is assumed to be complete.
+    pub fn complete(&self, prefix: &str) -> Result<Vec<Completion>, ParsingError> {
+        completion::complete(self, prefix)
     }
 
-    /// Parses a tokenized input.
-    pub fn parse<'i>(
+    /// Reparses an input after an edit, using the parse tree and the tokens of the input before it.
+    ///
+    /// Input is tokenized again from the last token that the scanner doesn't look ahead of into
+    /// the edit, until the tokens after the edit are the same as the old tokens. Subtrees of
+    /// the old parse tree are moved into the new parse tree when their tokens are unchanged and
+    /// the parser is in the state they were built in, so the result is the same as parsing
+    /// the edited input from scratch. Tokens of the edited input are returned along with
+    /// its parse tree, so they can be used for the next edit.
+    ///
+    /// Reused tokens and subtrees keep their slices, which refer to the old input,
+    /// so the old input has to live as long as the new parse tree. Old parse tree has to be
+    /// the parse tree of the old tokens (e.g., not a parse tree with the error nodes of recovery).
+    ///
+    /// Grammars with lexer modes or indentation sensitivity are tokenized from scratch.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the old tokens don't end with the end of input token, the edit is outside of
+    /// the old input, or the length of the input is not the length of the old input after the edit.
+    #[allow(clippy::type_complexity)]
+    pub fn reparse<'i>(
         &self,
+        tree: Tree<'i>,
         tokens: Vec<(Spanned<Token>, &'i str)>,
-    ) -> Result<Tree<'i>, ParsingError> {
-        self.parse_and_trace_internal(tokens, false).map(|(_, tree)| tree)
+        edit: &TextEdit,
+        input: &'i str,
+    ) -> Result<(Vec<(Spanned<Token>, &'i str)>, Tree<'i>), ParsingError> {
+        incremental::reparse(self, tree, tokens, edit, input)
+    }
+
+    /// Creates a resumable parse session, which is fed the tokens of an input one at a time.
+    pub fn session<'i>(&self) -> ParseSession<'_, 'i> {
+        ParseSession::new(self)
     }
 
     /// Traces the parsing of a tokenized input.
@@ -174,11 +260,74 @@ impl Parser {
         &self,
         tokens: Vec<(Spanned<Token>, &'i str)>,
     ) -> Result<(Trace<'i>, Tree<'i>), ParsingError> {
-        self.parse_and_trace_internal(tokens, true)
+        self.parse_and_trace_internal(tokens.into_iter().map(Ok), true)
+    }
+
+    /// Parses a tokenized input using semantic actions, without building a parse tree.
+    ///
+    /// Actions are called as the tokens are shifted and the rules are reduced,
+    /// and the value of the start symbol is returned once the input is accepted.
+    pub fn parse_with<'i, A: SemanticActions<'i>>(
+        &self,
+        tokens: Vec<(Spanned<Token>, &'i str)>,
+        actions: &mut A,
+    ) -> Result<A::Value, ParsingError> {
+        self.parse_with_internal(tokens.into_iter().map(Ok), actions)
+    }
+
+    /// Parses a tokenized input, recovering from syntax errors using the error token.
+    ///
+    /// Recovery is the same as yacc, the states are popped until the error token can be shifted,
+    /// and the tokens are discarded until one of them can follow the error token.
+    /// Syntax errors are not reported until three tokens are shifted after a recovery.
+    ///
+    /// The parse tree contains error nodes for the recovered parts. If the parser cannot recover
+    /// from one of the syntax errors, the parse tree is an error node of the trees that are
+    /// parsed until the syntax error (i.e., the trees in the stack of the parser).
+    pub fn parse_with_recovery<'i>(
+        &self,
+        tokens: Vec<(Spanned<Token>, &'i str)>,
+    ) -> (Tree<'i>, Vec<ParsingError>) {
+        let mut errors = vec![];
+        let mut tree_stack = vec![];
+        let tree = self
+            .parse_internal(tokens.into_iter().map(Ok), None, Some(&mut errors), &mut tree_stack)
+            .unwrap_or(Tree::Error { discarded: tree_stack });
+        (tree, errors)
+    }
+
+    /// Parses a tokenized input into a parse forest, following every action of conflicts.
+    ///
+    /// Unlike [Parser::parse], this works for parsers with conflicts as well
+    /// (e.g., the parser in [ParserError::Conflicts]). End of input is assumed to be
+    /// right after the last token if the tokens don't end with it.
+    pub fn parse_forest<'i>(
+        &self,
+        tokens: Vec<(Spanned<Token>, &'i str)>,
+    ) -> Result<Forest<'i>, ParsingError> {
+        Forest::construct(&self.grammar, &self.parsing_tables, tokens)
     }
 }
 
 impl Parser {
+    /// Internal construction logic.
+    fn construct_internal(grammar: Grammar, method: ParsingMethod) -> Result<Parser, ParserError> {
+        Parser::check_grammar_internal(&grammar)?;
+
+        let first_table = FirstTable::construct(&grammar);
+        let follow_table = FollowTable::construct(&grammar, &first_table);
+        let automaton = match method {
+            ParsingMethod::Lr0 | ParsingMethod::Slr => Automaton::construct_lr0(&grammar),
+            ParsingMethod::Lalr => Automaton::construct(&grammar, &first_table).to_lalr(),
+            ParsingMethod::Lr => Automaton::construct(&grammar, &first_table),
+        };
+        let parsing_tables = ParsingTables::construct(&grammar, &follow_table, &automaton, method)?;
+
+        let parser =
+            Parser { method, grammar, first_table, follow_table, automaton, parsing_tables };
+        parser.check_conflicts_internal()
+    }
+
     /// Internal grammar checks.
     fn check_grammar_internal(grammar: &Grammar) -> Result<(), ParserError> {
         if grammar.rules().is_empty() {
@@ -207,70 +356,179 @@ impl Parser {
                     },
                 }
             }
+            if let Some(precedence) = rule.precedence() {
+                if !grammar.precedences().contains_key(precedence) {
+                    return Err(ParserError::UndefinedPrecedence {
+                        precedence: precedence.clone(),
+                        rule: rule.clone(),
+                    });
+                }
+            }
+        }
+        for (mode, lexer_mode) in grammar.lexer_modes().iter().skip(1) {
+            if lexer_mode.tokens().is_empty() {
+                return Err(ParserError::UndefinedLexerMode { mode: mode.clone() });
+            }
         }
         Ok(())
     }
 
     /// Internal conflict checks.
     fn check_conflicts_internal(self) -> Result<Parser, ParserError> {
+        let mut conflicts = vec![];
         for (state, action_map) in self.action_table().iter().enumerate() {
             for (token, actions) in action_map.iter() {
                 if actions.len() > 1 {
-                    let token = token.clone();
-                    let parser = Box::new(self);
-                    return Err(ParserError::Conflict { parser, state, token });
+                    let state = &self.automaton.states()[state];
+                    conflicts.push(Conflict::construct(&self.grammar, state, token, actions));
                 }
             }
         }
+        if !conflicts.is_empty() {
+            let parser = Box::new(self);
+            return Err(ParserError::Conflicts { parser, conflicts });
+        }
         Ok(self)
     }
 
-    /// Internal parsing logic.
+    /// Internal parsing and tracing logic.
     fn parse_and_trace_internal<'i>(
         &self,
-        mut tokens: Vec<(Spanned<Token>, &'i str)>,
+        tokens: impl Lexer<'i>,
         traced: bool,
     ) -> Result<(Trace<'i>, Tree<'i>), ParsingError> {
+        let mut trace = Trace::default();
+        let tree = self.parse_internal(
+            tokens,
+            if traced { Some(&mut trace) } else { None },
+            None,
+            &mut vec![],
+        )?;
+        Ok((trace, tree))
+    }
+
+    /// Internal parsing logic.
+    ///
+    /// Trees of the states in the state stack are kept in the tree stack,
+    /// so the trees that are parsed until an error are available to the caller.
+    fn parse_internal<'i>(
+        &self,
+        tokens: impl Lexer<'i>,
+        mut trace: Option<&mut Trace<'i>>,
+        mut errors: Option<&mut Vec<ParsingError>>,
+        tree_stack: &mut Vec<Tree<'i>>,
+    ) -> Result<Tree<'i>, ParsingError> {
         let mut state_stack = vec![0];
-        let mut tree_stack = vec![];
-        let mut remaining_tokens = {
-            tokens.reverse();
-            tokens
+        let mut remaining_tokens = UpcomingTokens::new(tokens);
+
+        // Number of tokens to shift before reporting syntax errors again (i.e., `errflag` of yacc).
+        let mut tokens_to_shift_before_reporting = 0_usize;
+
+        // Tokens are scanned among the tokens that are acceptable in the state, if the source allows.
+        // Tokenization errors are reported as is, since they can't be recovered from.
+        let next_token = |remaining_tokens: &mut UpcomingTokens<'i, _>,
+                          errors: &mut Option<&mut Vec<ParsingError>>,
+                          state: usize| {
+            let acceptable = |token: &Token| self.action_table()[state].contains_key(token);
+            remaining_tokens.next(Some(&acceptable)).inspect_err(|error| {
+                if let Some(errors) = errors.as_deref_mut() {
+                    errors.push(error.clone());
+                }
+            })
         };
 
-        let mut trace = Trace::default();
-
-        let (mut current_token, mut current_slice) = remaining_tokens.pop().unwrap();
+        let (mut current_token, mut current_slice) =
+            next_token(&mut remaining_tokens, &mut errors, 0)?;
         loop {
-            let current_state = *state_stack.last().unwrap();
-            let action_to_take = match self.action_table()[current_state].get(current_token.deref())
-            {
-                Some(actions) => {
-                    assert_eq!(actions.len(), 1);
-                    *actions.iter().next().unwrap()
-                },
+            let action_to_take = match self.action_internal(&state_stack, &current_token) {
+                Some(action) => action,
                 None => {
-                    let mut expected = smallvec![];
-                    for (token, _) in self.action_table()[current_state].iter() {
-                        expected.push(token.clone());
+                    // Errors are created only when they are reported or returned,
+                    // since searching for repairs is expensive.
+                    let errors = match errors.as_deref_mut() {
+                        Some(errors) => errors,
+                        None => {
+                            return Err(self.error_internal(
+                                &state_stack,
+                                &current_token,
+                                current_slice,
+                                remaining_tokens.peek(UPCOMING_TOKENS_TO_SEARCH - 1),
+                            ));
+                        },
+                    };
+
+                    if tokens_to_shift_before_reporting == 3 {
+                        // Error token is just shifted, so the token is discarded,
+                        // unless it's the end of file, which means the recovery has failed.
+                        if *current_token == Token::Eof {
+                            let error = self.error_internal(
+                                &state_stack,
+                                &current_token,
+                                current_slice,
+                                remaining_tokens.peek(UPCOMING_TOKENS_TO_SEARCH - 1),
+                            );
+                            if !matches!(errors.last(), Some(ParsingError::UnexpectedEof { .. })) {
+                                errors.push(error.clone());
+                            }
+                            return Err(error);
+                        }
+                        if let Some(Tree::Error { discarded }) = tree_stack.last_mut() {
+                            let (token, span) = current_token.clone().into_components();
+                            discarded.push(Tree::Terminal {
+                                token,
+                                span,
+                                slice: current_slice,
+                                trivia: None,
+                            });
+                        }
+                        (current_token, current_slice) = next_token(
+                            &mut remaining_tokens,
+                            &mut Some(&mut *errors),
+                            *state_stack.last().unwrap(),
+                        )?;
+                        continue;
                     }
 
-                    return Err(if *current_token == Token::Eof {
-                        ParsingError::UnexpectedEof { expected, span: current_token.span().clone() }
-                    } else {
-                        ParsingError::UnexpectedToken {
-                            token: current_slice.into(),
-                            expected,
-                            span: current_token.span().clone(),
+                    // Error is reported even if syntax errors are not reported yet
+                    // when there are no states to recover from it, since parsing stops.
+                    let recoverable =
+                        state_stack.iter().any(|state| self.error_shift_internal(*state).is_some());
+                    let reported = tokens_to_shift_before_reporting == 0 || !recoverable;
+                    if reported {
+                        let error = self.error_internal(
+                            &state_stack,
+                            &current_token,
+                            current_slice,
+                            remaining_tokens.peek(UPCOMING_TOKENS_TO_SEARCH - 1),
+                        );
+                        errors.push(error.clone());
+                        if !recoverable {
+                            return Err(error);
                         }
-                    });
+                    }
+                    tokens_to_shift_before_reporting = 3;
+
+                    let mut discarded = vec![];
+                    loop {
+                        let state = *state_stack.last().unwrap();
+                        if let Some(next_state) = self.error_shift_internal(state) {
+                            discarded.reverse();
+                            state_stack.push(next_state);
+                            tree_stack.push(Tree::Error { discarded });
+                            break;
+                        }
+                        state_stack.pop();
+                        discarded.extend(tree_stack.pop());
+                    }
+                    continue;
                 },
             };
 
-            if traced {
-                let mut remaining_tokens_without_slices =
-                    remaining_tokens.iter().map(|(token, _)| token.clone()).collect::<Vec<_>>();
-                remaining_tokens_without_slices.push(current_token.clone());
+            if let Some(trace) = trace.as_deref_mut() {
+                let mut remaining_tokens_without_slices = vec![current_token.clone()];
+                remaining_tokens_without_slices
+                    .extend(remaining_tokens.peek(usize::MAX).map(|(token, _)| token));
+                remaining_tokens_without_slices.reverse();
 
                 trace.step(Step {
                     state_stack: state_stack.clone(),
@@ -280,111 +538,198 @@ impl Parser {
                 });
             }
 
+            self.step_internal(&mut state_stack, action_to_take);
             match action_to_take {
-                Action::Accept { .. } => {
-                    let parse_tree = Tree::NonTerminal {
-                        symbol: self.grammar.start_symbol().clone(),
-                        pattern: tree_stack,
-                    };
-                    return Ok((trace, parse_tree));
+                Action::Accept => {
+                    return Ok(Parser::accept_internal(tree_stack));
                 },
                 Action::Shift { next_state } => {
                     let (token, span) = current_token.clone().into_components();
-                    state_stack.push(next_state);
-                    tree_stack.push(Tree::Terminal { token, span, slice: current_slice });
-                    (current_token, current_slice) = remaining_tokens.pop().unwrap();
+                    tree_stack.push(Tree::Terminal {
+                        token,
+                        span,
+                        slice: current_slice,
+                        trivia: None,
+                    });
+                    (current_token, current_slice) =
+                        next_token(&mut remaining_tokens, &mut errors, next_state)?;
+
+                    tokens_to_shift_before_reporting =
+                        tokens_to_shift_before_reporting.saturating_sub(1);
                 },
                 Action::Reduce { rule_index } => {
-                    let rule = &self.grammar.rules()[rule_index];
-                    let pattern_length =
-                        if rule.is_empty_pattern() { 0 } else { rule.pattern().len() };
+                    self.reduce_internal(rule_index, tree_stack);
+                },
+            }
+        }
+    }
 
-                    let symbol = rule.symbol().clone();
-                    let pattern =
-                        tree_stack.split_off(tree_stack.len().saturating_sub(pattern_length));
+    /// Internal error token shifting logic, which returns the state after shifting
+    /// the error token in a state, if the error token can be shifted in it.
+    fn error_shift_internal(&self, state: usize) -> Option<usize> {
+        self.action_table()[state].get(&Token::Error).and_then(|actions| {
+            actions.iter().find_map(|action| {
+                match action {
+                    Action::Shift { next_state } => Some(*next_state),
+                    _ => None,
+                }
+            })
+        })
+    }
 
-                    tree_stack.push(Tree::NonTerminal { symbol, pattern });
+    /// Internal action lookup logic, which returns the action to take for a token
+    /// in the state on top of a state stack, if the token is acceptable in the state.
+    pub(crate) fn action_internal(&self, state_stack: &[usize], token: &Token) -> Option<Action> {
+        let current_state = *state_stack.last().unwrap();
+        let actions = self.action_table()[current_state].get(token)?;
+        assert_eq!(actions.len(), 1);
+        Some(*actions.iter().next().unwrap())
+    }
 
-                    let new_state_stack_len = state_stack.len().saturating_sub(pattern_length);
-                    state_stack.truncate(new_state_stack_len);
+    /// Internal step logic, which takes an action on a state stack.
+    ///
+    /// Shifts push the next state, and reductions replace the states of the pattern of a rule
+    /// with the state after the symbol of the rule. Trees or values of the symbols
+    /// are left to the callers (e.g., using [Parser::reduce_internal]).
+    pub(crate) fn step_internal(&self, state_stack: &mut Vec<usize>, action: Action) {
+        match action {
+            Action::Accept => {},
+            Action::Shift { next_state } => {
+                state_stack.push(next_state);
+            },
+            Action::Reduce { rule_index } => {
+                let pattern_length = self.pattern_length_internal(rule_index);
 
-                    let new_state = *state_stack.last().unwrap();
-                    match self.goto_table()[new_state].get(rule.symbol()) {
-                        Some(state) => {
-                            state_stack.push(*state);
-                        },
-                        None => {
-                            unreachable!();
-                        },
-                    }
-                },
-            }
+                let new_state_stack_len = state_stack.len().saturating_sub(pattern_length);
+                state_stack.truncate(new_state_stack_len);
+
+                let rule = &self.grammar.rules()[rule_index];
+                let new_state = *state_stack.last().unwrap();
+                match self.goto_table()[new_state].get(rule.symbol()) {
+                    Some(state) => {
+                        state_stack.push(*state);
+                    },
+                    None => {
+                        unreachable!();
+                    },
+                }
+            },
         }
     }
-}
 
-impl Parser {
-    /// Dumps the parser to stdout.
-    pub fn dump(&self) {
-        {
-            let mut pretty_grammar = Table::new();
+    /// Internal reduction logic, which replaces the trees of the pattern of a rule
+    /// with the tree of its symbol.
+    pub(crate) fn reduce_internal<'i>(&self, rule_index: usize, tree_stack: &mut Vec<Tree<'i>>) {
+        let rule = &self.grammar.rules()[rule_index];
+        let pattern_length = self.pattern_length_internal(rule_index);
 
-            pretty_grammar.add_row(row![cbFy->"Grammar"]);
-            {
-                let mut pretty_rules = Table::new();
-                pretty_rules.set_format(*prettytable::format::consts::FORMAT_CLEAN);
+        let symbol = rule.symbol().clone();
+        let pattern = tree_stack.split_off(tree_stack.len().saturating_sub(pattern_length));
+        tree_stack.push(Tree::reduced(self.grammar.repetition_symbols(), symbol, pattern));
+    }
 
-                for (rule_index, rule) in self.grammar.rules().iter().enumerate() {
-                    pretty_rules.add_row(row![r->format!("{})", rule_index + 1), rule]);
-                }
-                if !self.grammar.regular_expressions().is_empty() {
-                    pretty_rules.add_row(row![r->"", ""]);
-                }
-                for (regex_token, regex) in self.grammar.regular_expressions().iter() {
-                    pretty_rules.add_row(row![r->"", format!("{} -> /{}/", regex_token, regex)]);
-                }
+    /// Internal acceptance logic, which returns the tree or the value of the start symbol.
+    ///
+    /// Accept only happens on the end of file in `S' -> S . $`,
+    /// so the tree or the value of the start symbol is the only one in the stack.
+    pub(crate) fn accept_internal<T>(stack: &mut Vec<T>) -> T {
+        stack.pop().unwrap()
+    }
 
-                pretty_grammar.add_row(row![pretty_rules]);
-            }
+    /// Internal logic to get the number of states, trees or values that
+    /// the pattern of a rule replaces when it's reduced (i.e., zero for empty patterns).
+    pub(crate) fn pattern_length_internal(&self, rule_index: usize) -> usize {
+        let rule = &self.grammar.rules()[rule_index];
+        if rule.is_empty_pattern() { 0 } else { rule.pattern().len() }
+    }
+}
 
-            pretty_grammar.printstd();
+impl Parser {
+    /// Internal parsing logic using semantic actions.
+    fn parse_with_internal<'i, A: SemanticActions<'i>>(
+        &self,
+        tokens: impl Iterator<Item = Result<(Spanned<Token>, &'i str), ParsingError>>,
+        actions: &mut A,
+    ) -> Result<A::Value, ParsingError> {
+        let mut value_stack = vec![];
+        let mut events = self.events(tokens);
+        // Events end with either an accept or an error, so the loop stops before they end.
+        loop {
+            match events.next_internal() {
+                Event::Shift { token, span, slice } => {
+                    value_stack.push(actions.shift(Spanned::new(token, span), slice));
+                },
+                Event::Reduce { rule_index, child_count } => {
+                    let rule = &self.grammar.rules()[rule_index];
+                    let values = value_stack.split_off(value_stack.len() - child_count);
+                    value_stack.push(actions.reduce(rule_index, rule, values));
+                },
+                Event::Accept => {
+                    return Ok(Parser::accept_internal(&mut value_stack));
+                },
+                Event::Error { error } => {
+                    return Err(error);
+                },
+            }
         }
-        {
-            let mut pretty_first_and_follow_tables = Table::new();
-
-            pretty_first_and_follow_tables
-                .add_row(row![cbFy->"Symbol", cbFy->"First Set", cbFy->"Follow Set"]);
-            for (symbol, first_set) in self.first_table.iter() {
-                let first_set_formatted = {
-                    if first_set.is_empty() {
-                        "{}".to_owned()
-                    } else {
-                        format!("{{ {} }}", first_set.iter().join(", "))
-                    }
-                };
-                let follow_set_formatted = {
-                    match self.follow_table.get(symbol) {
-                        Some(follow_set) if !follow_set.is_empty() => {
-                            format!("{{ {} }}", follow_set.iter().join(", "))
-                        },
-                        _ => "{}".to_owned(),
-                    }
-                };
-                pretty_first_and_follow_tables.add_row(row![
-                    symbol,
-                    first_set_formatted,
-                    follow_set_formatted
-                ]);
+    }
+
+    /// Internal parsing error creation logic.
+    ///
+    /// Repairs are searched using the current token and the upcoming tokens after it.
+    pub(crate) fn error_internal<'i>(
+        &self,
+        state_stack: &[usize],
+        current_token: &Spanned<Token>,
+        current_slice: &'i str,
+        upcoming_tokens: impl Iterator<Item = (Spanned<Token>, &'i str)>,
+    ) -> ParsingError {
+        let current_state = *state_stack.last().unwrap();
+
+        let mut expected = smallvec![];
+        for (token, _) in self.action_table()[current_state].iter() {
+            if *token != Token::Error {
+                expected.push(token.clone());
             }
+        }
 
-            pretty_first_and_follow_tables.printstd();
+        let repairs = Repair::search(
+            self,
+            state_stack,
+            std::iter::once((current_token.clone(), current_slice)).chain(upcoming_tokens),
+        );
+
+        if **current_token == Token::Eof {
+            ParsingError::UnexpectedEof { expected, span: current_token.span().clone(), repairs }
+        } else {
+            ParsingError::UnexpectedToken {
+                token: current_token.describe(current_slice),
+                expected,
+                span: current_token.span().clone(),
+                repairs,
+            }
         }
+    }
+}
+
+impl Parser {
+    /// Dumps the parser to stdout.
+    pub fn dump(&self) {
+        Parser::dump_grammar_internal(&self.grammar);
+        Parser::dump_first_and_follow_tables_internal(&self.first_table, &self.follow_table);
         {
             let mut pretty_automaton = Table::new();
 
-            pretty_automaton.add_row(
-                row![cbFy->"State", cbFy->"Items", cbFy->"Lookaheads", cbFy->"Transitions"],
-            );
+            // Items of LR(0) automatons don't have lookaheads to show.
+            let has_lookaheads = matches!(self.method, ParsingMethod::Lalr | ParsingMethod::Lr);
+
+            if has_lookaheads {
+                pretty_automaton.add_row(
+                    row![cbFy->"State", cbFy->"Items", cbFy->"Lookaheads", cbFy->"Transitions"],
+                );
+            } else {
+                pretty_automaton.add_row(row![cbFy->"State", cbFy->"Items", cbFy->"Transitions"]);
+            }
             for state in self.automaton.states().iter() {
                 let mut pretty_items = Table::new();
                 {
@@ -419,23 +764,19 @@ impl Parser {
                     pretty_transitions.set_format(FormatBuilder::new().padding(1, 1).build());
                 }
 
-                pretty_automaton.add_row(
-                    row![state.id(), pretty_items, pretty_lookaheads, c->pretty_transitions],
-                );
+                if has_lookaheads {
+                    pretty_automaton.add_row(
+                        row![state.id(), pretty_items, pretty_lookaheads, c->pretty_transitions],
+                    );
+                } else {
+                    pretty_automaton.add_row(row![state.id(), pretty_items, c->pretty_transitions]);
+                }
             }
 
             pretty_automaton.printstd();
         }
         {
-            let all_tokens = self
-                .grammar
-                .constant_tokens()
-                .iter()
-                .cloned()
-                .map(Token::Constant)
-                .chain(self.grammar.regular_expressions().keys().cloned().map(Token::Regex))
-                .chain(std::iter::once(Token::Eof))
-                .collect::<Vec<_>>();
+            let all_tokens = self.grammar.input_tokens();
 
             let longest_state_length = format_smolstr!("{}", self.automaton.states().len()).len();
             let longest_actions_length = self
@@ -448,7 +789,8 @@ impl Parser {
                     actions.len()
                 })
                 .max()
-                .unwrap_or(0);
+                .unwrap_or(0)
+                + usize::from(!self.parsing_tables.resolved_conflicts().is_empty());
 
             fn pad(string: impl ToString, to: usize) -> String {
                 let string = string.to_string();
@@ -530,16 +872,24 @@ impl Parser {
                             padding += 2;
                         }
                         padding = padding.max(longest_actions_length);
+
+                        let resolved = self
+                            .parsing_tables
+                            .resolved_conflicts()
+                            .iter()
+                            .any(|conflict| conflict.state() == i && conflict.token() == token);
+                        let marker = if resolved { "*" } else { "" };
+
                         match action_map.get(token) {
                             Some(actions) => {
                                 let actions = actions
                                     .iter()
                                     .map(|action| format_smolstr!("{}", action))
                                     .join(", ");
-                                actions_row.add_cell(cell![pad(actions, padding)]);
+                                actions_row.add_cell(cell![pad(actions + marker, padding)]);
                             },
                             None => {
-                                actions_row.add_cell(cell![pad("-", padding)]);
+                                actions_row.add_cell(cell![pad(format!("-{}", marker), padding)]);
                             },
                         }
                     }
@@ -585,6 +935,435 @@ impl Parser {
 
             pretty_parsing_tables.printstd()
         }
+        if !self.parsing_tables.resolved_conflicts().is_empty() {
+            let mut pretty_resolved_conflicts = Table::new();
+
+            pretty_resolved_conflicts.add_row(row![
+                cbFy->"State",
+                cbFy->"Token",
+                cbFy->"Shift",
+                cbFy->"Reduce",
+                cbFy->"Resolution",
+            ]);
+            for conflict in self.parsing_tables.resolved_conflicts() {
+                let resolution = match conflict.chosen_action() {
+                    Some(action @ Action::Shift { .. }) => format!("{} (shift)", action),
+                    Some(action) => format!("{} (reduce)", action),
+                    None => "- (error)".to_owned(),
+                };
+                pretty_resolved_conflicts.add_row(row![
+                    conflict.state(),
+                    conflict.token(),
+                    conflict.shift(),
+                    conflict.reduce(),
+                    resolution,
+                ]);
+            }
+
+            pretty_resolved_conflicts.printstd();
+        }
+    }
+}
+
+impl Parser {
+    /// Internal grammar dumping logic.
+    fn dump_grammar_internal(grammar: &Grammar) {
+        let mut pretty_grammar = Table::new();
+
+        pretty_grammar.add_row(row![cbFy->"Grammar"]);
+        {
+            let mut pretty_rules = Table::new();
+            pretty_rules.set_format(*prettytable::format::consts::FORMAT_CLEAN);
+
+            for (rule_index, rule) in grammar.rules().iter().enumerate() {
+                pretty_rules.add_row(row![r->format!("{})", rule_index + 1), rule]);
+            }
+            if !grammar.regular_expressions().is_empty() {
+                pretty_rules.add_row(row![r->"", ""]);
+            }
+            for (regex_token, regex) in grammar.regular_expressions().iter() {
+                pretty_rules.add_row(row![r->"", format!("{} -> /{}/", regex_token, regex)]);
+            }
+            let token_declarations = grammar.token_declarations();
+            if !token_declarations.is_empty() {
+                pretty_rules.add_row(row![r->"", ""]);
+            }
+            for token_declaration in token_declarations {
+                pretty_rules.add_row(row![r->"", token_declaration]);
+            }
+            if !grammar.ignored_regular_expressions().is_empty()
+                || grammar.is_whitespace_significant()
+                || grammar.is_indentation_sensitive()
+            {
+                pretty_rules.add_row(row![r->"", ""]);
+            }
+            for regex in grammar.ignored_regular_expressions().iter() {
+                pretty_rules.add_row(row![r->"", format!("%ignore -> /{}/", regex)]);
+            }
+            if grammar.is_whitespace_significant() {
+                pretty_rules.add_row(row![r->"", "%significant_whitespace"]);
+            }
+            if grammar.is_indentation_sensitive() {
+                pretty_rules.add_row(row![r->"", "%indentation"]);
+            }
+            let lexer_mode_declarations = grammar.lexer_mode_declarations();
+            if !lexer_mode_declarations.is_empty() {
+                pretty_rules.add_row(row![r->"", ""]);
+            }
+            for lexer_mode_declaration in lexer_mode_declarations {
+                pretty_rules.add_row(row![r->"", lexer_mode_declaration]);
+            }
+            if !grammar.precedences().is_empty() {
+                pretty_rules.add_row(row![r->"", ""]);
+            }
+            for precedence_declaration in grammar.precedence_declarations() {
+                pretty_rules.add_row(row![r->"", precedence_declaration]);
+            }
+
+            pretty_grammar.add_row(row![pretty_rules]);
+        }
+
+        pretty_grammar.printstd();
+    }
+
+    /// Internal first and follow tables dumping logic.
+    fn dump_first_and_follow_tables_internal(first_table: &FirstTable, follow_table: &FollowTable) {
+        let mut pretty_first_and_follow_tables = Table::new();
+
+        pretty_first_and_follow_tables
+            .add_row(row![cbFy->"Symbol", cbFy->"First Set", cbFy->"Follow Set"]);
+        for (symbol, first_set) in first_table.iter() {
+            let first_set_formatted = {
+                if first_set.is_empty() {
+                    "{}".to_owned()
+                } else {
+                    format!("{{ {} }}", first_set.iter().join(", "))
+                }
+            };
+            let follow_set_formatted = {
+                match follow_table.get(symbol) {
+                    Some(follow_set) if !follow_set.is_empty() => {
+                        format!("{{ {} }}", follow_set.iter().join(", "))
+                    },
+                    _ => "{}".to_owned(),
+                }
+            };
+            pretty_first_and_follow_tables.add_row(row![
+                symbol,
+                first_set_formatted,
+                follow_set_formatted
+            ]);
+        }
+
+        pretty_first_and_follow_tables.printstd();
+    }
+}
+
+
+/// LL(1) parser of a grammar.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[derive(Debug)]
+pub struct LlParser {
+    grammar: Grammar,
+    first_table: FirstTable,
+    follow_table: FollowTable,
+    ll_table: LlTable,
+}
+
+impl LlParser {
+    /// Creates an LL(1) parser of a grammar.
+    pub fn new(grammar: Grammar) -> Result<LlParser, ParserError> {
+        Parser::check_grammar_internal(&grammar)?;
+
+        let first_table = FirstTable::construct(&grammar);
+        let follow_table = FollowTable::construct(&grammar, &first_table);
+        let ll_table = LlTable::construct(&grammar, &first_table, &follow_table);
+
+        let parser = LlParser { grammar, first_table, follow_table, ll_table };
+        parser.check_conflicts_internal()
+    }
+}
+
+impl LlParser {
+    /// Gets the grammar of the parser.
+    pub fn grammar(&self) -> &Grammar {
+        &self.grammar
+    }
+
+    /// Gets the first table of the symbols in the grammar of the parser.
+    pub fn first_table(&self) -> &FirstTable {
+        &self.first_table
+    }
+
+    /// Gets the follow table of the symbols in the grammar of the parser.
+    pub fn follow_table(&self) -> &FollowTable {
+        &self.follow_table
+    }
+
+    /// Gets the predictive parsing table of the parser.
+    pub fn ll_table(&self) -> &LlTable {
+        &self.ll_table
+    }
+}
+
+impl LlParser {
+    /// Tokenizes an input into a stream of tokens and their corresponding input slices.
+    pub fn tokenize<'i>(
+        &self,
+        input: &'i str,
+    ) -> Result<Vec<(Spanned<Token>, &'i str)>, ParsingError> {
+        TokenStream::new(&self.grammar, input).collect()
+    }
+
+    /// Tokenizes an input, recovering from tokenization errors.
+    pub fn tokenize_with_recovery<'i>(
+        &self,
+        input: &'i str,
+    ) -> (Vec<(Spanned<Token>, &'i str)>, Vec<ParsingError>) {
+        TokenStream::with_recovery(&self.grammar, input).partition_result()
+    }
+
+    /// Tokenizes an input using an external lexer, checking the lexed tokens against the grammar.
+    pub fn tokenize_with<'i>(
+        &self,
+        lexer: impl Lexer<'i>,
+    ) -> Result<Vec<(Spanned<Token>, &'i str)>, ParsingError> {
+        lexer::tokenize_with(&self.grammar, lexer)
+    }
+
+    /// Parses a tokenized input.
+    pub fn parse<'i>(
+        &self,
+        tokens: Vec<(Spanned<Token>, &'i str)>,
+    ) -> Result<Tree<'i>, ParsingError> {
+        self.parse_and_trace_internal(tokens, false).map(|(_, tree)| tree)
+    }
+
+    /// Parses an input into a lossless parse tree, which keeps the trivia of its terminals.
+    pub fn parse_lossless<'i>(&self, input: &'i str) -> Result<Tree<'i>, ParsingError> {
+        let mut tree = self.parse(self.tokenize(input)?)?;
+        tree.attach_trivia(&self.grammar, input);
+        Ok(tree)
+    }
+
+    /// Traces the parsing of a tokenized input.
+    pub fn trace<'i>(
+        &self,
+        tokens: Vec<(Spanned<Token>, &'i str)>,
+    ) -> Result<(LlTrace, Tree<'i>), ParsingError> {
+        self.parse_and_trace_internal(tokens, true)
+    }
+}
+
+impl LlParser {
+    /// Internal conflict checks.
+    fn check_conflicts_internal(self) -> Result<LlParser, ParserError> {
+        let mut conflicts = vec![];
+        for (symbol, rules_of_symbol) in self.ll_table.iter() {
+            for (token, rule_indices) in rules_of_symbol.iter() {
+                if rule_indices.len() > 1 {
+                    conflicts.push(LlConflict::construct(
+                        &self.grammar,
+                        &self.first_table,
+                        symbol,
+                        token,
+                        rule_indices,
+                    ));
+                }
+            }
+        }
+        if !conflicts.is_empty() {
+            let parser = Box::new(self);
+            return Err(ParserError::LlConflicts { parser, conflicts });
+        }
+        Ok(self)
+    }
+
+    /// Internal parsing logic.
+    fn parse_and_trace_internal<'i>(
+        &self,
+        mut tokens: Vec<(Spanned<Token>, &'i str)>,
+        traced: bool,
+    ) -> Result<(LlTrace, Tree<'i>), ParsingError> {
+        let mut stack = vec![
+            AtomicPattern::Token(Token::Eof),
+            AtomicPattern::Symbol(self.grammar.start_symbol().clone()),
+        ];
+
+        // Trees of the expanded symbols, along with the number of trees they're waiting for.
+        let mut pending_trees = Vec::<(Symbol, Vec<Tree<'i>>, usize)>::new();
+        let mut parse_tree = None;
+
+        let mut remaining_tokens = {
+            tokens.reverse();
+            tokens
+        };
+
+        let mut trace = LlTrace::default();
+
+        let (mut current_token, mut current_slice) = remaining_tokens.pop().unwrap();
+        loop {
+            let top = stack.last().unwrap();
+            let action_to_take = match top {
+                AtomicPattern::Symbol(symbol) => {
+                    let rules_of_symbol = &self.ll_table[symbol];
+                    match rules_of_symbol.get(current_token.deref()) {
+                        Some(rule_indices) => {
+                            assert_eq!(rule_indices.len(), 1);
+                            LlAction::Expand { rule_index: *rule_indices.iter().next().unwrap() }
+                        },
+                        None => {
+                            let expected = rules_of_symbol.keys().cloned().collect();
+                            return Err(self.error_internal(
+                                expected,
+                                current_token,
+                                current_slice,
+                            ));
+                        },
+                    }
+                },
+                AtomicPattern::Token(token) => {
+                    if *token != *current_token {
+                        let expected = smallvec![token.clone()];
+                        return Err(self.error_internal(expected, current_token, current_slice));
+                    }
+                    if *token == Token::Eof {
+                        LlAction::Accept
+                    } else {
+                        LlAction::Match { token: token.clone() }
+                    }
+                },
+            };
+
+            if traced {
+                let mut remaining_tokens_without_slices =
+                    remaining_tokens.iter().map(|(token, _)| token.clone()).collect::<Vec<_>>();
+                remaining_tokens_without_slices.push(current_token.clone());
+
+                trace.step(LlStep {
+                    stack: stack.clone(),
+                    remaining_tokens: remaining_tokens_without_slices,
+                    action_taken: action_to_take.clone(),
+                });
+            }
+
+            stack.pop();
+            let completed_tree = match action_to_take {
+                LlAction::Accept => {
+                    return Ok((trace, parse_tree.unwrap()));
+                },
+                LlAction::Match { .. } => {
+                    let (token, span) = current_token.clone().into_components();
+                    let tree = Tree::Terminal { token, span, slice: current_slice, trivia: None };
+                    (current_token, current_slice) = remaining_tokens.pop().unwrap();
+                    tree
+                },
+                LlAction::Expand { rule_index } => {
+                    let rule = &self.grammar.rules()[rule_index];
+                    let pattern = rule
+                        .pattern()
+                        .iter()
+                        .filter(|atomic_pattern| **atomic_pattern != Token::Empty.into())
+                        .cloned()
+                        .collect::<Vec<_>>();
+
+                    // Patterns that consist only of empty tokens (e.g., `A -> '' ''`)
+                    // are completed right away, as nothing will be added to them.
+                    if pattern.is_empty() {
+                        Tree::NonTerminal { symbol: rule.symbol().clone(), pattern: vec![] }
+                    } else {
+                        pending_trees.push((rule.symbol().clone(), vec![], pattern.len()));
+                        stack.extend(pattern.into_iter().rev());
+                        continue;
+                    }
+                },
+            };
+
+            // Completed trees are added to the tree of the symbol they're expanded from,
+            // which might complete that tree as well.
+            let mut completed_tree = completed_tree;
+            loop {
+                match pending_trees.last_mut() {
+                    Some((_, pattern, length)) => {
+                        pattern.push(completed_tree);
+                        if pattern.len() < *length {
+                            break;
+                        }
+                        let (symbol, pattern, _) = pending_trees.pop().unwrap();
+                        completed_tree = Tree::NonTerminal { symbol, pattern };
+                    },
+                    None => {
+                        parse_tree = Some(completed_tree);
+                        break;
+                    },
+                }
+            }
+        }
+    }
+
+    /// Internal parsing error creation logic.
+    fn error_internal(
+        &self,
+        expected: SmallVec<[Token; 2]>,
+        current_token: Spanned<Token>,
+        current_slice: &str,
+    ) -> ParsingError {
+        // Repairs are searched using the action table of LR parsers, so they are not available.
+        if *current_token == Token::Eof {
+            ParsingError::UnexpectedEof {
+                expected,
+                span: current_token.span().clone(),
+                repairs: vec![],
+            }
+        } else {
+            ParsingError::UnexpectedToken {
+                token: current_token.describe(current_slice),
+                expected,
+                span: current_token.span().clone(),
+                repairs: vec![],
+            }
+        }
+    }
+}
+
+impl LlParser {
+    /// Dumps the parser to stdout.
+    pub fn dump(&self) {
+        Parser::dump_grammar_internal(&self.grammar);
+        Parser::dump_first_and_follow_tables_internal(&self.first_table, &self.follow_table);
+
+        let all_tokens = self.grammar.input_tokens();
+
+        let mut pretty_ll_table = Table::new();
+        {
+            let mut header = Row::empty();
+            header.add_cell(cell![cbFy->"Symbol"]);
+            for token in all_tokens.iter() {
+                header.add_cell(cell![cbFy->token]);
+            }
+            pretty_ll_table.add_row(header);
+
+            for (symbol, rules_of_symbol) in self.ll_table.iter() {
+                let mut row = Row::empty();
+                row.add_cell(cell![symbol]);
+                for token in all_tokens.iter() {
+                    match rules_of_symbol.get(token) {
+                        Some(rule_indices) => {
+                            let rules =
+                                rule_indices.iter().map(|rule_index| rule_index + 1).join(", ");
+                            row.add_cell(cell![c->rules]);
+                        },
+                        None => {
+                            row.add_cell(cell![c->"-"]);
+                        },
+                    }
+                }
+                pretty_ll_table.add_row(row);
+            }
+        }
+        pretty_ll_table.printstd();
     }
 }
 
@@ -600,6 +1379,16 @@ impl Parser {
     pub fn new_lalr_wasm(grammar: Grammar) -> Result<Parser, WasmParserError> {
         Ok(Parser::lalr(grammar)?)
     }
+
+    /// Creates an LR(0) parser of a grammar (WASM).
+    pub fn new_lr0_wasm(grammar: Grammar) -> Result<Parser, WasmParserError> {
+        Ok(Parser::lr0(grammar)?)
+    }
+
+    /// Creates an SLR(1) parser of a grammar (WASM).
+    pub fn new_slr_wasm(grammar: Grammar) -> Result<Parser, WasmParserError> {
+        Ok(Parser::slr(grammar)?)
+    }
 }
 
 #[cfg(feature = "wasm")]
@@ -647,6 +1436,132 @@ impl Parser {
         }
     }
 
+    /// Tokenizes an input, recovering from tokenization errors (WASM).
+    pub fn tokenize_with_recovery_wasm(&self, input: &str) -> Result<Vec<JsValue>, JsValue> {
+        let (tokens, errors) = self.tokenize_with_recovery(input);
+        let tokens = serde_wasm_bindgen::to_value(&tokens)?;
+        let errors = serde_wasm_bindgen::to_value(&errors)?;
+        Ok(vec![tokens, errors])
+    }
+
+    /// Parses a tokenized input (WASM).
+    pub fn parse_wasm(&self, input: &str) -> Result<JsValue, JsValue> {
+        let tokens = self.tokenize(input);
+        let tokens = match tokens {
+            Ok(tokens) => tokens,
+            Err(error) => return Err(serde_wasm_bindgen::to_value(&error)?),
+        };
+        match self.parse(tokens) {
+            Ok(tree) => Ok(serde_wasm_bindgen::to_value(&tree)?),
+            Err(error) => Err(serde_wasm_bindgen::to_value(&error)?),
+        }
+    }
+
+    /// Traces the parsing of a tokenized input (WASM).
+    pub fn trace_wasm(&self, input: &str) -> Result<Vec<JsValue>, JsValue> {
+        let tokens = self.tokenize(input);
+        let tokens = match tokens {
+            Ok(tokens) => tokens,
+            Err(error) => return Err(serde_wasm_bindgen::to_value(&error)?),
+        };
+        match self.trace(tokens) {
+            Ok((trace, tree)) => {
+                let trace = serde_wasm_bindgen::to_value(&trace)?;
+                let tree = serde_wasm_bindgen::to_value(&tree)?;
+                Ok(vec![trace, tree])
+            },
+            Err(error) => Err(serde_wasm_bindgen::to_value(&error)?),
+        }
+    }
+
+    /// Parses a tokenized input into a parse forest and its parse trees up to a limit (WASM).
+    pub fn parse_forest_wasm(
+        &self,
+        input: &str,
+        max_trees: usize,
+    ) -> Result<Vec<JsValue>, JsValue> {
+        let tokens = self.tokenize(input);
+        let tokens = match tokens {
+            Ok(tokens) => tokens,
+            Err(error) => return Err(serde_wasm_bindgen::to_value(&error)?),
+        };
+        match self.parse_forest(tokens) {
+            Ok(forest) => {
+                let trees = serde_wasm_bindgen::to_value(&forest.trees(max_trees))?;
+                let forest = serde_wasm_bindgen::to_value(&forest)?;
+                Ok(vec![forest, trees])
+            },
+            Err(error) => Err(serde_wasm_bindgen::to_value(&error)?),
+        }
+    }
+
+    /// Parses an input, recovering from tokenization errors and syntax errors (WASM).
+    pub fn parse_with_recovery_wasm(&self, input: &str) -> Result<Vec<JsValue>, JsValue> {
+        let (tokens, mut errors) = self.tokenize_with_recovery(input);
+        let (tree, syntax_errors) = self.parse_with_recovery(tokens);
+        errors.extend(syntax_errors);
+        let tree = serde_wasm_bindgen::to_value(&tree)?;
+        let errors = serde_wasm_bindgen::to_value(&errors)?;
+        Ok(vec![tree, errors])
+    }
+
+    /// Parses an input using parser-directed lexing (WASM).
+    pub fn parse_contextual_wasm(&self, input: &str) -> Result<JsValue, JsValue> {
+        match self.parse_contextual(input) {
+            Ok(tree) => Ok(serde_wasm_bindgen::to_value(&tree)?),
+            Err(error) => Err(serde_wasm_bindgen::to_value(&error)?),
+        }
+    }
+
+    /// Completes an input prefix with the tokens that can follow it (WASM).
+    pub fn complete_wasm(&self, prefix: &str) -> Result<JsValue, JsValue> {
+        match self.complete(prefix) {
+            Ok(completions) => Ok(serde_wasm_bindgen::to_value(&completions)?),
+            Err(error) => Err(serde_wasm_bindgen::to_value(&error)?),
+        }
+    }
+}
+
+
+#[cfg(feature = "wasm")]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl LlParser {
+    /// Creates an LL(1) parser of a grammar (WASM).
+    pub fn new_wasm(grammar: Grammar) -> Result<LlParser, WasmParserError> {
+        Ok(LlParser::new(grammar)?)
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl LlParser {
+    /// Gets the first table of the symbols in the grammar of the parser (WASM).
+    pub fn first_table_wasm(&self) -> Result<JsValue, JsValue> {
+        Ok(serde_wasm_bindgen::to_value(&self.first_table)?)
+    }
+
+    /// Gets the follow table of the symbols in the grammar of the parser (WASM).
+    pub fn follow_table_wasm(&self) -> Result<JsValue, JsValue> {
+        Ok(serde_wasm_bindgen::to_value(&self.follow_table)?)
+    }
+
+    /// Gets the predictive parsing table of the parser (WASM).
+    pub fn ll_table_wasm(&self) -> Result<JsValue, JsValue> {
+        Ok(serde_wasm_bindgen::to_value(&self.ll_table)?)
+    }
+}
+
+#[cfg(feature = "wasm")]
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+impl LlParser {
+    /// Tokenizes an input into a stream of tokens and their corresponding input slices (WASM).
+    pub fn tokenize_wasm(&self, input: &str) -> Result<JsValue, JsValue> {
+        match self.tokenize(input) {
+            Ok(tokens) => Ok(serde_wasm_bindgen::to_value(&tokens)?),
+            Err(error) => Err(serde_wasm_bindgen::to_value(&error)?),
+        }
+    }
+
     /// Parses a tokenized input (WASM).
     pub fn parse_wasm(&self, input: &str) -> Result<JsValue, JsValue> {
         let tokens = self.tokenize(input);
@@ -0,0 +1,251 @@
+use crate::prelude::*;
+
+
+/// Maximum cost (i.e., number of edits) of a repair.
+const MAX_REPAIR_COST: usize = 3;
+
+/// Number of tokens that need to be parsed after the last edit of a repair to accept it.
+const TOKENS_TO_PARSE_AFTER_REPAIR: usize = 3;
+
+/// Maximum number of repairs to find for a syntax error.
+const MAX_REPAIRS: usize = 3;
+
+/// Maximum number of configurations to explore for each cost while searching for repairs.
+const SEARCH_LIMIT: usize = 10_000;
+
+/// Number of upcoming tokens (including the unexpected one) that are looked at to search repairs.
+///
+/// Repairs can't look further than deleting as many tokens as possible
+/// and parsing the required number of tokens after that.
+pub const UPCOMING_TOKENS_TO_SEARCH: usize = MAX_REPAIR_COST + TOKENS_TO_PARSE_AFTER_REPAIR + 1;
+
+
+/// Edit (e.g., `insert ')' at 3:7`) of a repair.
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Edit {
+    /// Inserts a token before the token at the span.
+    Insert { token: Token, span: Span },
+    /// Deletes the token at the span.
+    Delete { token: SmolStr, span: Span },
+    /// Replaces the token at the span with another token.
+    Replace { token: SmolStr, replacement: Token, span: Span },
+}
+
+impl Edit {
+    /// Gets the span of the edit.
+    pub fn span(&self) -> &Span {
+        match self {
+            Edit::Insert { span, .. } | Edit::Delete { span, .. } | Edit::Replace { span, .. } => {
+                span
+            },
+        }
+    }
+}
+
+impl Display for Edit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let span = self.span();
+        let position = format_smolstr!("{}:{}", span.line, span.column).cyan();
+        match self {
+            Edit::Insert { token, .. } => {
+                write!(f, "insert {} at {}", format_smolstr!("{}", token).green(), position)
+            },
+            Edit::Delete { token, .. } => {
+                write!(f, "delete {} at {}", token.green(), position)
+            },
+            Edit::Replace { token, replacement, .. } => {
+                write!(
+                    f,
+                    "replace {} with {} at {}",
+                    token.green(),
+                    format_smolstr!("{}", replacement).green(),
+                    position,
+                )
+            },
+        }
+    }
+}
+
+
+/// Repair (e.g., `insert ')' at 3:7 and delete ',' at 3:9`) of a syntax error.
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Repair {
+    edits: Vec<Edit>,
+}
+
+impl Repair {
+    /// Searches the cheapest repairs of a syntax error.
+    ///
+    /// Search is bounded, it tries every sequence of insertions, deletions and replacements
+    /// up to a small cost, starting from the cheapest, and accepts the ones that let the parser
+    /// consume a few more tokens of the input (or accept it) after the last edit.
+    pub fn search<'i>(
+        parser: &Parser,
+        state_stack: &[usize],
+        upcoming_tokens: impl IntoIterator<Item = (Spanned<Token>, &'i str)>,
+    ) -> Vec<Repair> {
+        let upcoming_tokens =
+            upcoming_tokens.into_iter().take(UPCOMING_TOKENS_TO_SEARCH).collect::<Vec<_>>();
+
+        let searcher = Searcher { parser, upcoming_tokens: &upcoming_tokens };
+        for cost in 1..=MAX_REPAIR_COST {
+            let mut search = Search { edits: vec![], repairs: vec![], explored: 0 };
+            searcher.search(&mut search, state_stack.to_vec(), 0, cost, 0);
+            if !search.repairs.is_empty() {
+                return search.repairs;
+            }
+        }
+        vec![]
+    }
+}
+
+impl Repair {
+    /// Gets the edits of the repair.
+    pub fn edits(&self) -> &[Edit] {
+        &self.edits
+    }
+
+    /// Gets the cost of the repair.
+    pub fn cost(&self) -> usize {
+        self.edits.len()
+    }
+}
+
+impl Display for Repair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.edits.iter().join(" and "))
+    }
+}
+
+
+/// State of a repair search with a specific cost.
+struct Search {
+    edits: Vec<Edit>,
+    repairs: Vec<Repair>,
+    explored: usize,
+}
+
+impl Search {
+    /// Records the current edits as a repair, unless it's already recorded.
+    fn record(&mut self) {
+        let repair = Repair { edits: self.edits.clone() };
+        if !self.repairs.contains(&repair) {
+            self.repairs.push(repair);
+        }
+    }
+}
+
+
+/// Searcher of the repairs of a syntax error.
+struct Searcher<'s, 'i> {
+    parser: &'s Parser,
+    upcoming_tokens: &'s [(Spanned<Token>, &'i str)],
+}
+
+impl Searcher<'_, '_> {
+    /// Searches the repairs that use exactly the remaining cost from a configuration.
+    fn search(
+        &self,
+        search: &mut Search,
+        state_stack: Vec<usize>,
+        position: usize,
+        remaining_cost: usize,
+        parsed_tokens: usize,
+    ) {
+        if search.repairs.len() == MAX_REPAIRS || search.explored == SEARCH_LIMIT {
+            return;
+        }
+        search.explored += 1;
+
+        if remaining_cost == 0 && parsed_tokens == TOKENS_TO_PARSE_AFTER_REPAIR {
+            search.record();
+            return;
+        }
+
+        let (current_token, current_slice) = match self.upcoming_tokens.get(position) {
+            Some(upcoming_token) => upcoming_token,
+            None => return,
+        };
+        let is_eof = *current_token.deref() == Token::Eof;
+
+        if remaining_cost > 0 {
+            let current_state = *state_stack.last().unwrap();
+            let candidates = self.parser.action_table()[current_state]
+                .keys()
+                .filter(|token| !matches!(token, Token::Eof | Token::Error));
+
+            for token in candidates.clone() {
+                if let Some((next_state_stack, false)) = self.simulate(&state_stack, token) {
+                    search.edits.push(Edit::Insert {
+                        token: token.clone(),
+                        span: current_token.span().clone(),
+                    });
+                    self.search(search, next_state_stack, position, remaining_cost - 1, 0);
+                    search.edits.pop();
+                }
+            }
+
+            if !is_eof {
+                search.edits.push(Edit::Delete {
+                    token: current_token.describe(current_slice),
+                    span: current_token.span().clone(),
+                });
+                self.search(search, state_stack.clone(), position + 1, remaining_cost - 1, 0);
+                search.edits.pop();
+
+                for token in candidates {
+                    if token == current_token.deref() {
+                        continue;
+                    }
+                    if let Some((next_state_stack, false)) = self.simulate(&state_stack, token) {
+                        search.edits.push(Edit::Replace {
+                            token: current_token.describe(current_slice),
+                            replacement: token.clone(),
+                            span: current_token.span().clone(),
+                        });
+                        self.search(search, next_state_stack, position + 1, remaining_cost - 1, 0);
+                        search.edits.pop();
+                    }
+                }
+            }
+        }
+
+        if parsed_tokens < TOKENS_TO_PARSE_AFTER_REPAIR {
+            match self.simulate(&state_stack, current_token) {
+                // Accepting the input is as good as parsing the required number of tokens.
+                Some((_, true)) if remaining_cost == 0 => {
+                    search.record();
+                },
+                Some((next_state_stack, false)) => {
+                    self.search(
+                        search,
+                        next_state_stack,
+                        position + 1,
+                        remaining_cost,
+                        parsed_tokens + 1,
+                    );
+                },
+                _ => {},
+            }
+        }
+    }
+
+    /// Simulates the parser on a token, returning the new state stack and whether it's accepted.
+    fn simulate(&self, state_stack: &[usize], token: &Token) -> Option<(Vec<usize>, bool)> {
+        let mut state_stack = state_stack.to_vec();
+        loop {
+            let action = self.parser.action_internal(&state_stack, token)?;
+            self.parser.step_internal(&mut state_stack, action);
+            match action {
+                Action::Accept => return Some((state_stack, true)),
+                Action::Shift { .. } => return Some((state_stack, false)),
+                Action::Reduce { .. } => {},
+            }
+        }
+    }
+}
@@ -0,0 +1,520 @@
+use {
+    crate::prelude::*,
+    regex_automata::{
+        Anchored,
+        Input,
+        MatchKind,
+        dfa::{
+            Automaton,
+            StartKind,
+            dense::{
+                Config,
+                DFA,
+            },
+        },
+    },
+};
+
+
+/// Maximum size (in bytes) of the DFA of a scanner, before falling back to trying tokens one by one.
+const DFA_SIZE_LIMIT: usize = 10 * 1024 * 1024;
+
+/// Maximum number of state pairs to explore while checking whether the leftmost-first match of
+/// a regular expression is its longest match, before assuming that they may differ.
+const MATCH_SEMANTICS_CHECK_LIMIT: usize = 10 * 1024;
+
+/// Maximum number of states of the DFA of a scanner to explore while computing how far it looks
+/// ahead of the scanned tokens, before assuming that it's not bounded.
+const LOOKAHEAD_CHECK_LIMIT: usize = 10 * 1024;
+
+
+/// Scanner of the tokens of a grammar.
+///
+/// All constant and regex tokens, along with the ignored regular expressions, are compiled into
+/// a single DFA, which finds the tokens that match at the start of the input, along with the
+/// lengths of their longest matches, in a single pass. Longest of them is scanned (tokens with
+/// higher priorities win ties, then the constant tokens, then the regex tokens that are defined
+/// first, then the ignored regular expressions). If the regular expressions cannot be compiled
+/// into a DFA (e.g., they use Unicode word boundaries or the DFA gets too large),
+/// each token is tried one by one instead.
+///
+/// Keywords only match at word boundaries, and the regex tokens that match one of their
+/// reserved words are scanned as the constant token of the reserved word.
+///
+/// Scanner may look ahead of a scanned token before the DFA dies (e.g., `1e+` is scanned
+/// before `1` is found to be a number, not `1e+5`), which is bounded when the tokens are
+/// compiled into a DFA (see [Scanner::lookahead]).
+#[derive(Clone)]
+pub struct Scanner {
+    tokens: Vec<Token>,
+    regular_expressions: Vec<Option<Regex>>,
+    priorities: Vec<usize>,
+    keywords: Vec<bool>,
+    reserved_words: IndexMap<RegexToken, IndexSet<ConstantToken>>,
+    dfa: Option<DFA<Vec<u32>>>,
+    longest_matches: Vec<bool>,
+    lookahead: Option<usize>,
+}
+
+impl Scanner {
+    /// Constructs the scanner of the tokens of a grammar (e.g., the tokens of a lexer mode).
+    ///
+    /// Priorities, keywords and reserved words of the tokens are taken from the grammar.
+    pub fn construct(
+        constant_tokens: &IndexSet<ConstantToken>,
+        regular_expressions: &IndexMap<RegexToken, Regex>,
+        ignored_regular_expressions: &[Regex],
+        grammar: &Grammar,
+    ) -> Scanner {
+        let mut tokens = vec![];
+        let mut patterns = vec![];
+
+        for constant_token in constant_tokens.iter() {
+            tokens.push(Token::Constant(constant_token.clone()));
+            patterns.push(regex::escape(constant_token.as_str()));
+        }
+        for (regex_token, regex) in regular_expressions.iter() {
+            tokens.push(Token::Regex(regex_token.clone()));
+            patterns.push(regex.as_str().to_owned());
+        }
+        for regex in ignored_regular_expressions.iter() {
+            patterns.push(regex.as_str().to_owned());
+        }
+
+        let priorities = constant_tokens
+            .iter()
+            .map(|_| 0)
+            .chain(
+                regular_expressions
+                    .keys()
+                    .map(|regex_token| grammar.priorities().get(regex_token).copied().unwrap_or(0)),
+            )
+            .chain(ignored_regular_expressions.iter().map(|_| 0))
+            .collect();
+        let keywords: Vec<bool> = constant_tokens
+            .iter()
+            .map(|constant_token| grammar.keywords().contains(constant_token))
+            .chain(regular_expressions.keys().map(|_| false))
+            .chain(ignored_regular_expressions.iter().map(|_| false))
+            .collect();
+        let reserved_words = grammar
+            .reserved_words()
+            .iter()
+            .filter(|(regex_token, _)| regular_expressions.contains_key(*regex_token))
+            .map(|(regex_token, reserved_words)| (regex_token.clone(), reserved_words.clone()))
+            .collect();
+
+        let regular_expressions = constant_tokens
+            .iter()
+            .map(|_| None)
+            .chain(regular_expressions.values().cloned().map(Some))
+            .chain(ignored_regular_expressions.iter().cloned().map(Some))
+            .collect();
+
+        let dfa = DFA::builder()
+            .configure(
+                Config::new()
+                    .match_kind(MatchKind::All)
+                    .start_kind(StartKind::Anchored)
+                    .dfa_size_limit(Some(DFA_SIZE_LIMIT))
+                    .determinize_size_limit(Some(DFA_SIZE_LIMIT)),
+            )
+            .build_many(&patterns)
+            .ok();
+
+        // Matches of the constant tokens are always their longest matches.
+        let longest_matches = match &dfa {
+            Some(_) => {
+                patterns
+                    .iter()
+                    .enumerate()
+                    .map(|(pattern, definition)| {
+                        pattern < constant_tokens.len()
+                            || Scanner::is_leftmost_first_longest_internal(definition)
+                    })
+                    .collect()
+            },
+            None => vec![],
+        };
+
+        // Lookahead is bounded only if the longest match found by the DFA is scanned.
+        let lookahead = match &dfa {
+            Some(dfa) if longest_matches.iter().all(|longest_match| *longest_match) => {
+                Scanner::lookahead_internal(dfa, &keywords)
+            },
+            _ => None,
+        };
+
+        Scanner {
+            tokens,
+            regular_expressions,
+            priorities,
+            keywords,
+            reserved_words,
+            dfa,
+            longest_matches,
+            lookahead,
+        }
+    }
+}
+
+impl Scanner {
+    /// Internal logic to check whether the leftmost-first match of a regular expression
+    /// (i.e., the match of [Regex::find]) is always its longest match at the start of an input.
+    ///
+    /// Leftmost-first matches prefer the earlier alternatives and the lazy quantifiers stop early
+    /// (e.g., `a|ab` and `a+?` match only `a` in `ab` and `aa`), so the longest matches reported
+    /// by the DFA of the scanner differ from them. States of the leftmost-first and the longest
+    /// DFAs of the regular expression are explored together, and the matches are the same unless
+    /// the longest DFA matches where the leftmost-first DFA doesn't.
+    fn is_leftmost_first_longest_internal(definition: &str) -> bool {
+        let build = |match_kind| {
+            DFA::builder()
+                .configure(
+                    Config::new()
+                        .match_kind(match_kind)
+                        .start_kind(StartKind::Anchored)
+                        .dfa_size_limit(Some(DFA_SIZE_LIMIT))
+                        .determinize_size_limit(Some(DFA_SIZE_LIMIT)),
+                )
+                .build(definition)
+                .ok()
+        };
+        let (leftmost_first, longest) =
+            match (build(MatchKind::LeftmostFirst), build(MatchKind::All)) {
+                (Some(leftmost_first), Some(longest)) => (leftmost_first, longest),
+                _ => return false,
+            };
+
+        let search = Input::new("").anchored(Anchored::Yes);
+        let start = match (
+            leftmost_first.start_state_forward(&search),
+            longest.start_state_forward(&search),
+        ) {
+            (Ok(leftmost_first_start), Ok(longest_start)) => (leftmost_first_start, longest_start),
+            _ => return false,
+        };
+
+        let mut explored = IndexSet::from([start]);
+        let mut index = 0;
+        while let Some(&(leftmost_first_state, longest_state)) = explored.get_index(index) {
+            index += 1;
+
+            let leftmost_first_eoi_state = leftmost_first.next_eoi_state(leftmost_first_state);
+            let longest_eoi_state = longest.next_eoi_state(longest_state);
+            if longest.is_match_state(longest_eoi_state)
+                && !leftmost_first.is_match_state(leftmost_first_eoi_state)
+            {
+                return false;
+            }
+
+            for byte in u8::MIN..=u8::MAX {
+                let next_longest_state = longest.next_state(longest_state, byte);
+                if longest.is_dead_state(next_longest_state) {
+                    continue;
+                }
+                let next_leftmost_first_state =
+                    leftmost_first.next_state(leftmost_first_state, byte);
+                if longest.is_match_state(next_longest_state)
+                    && !leftmost_first.is_match_state(next_leftmost_first_state)
+                {
+                    return false;
+                }
+
+                explored.insert((next_leftmost_first_state, next_longest_state));
+                if explored.len() > MATCH_SEMANTICS_CHECK_LIMIT {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+}
+
+impl Scanner {
+    /// Internal logic to compute how far the scanner looks ahead of the scanned tokens.
+    ///
+    /// Scanned token ends at the last match of the DFA, so the DFA only goes through the states
+    /// without matches after the match until it dies. Lookahead is the longest path of such states,
+    /// along with the byte after the match (as matches are reported one byte late) and the byte
+    /// the DFA dies on, and it's not bounded if the path can loop (e.g., `/` followed by the start
+    /// of a block comment). Matches of keywords don't end the path, since they may be rejected
+    /// if they are not followed by a word boundary, which is checked using the next character.
+    fn lookahead_internal(dfa: &DFA<Vec<u32>>, keywords: &[bool]) -> Option<usize> {
+        let search = Input::new("").anchored(Anchored::Yes);
+        let start = dfa.start_state_forward(&search).ok()?;
+
+        let mut explored = IndexSet::from([start]);
+        let mut index = 0;
+        while let Some(&state) = explored.get_index(index) {
+            index += 1;
+            for byte in u8::MIN..=u8::MAX {
+                let next_state = dfa.next_state(state, byte);
+                if !dfa.is_dead_state(next_state) {
+                    explored.insert(next_state);
+                }
+            }
+            if explored.len() > LOOKAHEAD_CHECK_LIMIT {
+                return None;
+            }
+        }
+
+        let ends_path = |state| {
+            dfa.is_match_state(state)
+                && (0..dfa.match_len(state))
+                    .any(|index| !keywords[dfa.match_pattern(state, index).as_usize()])
+        };
+
+        // States on the paths after the matches are collected along with the transitions between them.
+        let mut path_states = IndexSet::new();
+        for &state in explored.iter().filter(|state| dfa.is_match_state(**state)) {
+            for byte in u8::MIN..=u8::MAX {
+                let next_state = dfa.next_state(state, byte);
+                if !dfa.is_dead_state(next_state) && !ends_path(next_state) {
+                    path_states.insert(next_state);
+                }
+            }
+        }
+        let mut transitions = vec![vec![]; path_states.len()];
+        let mut index = 0;
+        while let Some(&state) = path_states.get_index(index) {
+            for byte in u8::MIN..=u8::MAX {
+                let next_state = dfa.next_state(state, byte);
+                if dfa.is_dead_state(next_state) || ends_path(next_state) {
+                    continue;
+                }
+                let (next_index, _) = path_states.insert_full(next_state);
+                if next_index == transitions.len() {
+                    transitions.push(vec![]);
+                }
+                if !transitions[index].contains(&next_index) {
+                    transitions[index].push(next_index);
+                }
+            }
+            index += 1;
+        }
+
+        // Longest path is found by visiting the states in topological order,
+        // which visits all of them only if the paths don't loop.
+        let mut incoming_transitions = vec![0; path_states.len()];
+        for next_index in transitions.iter().flatten() {
+            incoming_transitions[*next_index] += 1;
+        }
+        let mut path_lengths = vec![1; path_states.len()];
+        let mut queue = (0..path_states.len())
+            .filter(|index| incoming_transitions[*index] == 0)
+            .collect::<VecDeque<_>>();
+        let mut visited = 0;
+        while let Some(index) = queue.pop_front() {
+            visited += 1;
+            for &next_index in transitions[index].iter() {
+                path_lengths[next_index] = path_lengths[next_index].max(path_lengths[index] + 1);
+                incoming_transitions[next_index] -= 1;
+                if incoming_transitions[next_index] == 0 {
+                    queue.push_back(next_index);
+                }
+            }
+        }
+        if visited != path_states.len() {
+            return None;
+        }
+
+        let longest_path = path_lengths.into_iter().max().unwrap_or(0);
+        let lookahead = longest_path + 2;
+        if keywords.iter().any(|keyword| *keyword) {
+            Some(lookahead.max(char::MAX.len_utf8()))
+        } else {
+            Some(lookahead)
+        }
+    }
+}
+
+impl Scanner {
+    /// Gets the maximum number of bytes after a scanned token that the scanner looks at to scan it,
+    /// or [None] if it's not bounded (e.g., the tokens are tried one by one).
+    ///
+    /// Tokens that end at least this many bytes before a change of the input are scanned the same
+    /// way after the change (e.g., the tokens before an edit in [Parser::reparse]).
+    pub fn lookahead(&self) -> Option<usize> {
+        self.lookahead
+    }
+
+    /// Scans the longest token at the start of the input, along with its length.
+    ///
+    /// Token is [None] if the longest match is one of the ignored regular expressions.
+    /// If acceptable tokens are specified, other tokens are not scanned
+    /// (e.g., an identifier is scanned instead of a keyword that cannot follow),
+    /// except for the reserved words, which are never scanned as regex tokens.
+    pub fn scan(
+        &self,
+        input: &str,
+        acceptable: Option<&dyn Fn(&Token) -> bool>,
+    ) -> Option<(Option<Token>, usize)> {
+        match &self.dfa {
+            Some(dfa) => self.scan_with_dfa_internal(dfa, input, acceptable),
+            None => self.scan_one_by_one_internal(input, acceptable),
+        }
+    }
+}
+
+impl Scanner {
+    /// Internal scanning logic using the DFA.
+    ///
+    /// DFA reports all the matches of all the patterns, so the last match of each pattern is its
+    /// longest match. It's the length of the match, unless the leftmost-first match of the pattern
+    /// may be shorter (e.g., it has lazy quantifiers), in which case it's computed by its own
+    /// regular expression.
+    fn scan_with_dfa_internal(
+        &self,
+        dfa: &DFA<Vec<u32>>,
+        input: &str,
+        acceptable: Option<&dyn Fn(&Token) -> bool>,
+    ) -> Option<(Option<Token>, usize)> {
+        let search = Input::new(input).anchored(Anchored::Yes);
+        let mut state = dfa.start_state_forward(&search).ok()?;
+
+        // Matches are reported one byte late, so a match state after a byte is a match before it.
+        let mut candidates = SmallVec::<[(usize, Option<usize>); 4]>::new();
+        let mut add_candidates = |state, end| {
+            if dfa.is_match_state(state) {
+                for index in 0..dfa.match_len(state) {
+                    let pattern = dfa.match_pattern(state, index).as_usize();
+                    let length = if self.longest_matches[pattern] { Some(end) } else { None };
+                    match candidates.iter_mut().find(|(candidate, _)| *candidate == pattern) {
+                        Some(candidate) => *candidate = (pattern, length),
+                        None => candidates.push((pattern, length)),
+                    }
+                }
+            }
+        };
+
+        let mut dead = false;
+        for (offset, byte) in input.bytes().enumerate() {
+            state = dfa.next_state(state, byte);
+            add_candidates(state, offset);
+            if dfa.is_dead_state(state) {
+                dead = true;
+                break;
+            }
+        }
+        if !dead && !input.is_empty() {
+            add_candidates(dfa.next_eoi_state(state), input.len());
+        }
+
+        // Candidates are tried in the order of the patterns, so ties are resolved the same way.
+        candidates.sort_unstable_by_key(|(pattern, _)| *pattern);
+        self.longest_match_internal(candidates, input, acceptable)
+    }
+
+    /// Internal scanning logic that tries each token of the grammar one by one.
+    fn scan_one_by_one_internal(
+        &self,
+        input: &str,
+        acceptable: Option<&dyn Fn(&Token) -> bool>,
+    ) -> Option<(Option<Token>, usize)> {
+        let patterns = (0..self.regular_expressions.len()).map(|pattern| (pattern, None));
+        self.longest_match_internal(patterns, input, acceptable)
+    }
+
+    /// Internal logic to find the longest match among patterns at the start of the input.
+    ///
+    /// Lengths of the matches are computed for the patterns without known match lengths.
+    /// Patterns with higher priorities win ties, then the patterns that come first.
+    fn longest_match_internal(
+        &self,
+        patterns: impl IntoIterator<Item = (usize, Option<usize>)>,
+        input: &str,
+        acceptable: Option<&dyn Fn(&Token) -> bool>,
+    ) -> Option<(Option<Token>, usize)> {
+        let mut longest_match: Option<(usize, usize)> = None;
+        for (pattern, length) in patterns {
+            if !self.is_acceptable_internal(pattern, acceptable) {
+                continue;
+            }
+            let length =
+                match (length, &self.regular_expressions[pattern], self.tokens.get(pattern)) {
+                    (Some(length), _, _) => length,
+                    (None, Some(regex), _) => {
+                        match regex.find(input) {
+                            Some(match_info) => match_info.end(),
+                            None => continue,
+                        }
+                    },
+                    (None, None, Some(Token::Constant(constant_token)))
+                        if input.starts_with(constant_token.as_str()) =>
+                    {
+                        constant_token.len()
+                    },
+                    _ => continue,
+                };
+            if length == 0 || !self.is_at_boundary_internal(pattern, input, length) {
+                continue;
+            }
+
+            let is_better = match longest_match {
+                Some((longest_pattern, longest_length)) => {
+                    length > longest_length
+                        || (length == longest_length
+                            && self.priorities[pattern] > self.priorities[longest_pattern])
+                },
+                None => true,
+            };
+            if is_better {
+                longest_match = Some((pattern, length));
+            }
+        }
+        longest_match.map(|(pattern, length)| self.resolve_internal(pattern, input, length))
+    }
+
+    /// Internal logic to resolve the token of a match, scanning reserved words as constant tokens.
+    fn resolve_internal(
+        &self,
+        pattern: usize,
+        input: &str,
+        length: usize,
+    ) -> (Option<Token>, usize) {
+        let token = match self.tokens.get(pattern) {
+            Some(Token::Regex(regex_token)) => {
+                let slice = &input[..length];
+                let reserved_word =
+                    self.reserved_words.get(regex_token).and_then(|reserved_words| {
+                        reserved_words.iter().find(|reserved_word| reserved_word.as_str() == slice)
+                    });
+                match reserved_word {
+                    Some(reserved_word) => Some(Token::Constant(reserved_word.clone())),
+                    None => Some(Token::Regex(regex_token.clone())),
+                }
+            },
+            token => token.cloned(),
+        };
+        (token, length)
+    }
+
+    /// Internal logic to check whether a match of a pattern ends at a word boundary if it's a keyword
+    /// (i.e., the match is not followed by a letter, a digit or an underscore).
+    fn is_at_boundary_internal(&self, pattern: usize, input: &str, length: usize) -> bool {
+        !self.keywords[pattern]
+            || !input[length..]
+                .starts_with(|character: char| character.is_alphanumeric() || character == '_')
+    }
+
+    /// Internal logic to check whether a pattern can be scanned among the acceptable tokens.
+    ///
+    /// Ignored regular expressions are always acceptable, as they are not tokens.
+    fn is_acceptable_internal(
+        &self,
+        pattern: usize,
+        acceptable: Option<&dyn Fn(&Token) -> bool>,
+    ) -> bool {
+        match (acceptable, self.tokens.get(pattern)) {
+            (Some(acceptable), Some(token)) => acceptable(token),
+            _ => true,
+        }
+    }
+}
+
+impl Debug for Scanner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scanner").field("tokens", &self.tokens).finish_non_exhaustive()
+    }
+}
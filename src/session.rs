@@ -0,0 +1,151 @@
+use crate::prelude::*;
+
+
+/// Status of a parse session after a token is fed.
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Status {
+    /// The token has been shifted, and more tokens are expected.
+    Shifted,
+    /// The end of input has been accepted.
+    Accepted,
+}
+
+
+/// Resumable parse session, which is fed the tokens of the input one at a time.
+///
+/// Session keeps the state stack and the tree stack of the parser between the tokens,
+/// so it can be suspended until the next token is available (e.g., in an editor which
+/// receives the tokens from an external source).
+#[derive(Debug)]
+pub struct ParseSession<'p, 'i> {
+    parser: &'p Parser,
+    state_stack: Vec<usize>,
+    tree_stack: Vec<Tree<'i>>,
+    last_token: Option<(Spanned<Token>, &'i str)>,
+    accepted: bool,
+}
+
+impl<'p, 'i> ParseSession<'p, 'i> {
+    /// Creates a parse session using a parser.
+    pub(crate) fn new(parser: &'p Parser) -> ParseSession<'p, 'i> {
+        ParseSession {
+            parser,
+            state_stack: vec![0],
+            tree_stack: vec![],
+            last_token: None,
+            accepted: false,
+        }
+    }
+}
+
+impl ParseSession<'_, '_> {
+    /// Gets the tokens that can be fed to the session next.
+    ///
+    /// Tokens are checked after the reductions they cause, so only the tokens that are
+    /// eventually shifted or accepted are returned (i.e., not all the tokens in
+    /// [Parser::action_table] of the current state, which may include tokens that are
+    /// rejected after a reduction in LALR(1) and SLR(1) parsers).
+    pub fn expected_tokens(&self) -> SmallVec<[Token; 2]> {
+        let current_state = *self.state_stack.last().unwrap();
+        self.parser.action_table()[current_state]
+            .keys()
+            .filter(|token| **token != Token::Error && self.simulate_internal(token).is_ok())
+            .cloned()
+            .collect()
+    }
+
+    /// Gets whether the end of input is accepted by the session.
+    pub fn is_accepted(&self) -> bool {
+        self.accepted
+    }
+}
+
+impl ParseSession<'_, '_> {
+    /// Internal logic to simulate feeding a token on the state stack, without changing the session.
+    ///
+    /// Result is the state stack after the token, the reductions caused by the token and
+    /// the action taken for it afterward, or the state stack when the token is rejected.
+    #[allow(clippy::type_complexity)]
+    fn simulate_internal(
+        &self,
+        token: &Token,
+    ) -> Result<(Vec<usize>, Vec<usize>, Action), Vec<usize>> {
+        let mut state_stack = self.state_stack.clone();
+        let mut reductions = vec![];
+        loop {
+            let action = match self.parser.action_internal(&state_stack, token) {
+                Some(action) => action,
+                None => return Err(state_stack),
+            };
+            self.parser.step_internal(&mut state_stack, action);
+            match action {
+                Action::Reduce { rule_index } => {
+                    reductions.push(rule_index);
+                },
+                action => return Ok((state_stack, reductions, action)),
+            }
+        }
+    }
+}
+
+impl<'i> ParseSession<'_, 'i> {
+    /// Feeds the next token of the input and its corresponding input slice to the session.
+    ///
+    /// Tokens that are rejected don't change the session, so feeding can continue with
+    /// other tokens after an error. Repairs of the errors are searched using only
+    /// the rejected token, since the upcoming tokens are not known yet, so they are rarely found.
+    pub fn feed(&mut self, token: Spanned<Token>, slice: &'i str) -> Result<Status, ParsingError> {
+        // Reductions are simulated on the state stack first, so the session is only changed
+        // if the token is eventually shifted or accepted.
+        let (state_stack, reductions, action_to_take) = match self.simulate_internal(&token) {
+            Ok(simulation) => simulation,
+            Err(state_stack) => {
+                return Err(self.parser.error_internal(
+                    &state_stack,
+                    &token,
+                    slice,
+                    std::iter::empty(),
+                ));
+            },
+        };
+
+        self.state_stack = state_stack;
+        for rule_index in reductions {
+            self.parser.reduce_internal(rule_index, &mut self.tree_stack);
+        }
+        match action_to_take {
+            Action::Accept => {
+                self.accepted = true;
+                Ok(Status::Accepted)
+            },
+            Action::Shift { .. } => {
+                self.tree_stack.push(Tree::Terminal {
+                    token: token.deref().clone(),
+                    span: token.span().clone(),
+                    slice,
+                    trivia: None,
+                });
+                self.last_token = Some((token, slice));
+                Ok(Status::Shifted)
+            },
+            Action::Reduce { .. } => unreachable!(),
+        }
+    }
+
+    /// Finishes the session, returning the parse tree of the input.
+    ///
+    /// If the end of input token is not fed, it's assumed to be right after the last token.
+    pub fn finish(mut self) -> Result<Tree<'i>, ParsingError> {
+        if !self.accepted {
+            let span = match &self.last_token {
+                Some((token, slice)) => utils::position_after(token.span(), slice),
+                None => Span { offset: 0, length: 0, line: 1, column: 1 },
+            };
+            self.feed(Spanned::new(Token::Eof, span), "\0")?;
+        }
+
+        Ok(Parser::accept_internal(&mut self.tree_stack))
+    }
+}
@@ -59,6 +59,11 @@ impl<
         &self.span
     }
 
+    /// Gets the span of the object mutably.
+    pub(crate) fn span_mut(&mut self) -> &mut Span {
+        &mut self.span
+    }
+
     /// Splits the spanned object into the object and the span.
     pub fn into_components(self) -> (T, Span) {
         (self.object, self.span)
@@ -0,0 +1,408 @@
+use crate::prelude::*;
+
+
+/// Lazy stream of the tokens of an input and their corresponding input slices.
+///
+/// Each token is scanned only when it's requested, so the parser can consume
+/// the tokens as they are scanned (e.g., using [Parser::parse_iter]).
+///
+/// Tokens are scanned in the active lexer mode, which starts as the default mode
+/// and changes with the transitions of the scanned tokens (e.g., `%push Default '"' -> String`).
+///
+/// If the grammar is indentation sensitive, layout tokens are synthesized in the default mode:
+/// `%newline` at the end of each non-blank line, `%indent` before the first token of a line that is
+/// indented more than the enclosing line, and `%dedent` for each indentation level a line closes.
+///
+/// The stream ends with the end of input token, or with the first tokenization error
+/// unless the stream recovers from tokenization errors (see [TokenStream::with_recovery]).
+#[derive(Clone, Debug)]
+pub struct TokenStream<'g, 'i> {
+    grammar: &'g Grammar,
+    input: &'i str,
+    remaining_input: &'i str,
+    offset: usize,
+    line: usize,
+    last_newline_offset: usize,
+    column: usize,
+    lexer_modes: Vec<usize>,
+    indentation_levels: Vec<usize>,
+    indentation: Option<Span>,
+    line_has_tokens: bool,
+    pending_tokens: VecDeque<(Spanned<Token>, &'i str)>,
+    recovering: bool,
+    finished: bool,
+}
+
+impl<'g, 'i> TokenStream<'g, 'i> {
+    /// Creates a stream of the tokens of an input of a grammar.
+    pub fn new(grammar: &'g Grammar, input: &'i str) -> TokenStream<'g, 'i> {
+        TokenStream {
+            grammar,
+            input,
+            remaining_input: input,
+            offset: 0,
+            line: 1,
+            last_newline_offset: 0,
+            column: 1,
+            lexer_modes: vec![0],
+            indentation_levels: vec![0],
+            indentation: None,
+            line_has_tokens: false,
+            pending_tokens: VecDeque::new(),
+            recovering: false,
+            finished: false,
+        }
+    }
+
+    /// Creates a stream of the tokens of an input of a grammar, starting at a position of the input.
+    ///
+    /// Stream starts in the default lexer mode without any indentation levels, so it's only used
+    /// for the grammars without multiple lexer modes or indentation sensitivity.
+    pub(crate) fn starting_at(
+        grammar: &'g Grammar,
+        input: &'i str,
+        position: &Span,
+    ) -> TokenStream<'g, 'i> {
+        let last_newline_offset =
+            input[..position.offset].rfind('\n').map(|offset| offset + 1).unwrap_or(0);
+        TokenStream {
+            remaining_input: &input[position.offset..],
+            offset: position.offset,
+            line: position.line,
+            last_newline_offset,
+            column: position.column,
+            ..TokenStream::new(grammar, input)
+        }
+    }
+
+    /// Creates a stream of the tokens of an input of a grammar, which recovers from tokenization errors.
+    ///
+    /// Each run of unknown characters is reported as a single unknown token error and skipped,
+    /// and lines with inconsistent dedents are reported and kept in the innermost indentation level
+    /// they dedent out of, so the stream continues until the end of input token.
+    pub fn with_recovery(grammar: &'g Grammar, input: &'i str) -> TokenStream<'g, 'i> {
+        TokenStream { recovering: true, ..TokenStream::new(grammar, input) }
+    }
+}
+
+impl<'i> TokenStream<'_, 'i> {
+    /// Scans the next token among the acceptable tokens (e.g., the tokens expected by the parser).
+    ///
+    /// If none of the acceptable tokens can be scanned, all tokens are tried,
+    /// so the token can be reported as an unexpected token.
+    pub fn next_acceptable(
+        &mut self,
+        acceptable: impl Fn(&Token) -> bool,
+    ) -> Option<Result<(Spanned<Token>, &'i str), ParsingError>> {
+        self.next_internal(Some(&acceptable))
+    }
+}
+
+impl<'i> TokenStream<'_, 'i> {
+    /// Internal logic to scan the next token, among the acceptable tokens if specified.
+    fn next_internal(
+        &mut self,
+        acceptable: Option<&dyn Fn(&Token) -> bool>,
+    ) -> Option<Result<(Spanned<Token>, &'i str), ParsingError>> {
+        if let Some(pending_token) = self.pending_tokens.pop_front() {
+            return Some(Ok(pending_token));
+        }
+        if self.finished {
+            return None;
+        }
+
+        let indentation_sensitive = self.grammar.is_indentation_sensitive();
+        loop {
+            let lexer_mode_index = *self.lexer_modes.last().unwrap();
+            let (lexer_mode_name, lexer_mode) =
+                self.grammar.lexer_modes().get_index(lexer_mode_index).unwrap();
+
+            // Whitespace is only skipped in the default mode (e.g., not in strings).
+            if lexer_mode_index == 0 && indentation_sensitive {
+                let whitespace_length = self.remaining_input.len()
+                    - self
+                        .remaining_input
+                        .trim_start_matches(|character: char| {
+                            character != '\n' && character.is_whitespace()
+                        })
+                        .len();
+                self.advance_internal(whitespace_length);
+
+                // Lines end with newlines, unless they are blank.
+                if self.remaining_input.starts_with('\n') {
+                    let span = Span {
+                        offset: self.offset,
+                        length: 1,
+                        line: self.line,
+                        column: self.column,
+                    };
+                    let slice = self.advance_internal(1);
+                    self.indentation = None;
+                    if std::mem::take(&mut self.line_has_tokens) {
+                        return Some(Ok((Spanned::new(Token::Newline, span), slice)));
+                    }
+                    continue;
+                }
+            } else if lexer_mode_index == 0 && !self.grammar.is_whitespace_significant() {
+                let whitespace_length =
+                    self.remaining_input.len() - self.remaining_input.trim_start().len();
+                self.advance_internal(whitespace_length);
+            }
+
+            let (offset, line, column) = (self.offset, self.line, self.column);
+            if self.remaining_input.is_empty() {
+                // Last line and the open indentation levels are closed at the end of input.
+                if indentation_sensitive {
+                    let span = Span { offset, length: 0, line, column };
+                    if std::mem::take(&mut self.line_has_tokens) {
+                        return Some(Ok((Spanned::new(Token::Newline, span), "")));
+                    }
+                    if self.indentation_levels.len() > 1 {
+                        self.indentation_levels.pop();
+                        return Some(Ok((Spanned::new(Token::Dedent, span), "")));
+                    }
+                }
+
+                self.finished = true;
+                let eof = Spanned::new(Token::Eof, Span { offset, length: 0, line, column });
+                return Some(Ok((eof, "\0")));
+            }
+
+            // Indentation of a line ends at its first non-whitespace character (e.g., a comment).
+            if indentation_sensitive && self.indentation.is_none() {
+                let length = offset - self.last_newline_offset;
+                let (offset, column) = (self.last_newline_offset, 1);
+                self.indentation = Some(Span { offset, length, line, column });
+            }
+
+            let scanner = lexer_mode.scanner();
+            let longest_match = match acceptable {
+                Some(_) => {
+                    scanner
+                        .scan(self.remaining_input, acceptable)
+                        .or_else(|| scanner.scan(self.remaining_input, None))
+                },
+                None => scanner.scan(self.remaining_input, None),
+            };
+            let (matching_token, matching_length) = match longest_match {
+                Some(longest_match) => longest_match,
+                None => {
+                    let mode =
+                        (self.grammar.lexer_modes().len() > 1).then(|| lexer_mode_name.clone());
+                    if self.recovering {
+                        let length = self.unknown_length_internal(lexer_mode_index);
+                        let token = self.advance_internal(length).into();
+                        let span = Span { offset, length, line, column };
+                        return Some(Err(ParsingError::UnknownToken { token, span, mode }));
+                    }
+
+                    self.finished = true;
+                    let token = format_smolstr!("{}", self.remaining_input.chars().next().unwrap());
+                    let span = Span { offset, length: 1, line, column };
+                    return Some(Err(ParsingError::UnknownToken { token, span, mode }));
+                },
+            };
+            let matching_slice = self.advance_internal(matching_length);
+
+            // Ignored parts of the input (e.g., comments) are skipped like whitespace.
+            if let Some(matching_token) = matching_token {
+                match lexer_mode.transitions().get(&matching_token) {
+                    Some(LexerModeTransition::Push(next_mode)) => {
+                        self.lexer_modes
+                            .push(self.grammar.lexer_modes().get_index_of(next_mode).unwrap());
+                    },
+                    // Popping the default mode has no effect.
+                    Some(LexerModeTransition::Pop) if self.lexer_modes.len() > 1 => {
+                        self.lexer_modes.pop();
+                    },
+                    _ => {},
+                }
+
+                let span = Span { offset, length: matching_length, line, column };
+                let matching_token = (Spanned::new(matching_token, span), matching_slice);
+                if indentation_sensitive && !std::mem::replace(&mut self.line_has_tokens, true) {
+                    return self.indent_internal(matching_token);
+                }
+                return Some(Ok(matching_token));
+            }
+        }
+    }
+
+    /// Internal logic to synthesize the indentation tokens before the first token of a line.
+    fn indent_internal(
+        &mut self,
+        first_token: (Spanned<Token>, &'i str),
+    ) -> Option<Result<(Spanned<Token>, &'i str), ParsingError>> {
+        let indentation = self.indentation.clone().unwrap();
+        let indentation_slice = &self.input[indentation.offset..][..indentation.length];
+        let indentation_level = indentation_slice.chars().count();
+
+        let current_level = *self.indentation_levels.last().unwrap();
+        if indentation_level > current_level {
+            self.indentation_levels.push(indentation_level);
+            self.pending_tokens
+                .push_back((Spanned::new(Token::Indent, indentation), indentation_slice));
+        } else {
+            let span = Span { length: 0, ..first_token.0.span().clone() };
+            while indentation_level < *self.indentation_levels.last().unwrap() {
+                self.indentation_levels.pop();
+                self.pending_tokens.push_back((Spanned::new(Token::Dedent, span.clone()), ""));
+            }
+            if indentation_level != *self.indentation_levels.last().unwrap() {
+                // Line is assumed to stay in the innermost indentation level it dedents out of
+                // during recovery, so the indentation tokens are still balanced.
+                if self.recovering {
+                    self.pending_tokens.pop_back();
+                    self.indentation_levels.push(indentation_level);
+                    self.pending_tokens.push_back(first_token);
+                } else {
+                    self.finished = true;
+                    self.pending_tokens.clear();
+                }
+                return Some(Err(ParsingError::InconsistentDedent { span: indentation }));
+            }
+        }
+
+        self.pending_tokens.push_back(first_token);
+        self.pending_tokens.pop_front().map(Ok)
+    }
+
+    /// Internal logic to compute the length of the run of unknown characters at the start of the remaining input.
+    ///
+    /// Run ends right before the first character that starts a token, an ignored part of the input,
+    /// or whitespace that is skipped in the lexer mode.
+    fn unknown_length_internal(&self, lexer_mode_index: usize) -> usize {
+        let lexer_mode = &self.grammar.lexer_modes()[lexer_mode_index];
+        let skips_whitespace = lexer_mode_index == 0
+            && (self.grammar.is_indentation_sensitive()
+                || !self.grammar.is_whitespace_significant());
+
+        for (offset, character) in self.remaining_input.char_indices().skip(1) {
+            if (skips_whitespace && character.is_whitespace())
+                || lexer_mode.scanner().scan(&self.remaining_input[offset..], None).is_some()
+            {
+                return offset;
+            }
+        }
+        self.remaining_input.len()
+    }
+
+    /// Internal logic to consume a slice from the start of the remaining input.
+    fn advance_internal(&mut self, length: usize) -> &'i str {
+        let slice = &self.remaining_input[..length];
+        self.remaining_input = &self.remaining_input[length..];
+
+        let (new_lines, newline_offset) = utils::count_new_lines(slice);
+        if let Some(newline_offset) = newline_offset {
+            self.line += new_lines;
+            self.last_newline_offset = self.offset + newline_offset;
+        }
+        self.offset += length;
+        self.column = self.input[self.last_newline_offset..self.offset].chars().count() + 1;
+
+        slice
+    }
+}
+
+impl<'i> Iterator for TokenStream<'_, 'i> {
+    type Item = Result<(Spanned<Token>, &'i str), ParsingError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_internal(None)
+    }
+}
+
+impl std::iter::FusedIterator for TokenStream<'_, '_> {}
+
+
+/// Token stream that scans the tokens expected by the parser (i.e., parser-directed lexing).
+pub struct ContextualTokenStream<'g, 'i>(pub TokenStream<'g, 'i>);
+
+impl<'i> Lexer<'i> for ContextualTokenStream<'_, 'i> {
+    fn next_token(&mut self) -> Option<Result<(Spanned<Token>, &'i str), ParsingError>> {
+        self.0.next_internal(None)
+    }
+
+    fn next_acceptable_token(
+        &mut self,
+        acceptable: &dyn Fn(&Token) -> bool,
+    ) -> Option<Result<(Spanned<Token>, &'i str), ParsingError>> {
+        self.0.next_internal(Some(acceptable))
+    }
+}
+
+
+/// Upcoming tokens of a source, which can be looked ahead without being consumed.
+///
+/// Tokens that are looked ahead are scanned without acceptable tokens,
+/// since the parser doesn't know which tokens it'll accept after the next one.
+pub struct UpcomingTokens<'i, S: Lexer<'i>> {
+    tokens: S,
+    buffer: VecDeque<Result<(Spanned<Token>, &'i str), ParsingError>>,
+    last_token: Option<(Spanned<Token>, &'i str)>,
+    finished: bool,
+}
+
+impl<'i, S: Lexer<'i>> UpcomingTokens<'i, S> {
+    /// Creates the upcoming tokens of a source.
+    pub fn new(tokens: S) -> UpcomingTokens<'i, S> {
+        UpcomingTokens { tokens, buffer: VecDeque::new(), last_token: None, finished: false }
+    }
+}
+
+impl<'i, S: Lexer<'i>> UpcomingTokens<'i, S> {
+    /// Consumes the next token, which is scanned among the acceptable tokens if possible.
+    pub fn next(
+        &mut self,
+        acceptable: Option<&dyn Fn(&Token) -> bool>,
+    ) -> Result<(Spanned<Token>, &'i str), ParsingError> {
+        if self.buffer.is_empty() {
+            self.pull_internal(acceptable);
+        }
+        // Parsing stops at the end of input token or at an error,
+        // so the buffer is not empty whenever the next token is needed.
+        self.buffer.pop_front().unwrap()
+    }
+
+    /// Looks ahead the next tokens, until the count or the first tokenization error is reached.
+    pub fn peek(&mut self, count: usize) -> impl Iterator<Item = (Spanned<Token>, &'i str)> + '_ {
+        while self.buffer.len() < count && !self.finished {
+            self.pull_internal(None);
+        }
+        self.buffer.iter().take(count).map_while(|token| token.as_ref().ok().cloned())
+    }
+}
+
+impl<'i, S: Lexer<'i>> UpcomingTokens<'i, S> {
+    /// Internal logic to pull the next token of the source into the buffer.
+    fn pull_internal(&mut self, acceptable: Option<&dyn Fn(&Token) -> bool>) {
+        if self.finished {
+            return;
+        }
+        let token = match acceptable {
+            Some(acceptable) => self.tokens.next_acceptable_token(acceptable),
+            None => self.tokens.next_token(),
+        };
+        match token {
+            Some(Ok(token)) => {
+                self.finished = *token.0 == Token::Eof;
+                self.last_token = Some(token.clone());
+                self.buffer.push_back(Ok(token));
+            },
+            Some(Err(error)) => {
+                self.finished = true;
+                self.buffer.push_back(Err(error));
+            },
+            None => {
+                self.finished = true;
+
+                // End of input is assumed to be right after the last token.
+                let span = match &self.last_token {
+                    Some((token, slice)) => utils::position_after(token.span(), slice),
+                    None => Span { offset: 0, length: 0, line: 1, column: 1 },
+                };
+                self.buffer.push_back(Ok((Spanned::new(Token::Eof, span), "\0")));
+            },
+        }
+    }
+}
@@ -62,6 +62,38 @@ impl FirstTable {
     }
 }
 
+impl FirstTable {
+    /// Computes the first set of a sequence of atomic patterns (e.g., `E '+' F`).
+    ///
+    /// The first set contains `ε` if the sequence can be empty.
+    pub fn first_set_of(&self, atomic_patterns: &[AtomicPattern]) -> IndexSet<Token> {
+        let mut first_set = IndexSet::new();
+        for atomic_pattern in atomic_patterns {
+            match atomic_pattern {
+                AtomicPattern::Symbol(symbol) => {
+                    let symbol_first_set = match self.0.get(symbol) {
+                        Some(symbol_first_set) => symbol_first_set,
+                        None => return first_set,
+                    };
+                    first_set.extend(
+                        symbol_first_set.iter().filter(|&token| *token != Token::Empty).cloned(),
+                    );
+                    if !symbol_first_set.contains(&Token::Empty) {
+                        return first_set;
+                    }
+                },
+                AtomicPattern::Token(Token::Empty) => {},
+                AtomicPattern::Token(token) => {
+                    first_set.insert(token.clone());
+                    return first_set;
+                },
+            }
+        }
+        first_set.insert(Token::Empty);
+        first_set
+    }
+}
+
 impl Deref for FirstTable {
     type Target = IndexMap<Symbol, IndexSet<Token>>;
 
@@ -158,7 +190,7 @@ impl Deref for FollowTable {
 }
 
 
-/// Action (e.g., `Shift 3`, `Reduce 2`, `Accept 1`) to perform during a parsing step.
+/// Action (e.g., `Shift 3`, `Reduce 2`, `Accept`) to perform during a parsing step.
 #[cfg_attr(feature = "serde", derive(Serialize))]
 #[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
 #[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
@@ -175,10 +207,7 @@ pub enum Action {
         rule_index: usize,
     },
     /// Accept the parse and finish parsing.
-    Accept {
-        /// Index of the rule that was matched.
-        rule_index: usize,
-    },
+    Accept,
 }
 
 impl Display for Action {
@@ -186,11 +215,219 @@ impl Display for Action {
         match self {
             Action::Shift { next_state } => write!(f, "s{}", next_state),
             Action::Reduce { rule_index } => write!(f, "r{}", rule_index + 1),
-            Action::Accept { rule_index } => write!(f, "a{}", rule_index + 1),
+            Action::Accept => write!(f, "acc"),
+        }
+    }
+}
+
+
+/// Shift/reduce conflict resolved using the precedence declarations of a grammar.
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResolvedConflict {
+    state: usize,
+    token: Token,
+    shift: Action,
+    reduce: Action,
+    chosen_action: Option<Action>,
+}
+
+impl ResolvedConflict {
+    /// Gets the state of the resolved conflict.
+    pub fn state(&self) -> usize {
+        self.state
+    }
+
+    /// Gets the token of the resolved conflict.
+    pub fn token(&self) -> &Token {
+        &self.token
+    }
+
+    /// Gets the shift action of the resolved conflict.
+    pub fn shift(&self) -> Action {
+        self.shift
+    }
+
+    /// Gets the reduce action of the resolved conflict.
+    pub fn reduce(&self) -> Action {
+        self.reduce
+    }
+
+    /// Gets the action chosen to resolve the conflict (`None` for non associative errors).
+    pub fn chosen_action(&self) -> Option<Action> {
+        self.chosen_action
+    }
+}
+
+
+/// Kind of a conflict in the action table of a parser.
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ConflictKind {
+    /// Both shifting the token and reducing a rule is possible.
+    ShiftReduce,
+    /// Reducing multiple rules is possible.
+    ReduceReduce,
+}
+
+impl Display for ConflictKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConflictKind::ShiftReduce => write!(f, "shift/reduce"),
+            ConflictKind::ReduceReduce => write!(f, "reduce/reduce"),
+        }
+    }
+}
+
+
+/// Conflict (i.e., multiple possible actions) in the action table of a parser.
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Conflict {
+    state: usize,
+    token: Token,
+    kind: ConflictKind,
+    actions: Vec<Action>,
+    items: Vec<Item>,
+    rules: Vec<Rule>,
+}
+
+impl Conflict {
+    /// Constructs the conflict of a state on a token from the possible actions.
+    pub fn construct(
+        grammar: &Grammar,
+        state: &State,
+        token: &Token,
+        actions: &IndexSet<Action>,
+    ) -> Conflict {
+        let kind = if actions.iter().any(|action| !matches!(action, Action::Reduce { .. })) {
+            ConflictKind::ShiftReduce
+        } else {
+            ConflictKind::ReduceReduce
+        };
+
+        let rules = actions
+            .iter()
+            .filter_map(|action| {
+                match action {
+                    Action::Shift { .. } | Action::Accept => None,
+                    Action::Reduce { rule_index } => Some(grammar.rules()[*rule_index].clone()),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let items = state
+            .items()
+            .iter()
+            .filter(|item| {
+                let pattern = item.rule().pattern();
+                if item.dot() == pattern.len() || item.rule().is_empty_pattern() {
+                    // Items of LR(0) automatons don't have lookaheads to filter with.
+                    (item.lookahead().is_empty() || item.lookahead().contains(token))
+                        && rules.contains(item.rule())
+                } else {
+                    pattern[item.dot()] == AtomicPattern::Token(token.clone())
+                }
+            })
+            .cloned()
+            .collect();
+
+        Conflict {
+            state: state.id(),
+            token: token.clone(),
+            kind,
+            actions: actions.iter().copied().collect(),
+            items,
+            rules,
         }
     }
 }
 
+impl Conflict {
+    /// Gets the state of the conflict.
+    pub fn state(&self) -> usize {
+        self.state
+    }
+
+    /// Gets the token of the conflict.
+    pub fn token(&self) -> &Token {
+        &self.token
+    }
+
+    /// Gets the kind of the conflict.
+    pub fn kind(&self) -> ConflictKind {
+        self.kind
+    }
+
+    /// Gets the competing actions of the conflict.
+    pub fn actions(&self) -> &[Action] {
+        &self.actions
+    }
+
+    /// Gets the competing items of the conflict, with their lookaheads.
+    pub fn items(&self) -> &[Item] {
+        &self.items
+    }
+
+    /// Gets the rules that can be reduced in the conflict.
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+}
+
+impl Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} conflict at state {} on {}", self.kind, self.state, self.token)?;
+        for action in self.actions.iter() {
+            let items = match action {
+                Action::Shift { .. } | Action::Accept => {
+                    writeln!(f)?;
+                    match action {
+                        Action::Shift { next_state } => write!(f, "  shift {}", next_state)?,
+                        _ => write!(f, "  accept")?,
+                    }
+                    self.items
+                        .iter()
+                        .filter(|item| item.dot() < item.rule().pattern().len())
+                        .filter(|item| !item.rule().is_empty_pattern())
+                        .collect::<Vec<_>>()
+                },
+                Action::Reduce { rule_index } => {
+                    let rule = &self.rules[self
+                        .actions
+                        .iter()
+                        .filter(|action| matches!(action, Action::Reduce { .. }))
+                        .position(|candidate| candidate == action)
+                        .unwrap()];
+
+                    writeln!(f)?;
+                    write!(f, "  reduce {} ({})", rule_index + 1, rule)?;
+                    self.items
+                        .iter()
+                        .filter(|item| {
+                            item.dot() == item.rule().pattern().len()
+                                || item.rule().is_empty_pattern()
+                        })
+                        .filter(|item| item.rule() == rule)
+                        .collect::<Vec<_>>()
+                },
+            };
+            for item in items {
+                writeln!(f)?;
+                if item.lookahead().is_empty() {
+                    write!(f, "    {}", item)?;
+                } else {
+                    write!(f, "    {}  {{ {} }}", item, item.lookahead().iter().join(", "))?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 
 /// Action and goto tables of a parser.
 #[cfg_attr(feature = "wasm", wasm_bindgen)]
@@ -200,6 +437,7 @@ impl Display for Action {
 pub struct ParsingTables {
     action_table: Vec<IndexMap<Token, IndexSet<Action>>>,
     goto_table: Vec<IndexMap<Symbol, usize>>,
+    resolved_conflicts: Vec<ResolvedConflict>,
 }
 
 impl ParsingTables {
@@ -208,9 +446,13 @@ impl ParsingTables {
         grammar: &Grammar,
         follow_table: &FollowTable,
         automaton: &Automaton,
+        method: ParsingMethod,
     ) -> Result<ParsingTables, ParserError> {
+        let all_tokens = grammar.input_tokens().into_iter().collect::<IndexSet<_>>();
+
         let mut action_table = Vec::with_capacity(automaton.states().len());
         let mut goto_table = Vec::with_capacity(automaton.states().len());
+        let mut resolved_conflicts = Vec::new();
 
         for state in automaton.states().iter() {
             let mut actions = IndexMap::<Token, IndexSet<Action>>::new();
@@ -219,31 +461,32 @@ impl ParsingTables {
             for item in state.items() {
                 let rule = item.rule();
                 if item.dot() == rule.pattern().len() || rule.is_empty_pattern() {
-                    if let Some(follows) = follow_table.get(item.rule().symbol()) {
-                        let rule_index =
-                            grammar.rules().iter().position(|rule| rule == item.rule()).unwrap();
-                        for token in follows {
-                            if !item.lookahead().contains(token) {
-                                continue;
-                            }
-
-                            if *token == Token::Eof
-                                && item.rule().symbol() == grammar.start_symbol()
-                            {
-                                actions
-                                    .entry(token.clone())
-                                    .or_default()
-                                    .insert(Action::Accept { rule_index });
-                            } else {
-                                actions
-                                    .entry(token.clone())
-                                    .or_default()
-                                    .insert(Action::Reduce { rule_index });
+                    let rule_index =
+                        grammar.rules().iter().position(|rule| rule == item.rule()).unwrap();
+                    let tokens = match method {
+                        ParsingMethod::Lr0 => all_tokens.clone(),
+                        ParsingMethod::Slr => {
+                            follow_table.get(rule.symbol()).cloned().unwrap_or_default()
+                        },
+                        ParsingMethod::Lalr | ParsingMethod::Lr => {
+                            match follow_table.get(rule.symbol()) {
+                                Some(follows) => {
+                                    follows.intersection(item.lookahead()).cloned().collect()
+                                },
+                                None => IndexSet::new(),
                             }
-                        }
+                        },
+                    };
+                    for token in tokens {
+                        actions.entry(token).or_default().insert(Action::Reduce { rule_index });
                     }
                 } else {
                     let next_atomic_pattern = &item.rule().pattern()[item.dot()];
+                    if *next_atomic_pattern == AtomicPattern::Token(Token::Eof) {
+                        actions.entry(Token::Eof).or_default().insert(Action::Accept);
+                        continue;
+                    }
+
                     let transition = state.transitions()[next_atomic_pattern];
                     match next_atomic_pattern {
                         AtomicPattern::Symbol(symbol) => {
@@ -259,11 +502,108 @@ impl ParsingTables {
                 }
             }
 
+            ParsingTables::resolve_conflicts_internal(
+                grammar,
+                state.id(),
+                &mut actions,
+                &mut resolved_conflicts,
+            );
+
             action_table.push(actions);
             goto_table.push(gotos);
         }
 
-        Ok(ParsingTables { action_table, goto_table })
+        Ok(ParsingTables { action_table, goto_table, resolved_conflicts })
+    }
+}
+
+impl ParsingTables {
+    /// Internal shift/reduce conflict resolution using precedence declarations.
+    fn resolve_conflicts_internal(
+        grammar: &Grammar,
+        state: usize,
+        actions: &mut IndexMap<Token, IndexSet<Action>>,
+        resolved_conflicts: &mut Vec<ResolvedConflict>,
+    ) {
+        if grammar.precedences().is_empty() {
+            return;
+        }
+
+        let mut tokens_to_remove = vec![];
+        for (token, possible_actions) in actions.iter_mut() {
+            if possible_actions.len() < 2 {
+                continue;
+            }
+
+            let shift =
+                match possible_actions.iter().find(|action| matches!(action, Action::Shift { .. }))
+                {
+                    Some(shift) => *shift,
+                    None => continue,
+                };
+            let token_precedence =
+                match grammar.precedences().get(&AtomicPattern::Token(token.clone())) {
+                    Some(precedence) => *precedence,
+                    None => continue,
+                };
+
+            // Each reduction is resolved against the shift separately (e.g., in a cell with
+            // a shift and two reductions), and the remaining actions are still conflicts.
+            let mut shift_is_removed = false;
+            let reductions = possible_actions
+                .iter()
+                .filter_map(|action| {
+                    match action {
+                        Action::Reduce { rule_index } => Some((*action, *rule_index)),
+                        _ => None,
+                    }
+                })
+                .collect::<Vec<_>>();
+            for (reduce, rule_index) in reductions {
+                let rule_precedence = match grammar.rule_precedence(&grammar.rules()[rule_index]) {
+                    Some(precedence) => precedence,
+                    None => continue,
+                };
+
+                let chosen_action = if rule_precedence.level() > token_precedence.level() {
+                    Some(reduce)
+                } else if rule_precedence.level() < token_precedence.level() {
+                    Some(shift)
+                } else {
+                    match token_precedence.associativity() {
+                        Associativity::Left => Some(reduce),
+                        Associativity::Right => Some(shift),
+                        Associativity::NonAssociative => None,
+                    }
+                };
+
+                if chosen_action != Some(reduce) {
+                    possible_actions.shift_remove(&reduce);
+                }
+                if chosen_action != Some(shift) {
+                    shift_is_removed = true;
+                }
+
+                resolved_conflicts.push(ResolvedConflict {
+                    state,
+                    token: token.clone(),
+                    shift,
+                    reduce,
+                    chosen_action,
+                });
+            }
+
+            if shift_is_removed {
+                possible_actions.shift_remove(&shift);
+            }
+            if possible_actions.is_empty() {
+                tokens_to_remove.push(token.clone());
+            }
+        }
+
+        for token in tokens_to_remove {
+            actions.shift_remove(&token);
+        }
     }
 }
 
@@ -277,4 +617,218 @@ impl ParsingTables {
     pub fn goto_table(&self) -> &[IndexMap<Symbol, usize>] {
         &self.goto_table
     }
+
+    /// Gets the conflicts resolved using the precedence declarations of the grammar.
+    pub fn resolved_conflicts(&self) -> &[ResolvedConflict] {
+        &self.resolved_conflicts
+    }
+}
+
+
+/// Kind of a conflict in the predictive parsing table of an LL(1) parser.
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum LlConflictKind {
+    /// Multiple rules of the symbol can start with the token.
+    FirstFirst,
+    /// A rule of the symbol can be empty, and the token can follow the symbol.
+    FirstFollow,
+    /// A rule of the symbol is left recursive (e.g., `E -> E '+' F`).
+    LeftRecursion,
+}
+
+impl Display for LlConflictKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LlConflictKind::FirstFirst => write!(f, "first/first"),
+            LlConflictKind::FirstFollow => write!(f, "first/follow"),
+            LlConflictKind::LeftRecursion => write!(f, "left recursion"),
+        }
+    }
+}
+
+
+/// Conflict (i.e., multiple possible rules) in the predictive parsing table of an LL(1) parser.
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LlConflict {
+    symbol: Symbol,
+    token: Token,
+    kind: LlConflictKind,
+    rule_indices: Vec<usize>,
+    rules: Vec<Rule>,
+}
+
+impl LlConflict {
+    /// Constructs the conflict of a symbol on a token from the possible rules.
+    pub fn construct(
+        grammar: &Grammar,
+        first_table: &FirstTable,
+        symbol: &Symbol,
+        token: &Token,
+        rule_indices: &IndexSet<usize>,
+    ) -> LlConflict {
+        let rules = rule_indices
+            .iter()
+            .map(|rule_index| grammar.rules()[*rule_index].clone())
+            .collect::<Vec<_>>();
+
+        let kind = if rules
+            .iter()
+            .any(|rule| LlConflict::is_left_recursive_internal(grammar, first_table, rule))
+        {
+            LlConflictKind::LeftRecursion
+        } else if rules.iter().any(|rule| !first_table.first_set_of(rule.pattern()).contains(token))
+        {
+            LlConflictKind::FirstFollow
+        } else {
+            LlConflictKind::FirstFirst
+        };
+
+        LlConflict {
+            symbol: symbol.clone(),
+            token: token.clone(),
+            kind,
+            rule_indices: rule_indices.iter().copied().collect(),
+            rules,
+        }
+    }
+}
+
+impl LlConflict {
+    /// Gets the symbol of the conflict.
+    pub fn symbol(&self) -> &Symbol {
+        &self.symbol
+    }
+
+    /// Gets the token of the conflict.
+    pub fn token(&self) -> &Token {
+        &self.token
+    }
+
+    /// Gets the kind of the conflict.
+    pub fn kind(&self) -> LlConflictKind {
+        self.kind
+    }
+
+    /// Gets the indices of the competing rules of the conflict.
+    pub fn rule_indices(&self) -> &[usize] {
+        &self.rule_indices
+    }
+
+    /// Gets the competing rules of the conflict.
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+}
+
+impl LlConflict {
+    /// Internal check of whether the symbol of a rule can derive itself at the start of the rule.
+    fn is_left_recursive_internal(
+        grammar: &Grammar,
+        first_table: &FirstTable,
+        rule: &Rule,
+    ) -> bool {
+        let leftmost_symbols = |pattern: &[AtomicPattern]| {
+            let mut symbols = vec![];
+            for atomic_pattern in pattern {
+                match atomic_pattern {
+                    AtomicPattern::Symbol(symbol) => {
+                        symbols.push(symbol.clone());
+                        let can_be_empty = first_table
+                            .get(symbol)
+                            .map(|first_set| first_set.contains(&Token::Empty))
+                            .unwrap_or(false);
+                        if !can_be_empty {
+                            break;
+                        }
+                    },
+                    AtomicPattern::Token(Token::Empty) => {},
+                    AtomicPattern::Token(_) => break,
+                }
+            }
+            symbols
+        };
+
+        let mut visited_symbols = IndexSet::new();
+        let mut symbols_to_visit = leftmost_symbols(rule.pattern());
+        while let Some(symbol) = symbols_to_visit.pop() {
+            if symbol == *rule.symbol() {
+                return true;
+            }
+            if !visited_symbols.insert(symbol.clone()) {
+                continue;
+            }
+            for candidate in
+                grammar.rules().iter().filter(|candidate| *candidate.symbol() == symbol)
+            {
+                symbols_to_visit.extend(leftmost_symbols(candidate.pattern()));
+            }
+        }
+        false
+    }
+}
+
+impl Display for LlConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} conflict at {} on {}", self.kind, self.symbol, self.token)?;
+        for (rule_index, rule) in self.rule_indices.iter().zip(self.rules.iter()) {
+            writeln!(f)?;
+            write!(f, "  expand {} ({})", rule_index + 1, rule)?;
+        }
+        Ok(())
+    }
+}
+
+
+/// Predictive parsing table of an LL(1) parser.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[derive(Debug)]
+pub struct LlTable(IndexMap<Symbol, IndexMap<Token, IndexSet<usize>>>);
+
+impl LlTable {
+    /// Constructs the predictive parsing table from the grammar.
+    pub fn construct(
+        grammar: &Grammar,
+        first_table: &FirstTable,
+        follow_table: &FollowTable,
+    ) -> LlTable {
+        let mut ll_table = grammar
+            .symbols()
+            .iter()
+            .map(|symbol| (symbol.clone(), IndexMap::<Token, IndexSet<usize>>::new()))
+            .collect::<IndexMap<_, _>>();
+
+        for (rule_index, rule) in grammar.rules().iter().enumerate() {
+            let rules_of_symbol = ll_table.entry(rule.symbol().clone()).or_default();
+
+            let first_set = first_table.first_set_of(rule.pattern());
+            for token in first_set.iter().filter(|&token| *token != Token::Empty) {
+                rules_of_symbol.entry(token.clone()).or_default().insert(rule_index);
+            }
+
+            // Rules that can be empty are expanded on the tokens that can follow the symbol.
+            if first_set.contains(&Token::Empty) {
+                if let Some(follow_set) = follow_table.get(rule.symbol()) {
+                    for token in follow_set {
+                        rules_of_symbol.entry(token.clone()).or_default().insert(rule_index);
+                    }
+                }
+            }
+        }
+
+        LlTable(ll_table)
+    }
+}
+
+impl Deref for LlTable {
+    type Target = IndexMap<Symbol, IndexMap<Token, IndexSet<usize>>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
 }
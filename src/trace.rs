@@ -88,6 +88,9 @@ impl Trace<'_> {
                         Tree::NonTerminal { symbol, .. } => {
                             format_smolstr!("{}", symbol)
                         },
+                        Tree::Error { .. } => {
+                            format_smolstr!("{}", Token::Error)
+                        },
                     }
                 })
                 .join(" ");
@@ -99,8 +102,8 @@ impl Trace<'_> {
                 Action::Reduce { rule_index } => {
                     format!("Reduce {} ({})", rule_index + 1, grammar.rules()[rule_index])
                 },
-                Action::Accept { rule_index } => {
-                    format!("Accept {} ({})", rule_index + 1, grammar.rules()[rule_index])
+                Action::Accept => {
+                    format!("Accept ({})", grammar.augmented_rule())
                 },
             };
 
@@ -115,3 +118,111 @@ impl Trace<'_> {
         pretty_trace_table.printstd();
     }
 }
+
+
+/// Action (e.g., `Expand 2`, `Match '+'`, `Accept`) performed during an LL(1) parsing step.
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[cfg_attr(feature = "serde", serde(tag = "type", content = "value"))]
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum LlAction {
+    /// Replace the symbol on top of the stack with the pattern of a rule of the grammar.
+    Expand {
+        /// Index of the rule to expand.
+        rule_index: usize,
+    },
+    /// Pop the token on top of the stack as it's matching the first remaining input token.
+    Match {
+        /// Token that is matched.
+        token: Token,
+    },
+    /// Accept the parse and finish parsing.
+    Accept,
+}
+
+
+/// Step of an LL(1) parsing trace.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+pub struct LlStep {
+    pub(crate) stack: Vec<AtomicPattern>,
+    pub(crate) remaining_tokens: Vec<Spanned<Token>>,
+    pub(crate) action_taken: LlAction,
+}
+
+impl LlStep {
+    /// Gets the stack of atomic patterns to match during the step, with its top at the end.
+    pub fn stack(&self) -> &[AtomicPattern] {
+        &self.stack
+    }
+
+    /// Gets the remaining tokens during the step.
+    pub fn remaining_tokens(&self) -> &[Spanned<Token>] {
+        &self.remaining_tokens
+    }
+
+    /// Gets the action taken in the step.
+    pub fn action_taken(&self) -> &LlAction {
+        &self.action_taken
+    }
+}
+
+
+/// Trace of an LL(1) parse.
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+pub struct LlTrace {
+    steps: Vec<LlStep>,
+}
+
+impl LlTrace {
+    /// Creates a new trace.
+    pub fn new() -> LlTrace {
+        Self { steps: vec![] }
+    }
+}
+
+impl LlTrace {
+    /// Adds a step to the trace.
+    pub fn step(&mut self, step: LlStep) {
+        self.steps.push(step);
+    }
+}
+
+impl LlTrace {
+    /// Gets the steps in the trace.
+    pub fn steps(&self) -> &[LlStep] {
+        &self.steps
+    }
+}
+
+impl LlTrace {
+    /// Dumps the trace to stdout.
+    pub fn dump(&self, grammar: &Grammar) {
+        let mut pretty_trace_table = Table::new();
+        pretty_trace_table.add_row(row![
+            cbFy->"Step",
+            cbFy->"Stack",
+            cbFy->"Remaining Input",
+            cbFy->"Action Taken",
+        ]);
+        for (i, step) in self.steps.iter().enumerate() {
+            let stack = step.stack.iter().join(" ");
+            let remaining_input = step.remaining_tokens.iter().rev().map(|t| t.deref()).join(" ");
+            let action_taken = match &step.action_taken {
+                LlAction::Expand { rule_index } => {
+                    format!("Expand {} ({})", rule_index + 1, grammar.rules()[*rule_index])
+                },
+                LlAction::Match { token } => {
+                    format!("Match {}", token)
+                },
+                LlAction::Accept => "Accept".to_owned(),
+            };
+
+            pretty_trace_table.add_row(row![i, stack, r->remaining_input, action_taken]);
+        }
+        pretty_trace_table.printstd();
+    }
+}
@@ -15,6 +15,9 @@ pub enum Tree<'i> {
         span: Span,
         /// Matching slice.
         slice: &'i str,
+        /// Whitespace and comments around the slice (only in lossless trees).
+        #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+        trivia: Option<Box<Trivia<'i>>>,
     },
     /// Non-terminal node.
     NonTerminal {
@@ -23,6 +26,11 @@ pub enum Tree<'i> {
         /// Matching pattern.
         pattern: Vec<Tree<'i>>,
     },
+    /// Error node, matching the error token during error recovery.
+    Error {
+        /// Trees and tokens discarded to recover from the error.
+        discarded: Vec<Tree<'i>>,
+    },
 }
 
 impl Tree<'_> {
@@ -32,18 +40,180 @@ impl Tree<'_> {
     }
 }
 
+impl Tree<'_> {
+    /// Gets the text of the parse tree, including the trivia of its terminals.
+    ///
+    /// Text of a lossless tree (e.g., from [Parser::parse_lossless]) is the parsed input.
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        self.text_internal(&mut text);
+        text
+    }
+}
+
+impl<'i> Tree<'i> {
+    /// Attaches the skipped parts of the input (e.g., whitespace and comments)
+    /// to the terminals of the parse tree as trivia.
+    ///
+    /// Trailing trivia of a terminal extends to the end of its line (excluding the line breaks
+    /// in comments), and the rest of the trivia before the next terminal is its leading trivia.
+    /// The last terminal takes the trivia until the end of the input as trailing trivia.
+    ///
+    /// If the parse tree has no terminals, the end of input token is appended to the root
+    /// as a terminal with an empty slice, which takes the whole input as leading trivia.
+    pub(crate) fn attach_trivia(&mut self, grammar: &Grammar, input: &'i str) {
+        let mut terminals = vec![];
+        self.terminals_internal(&mut terminals);
+
+        let mut position = Span { offset: 0, length: 0, line: 1, column: 1 };
+        if terminals.is_empty() {
+            if let Tree::NonTerminal { pattern: branches, .. }
+            | Tree::Error { discarded: branches } = self
+            {
+                let span = utils::position_after(&position, input);
+                let leading = Tree::trivia_slice(input, &position, input.len());
+                let trailing = Tree::trivia_slice(input, &span, 0);
+                branches.push(Tree::Terminal {
+                    token: Token::Eof,
+                    span,
+                    slice: "",
+                    trivia: Some(Box::new(Trivia { leading, trailing })),
+                });
+            }
+            return;
+        }
+
+        let mut leading = Spanned::new("", position.clone());
+        for index in 0..terminals.len() {
+            let (span, slice) = match &*terminals[index] {
+                Tree::Terminal { span, slice, .. } => (span.clone(), *slice),
+                _ => unreachable!(),
+            };
+
+            let gap = &input[position.offset..span.offset];
+            let split = if index == 0 { 0 } else { Tree::trailing_trivia_length(grammar, gap) };
+            let trailing = Tree::trivia_slice(input, &position, split);
+
+            if index > 0 {
+                if let Tree::Terminal { trivia, .. } = &mut *terminals[index - 1] {
+                    *trivia = Some(Box::new(Trivia { leading, trailing }));
+                }
+            }
+
+            let leading_start = utils::position_after(&position, &gap[..split]);
+            leading = Tree::trivia_slice(input, &leading_start, gap.len() - split);
+            position = utils::position_after(&span, slice);
+        }
+
+        if let Some(last) = terminals.last_mut() {
+            let trailing = Tree::trivia_slice(input, &position, input.len() - position.offset);
+            if let Tree::Terminal { trivia, .. } = &mut **last {
+                *trivia = Some(Box::new(Trivia { leading, trailing }));
+            }
+        }
+    }
+}
+
+impl<'i> Tree<'i> {
+    /// Creates the tree of a reduced rule from the trees of its pattern.
+    ///
+    /// Repetitions are left recursive (e.g., `Value* -> Value* Value`),
+    /// so they are flattened into a single node with all the elements.
+    pub(crate) fn reduced(
+        repetition_symbols: &IndexSet<Symbol>,
+        symbol: Symbol,
+        mut pattern: Vec<Tree<'i>>,
+    ) -> Tree<'i> {
+        if repetition_symbols.contains(&symbol) {
+            if let Some(Tree::NonTerminal { symbol: first_symbol, .. }) = pattern.first() {
+                if *first_symbol == symbol {
+                    if let Tree::NonTerminal { pattern: mut elements, .. } = pattern.remove(0) {
+                        elements.append(&mut pattern);
+                        pattern = elements;
+                    }
+                }
+            }
+        }
+        Tree::NonTerminal { symbol, pattern }
+    }
+}
+
+impl<'i> Tree<'i> {
+    /// Internal logic to append the text of the parse tree to a string.
+    fn text_internal(&self, text: &mut String) {
+        match self {
+            Tree::Terminal { slice, trivia, .. } => {
+                if let Some(trivia) = trivia {
+                    text.push_str(&trivia.leading);
+                }
+                text.push_str(slice);
+                if let Some(trivia) = trivia {
+                    text.push_str(&trivia.trailing);
+                }
+            },
+            Tree::NonTerminal { pattern: branches, .. } | Tree::Error { discarded: branches } => {
+                for branch in branches {
+                    branch.text_internal(text);
+                }
+            },
+        }
+    }
+
+    /// Internal logic to collect the terminals of the parse tree in order.
+    fn terminals_internal<'t>(&'t mut self, terminals: &mut Vec<&'t mut Tree<'i>>) {
+        match self {
+            Tree::Terminal { .. } => terminals.push(self),
+            Tree::NonTerminal { pattern: branches, .. } | Tree::Error { discarded: branches } => {
+                for branch in branches {
+                    branch.terminals_internal(terminals);
+                }
+            },
+        }
+    }
+
+    /// Internal logic to compute the length of the trailing trivia at the start of a gap.
+    fn trailing_trivia_length(grammar: &Grammar, gap: &str) -> usize {
+        let mut length = 0;
+        while let Some(character) = gap[length..].chars().next() {
+            if !grammar.is_whitespace_significant() && character.is_whitespace() {
+                length += character.len_utf8();
+                if character == '\n' {
+                    return length;
+                }
+                continue;
+            }
+            match grammar.scanner().scan(&gap[length..], None) {
+                Some((None, ignored_length)) => length += ignored_length,
+                _ => break,
+            }
+        }
+        gap.len()
+    }
+
+    /// Internal logic to create a trivia slice at a position of the input.
+    fn trivia_slice(input: &'i str, position: &Span, length: usize) -> Spanned<&'i str> {
+        let span = Span { length, ..position.clone() };
+        Spanned::new(&input[position.offset..position.offset + length], span)
+    }
+}
+
 impl Display for Tree<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         fn display_name_of(tree: &Tree) -> String {
             match tree {
-                Tree::Terminal { slice, .. } => slice.green().bold().to_string(),
+                Tree::Terminal { token, slice, .. } => {
+                    token.describe(slice).green().bold().to_string()
+                },
                 Tree::NonTerminal { symbol, .. } => format!("{}", symbol),
+                Tree::Error { .. } => Token::Error.to_string().red().bold().to_string(),
             }
         }
 
         fn recurse(tree: &Tree, builder: &mut TreeBuilder) {
-            if let Tree::NonTerminal { pattern, .. } = tree {
-                for branch in pattern {
+            if let Tree::NonTerminal { pattern: branches, .. }
+            | Tree::Error { discarded: branches } = tree
+            {
+                for branch in branches {
                     builder.begin_child(display_name_of(branch));
                     recurse(branch, builder);
                     builder.end_child();
@@ -61,3 +231,15 @@ impl Display for Tree<'_> {
         write!(f, "{}", String::from_utf8(bytes).unwrap().trim())
     }
 }
+
+
+/// Trivia (i.e., skipped whitespace and comments) around a terminal of a lossless parse tree.
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(crate = "serde_renamed"))]
+#[derive(Clone, Debug)]
+pub struct Trivia<'i> {
+    /// Trivia before the terminal.
+    pub leading: Spanned<&'i str>,
+    /// Trivia after the terminal.
+    pub trailing: Spanned<&'i str>,
+}
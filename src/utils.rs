@@ -9,6 +9,8 @@ use crate::prelude::*;
 pub trait MockColored {
     fn green(&self) -> String;
 
+    fn red(&self) -> String;
+
     fn cyan(&self) -> String;
 
     fn bold(&self) -> String;
@@ -19,6 +21,9 @@ impl<T: AsRef<str>> MockColored for T {
     fn green(&self) -> String {
         self.as_ref().to_owned()
     }
+    fn red(&self) -> String {
+        self.as_ref().to_owned()
+    }
     fn cyan(&self) -> String {
         self.as_ref().to_owned()
     }
@@ -44,6 +49,15 @@ where
     map_serializer.end()
 }
 
+/// Serializes a list of compiled regex objects.
+#[cfg(feature = "serde")]
+pub fn serialize_regexes<S>(regexes: &[Regex], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.collect_seq(regexes.iter().map(|regex| regex.to_string()))
+}
+
 
 /// Counts the number of new lines in a slice and computes the offset after the last new line.
 pub fn count_new_lines(slice: &str) -> (usize, Option<usize>) {
@@ -59,3 +73,13 @@ pub fn count_new_lines(slice: &str) -> (usize, Option<usize>) {
 
     (count, offset_after_newline)
 }
+
+/// Computes the position right after a slice that starts at a span.
+pub fn position_after(span: &Span, slice: &str) -> Span {
+    let (new_lines, newline_offset) = count_new_lines(slice);
+    let column = match newline_offset {
+        Some(newline_offset) => slice[newline_offset..].chars().count() + 1,
+        None => span.column + slice.chars().count(),
+    };
+    Span { offset: span.offset + slice.len(), length: 0, line: span.line + new_lines, column }
+}
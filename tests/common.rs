@@ -3,27 +3,55 @@ pub mod grammars {
     // ----------------
     // Correct grammars
     // ----------------
-    pub const CORRECT: &[&str] =
-        &[BINARY_ADDITION, CALCULATOR, CONDITIONAL, G9, G10, G11, JSON, NOT_LALR, OPTIONAL];
+    pub const CORRECT: &[&str] = &[
+        BINARY_ADDITION,
+        CALCULATOR,
+        CALCULATOR_PRECEDENCE,
+        CONDITIONAL,
+        G9,
+        G10,
+        G11,
+        INDENTATION,
+        INTERPOLATION,
+        JSON,
+        JSON_COMMENTS,
+        JSON_EBNF,
+        JSON_RECOVERY,
+        NOT_LALR,
+        OPTIONAL,
+        RECURSIVE_START,
+        STATEMENTS_EBNF,
+    ];
 
     pub const BINARY_ADDITION: &str = include_str!("../assets/grammars/correct/binary-addition.lr");
     pub const CALCULATOR: &str = include_str!("../assets/grammars/correct/calculator.lr");
+    pub const CALCULATOR_PRECEDENCE: &str =
+        include_str!("../assets/grammars/correct/calculator.precedence.lr");
     pub const CONDITIONAL: &str = include_str!("../assets/grammars/correct/conditional.lr");
     pub const G9: &str = include_str!("../assets/grammars/correct/g9.lr");
     pub const G10: &str = include_str!("../assets/grammars/correct/g10.lr");
     pub const G11: &str = include_str!("../assets/grammars/correct/g11.lr");
+    pub const INDENTATION: &str = include_str!("../assets/grammars/correct/indentation.lr");
     pub const INDIRECT_EMPTY: &str = include_str!("../assets/grammars/correct/indirect_empty.lr");
+    pub const INTERPOLATION: &str = include_str!("../assets/grammars/correct/interpolation.lr");
     pub const JSON: &str = include_str!("../assets/grammars/correct/json.lr");
+    pub const JSON_COMMENTS: &str = include_str!("../assets/grammars/correct/json.comments.lr");
+    pub const JSON_EBNF: &str = include_str!("../assets/grammars/correct/json.ebnf.lr");
+    pub const JSON_RECOVERY: &str = include_str!("../assets/grammars/correct/json.recovery.lr");
     pub const NOT_LALR: &str = include_str!("../assets/grammars/correct/not-lalr.lr");
     pub const OPTIONAL: &str = include_str!("../assets/grammars/correct/optional.lr");
+    pub const RECURSIVE_START: &str = include_str!("../assets/grammars/correct/recursive-start.lr");
+    pub const STATEMENTS_EBNF: &str = include_str!("../assets/grammars/correct/statements.ebnf.lr");
 
     // --------------------------------
     // Syntactically incorrect grammars
     // --------------------------------
-    pub const SYNTACTICALLY_INCORRECT: &[&str] = &[INVALID_REGEX, UNEXPECTED_TOKEN];
+    pub const SYNTACTICALLY_INCORRECT: &[&str] = &[INVALID_REGEX, UNCLOSED_GROUP, UNEXPECTED_TOKEN];
 
     pub const INVALID_REGEX: &str =
         include_str!("../assets/grammars/incorrect/syntactic/invalid-regex.lr");
+    pub const UNCLOSED_GROUP: &str =
+        include_str!("../assets/grammars/incorrect/syntactic/unclosed-group.lr");
     pub const UNEXPECTED_TOKEN: &str =
         include_str!("../assets/grammars/incorrect/syntactic/unexpected-token.lr");
 
@@ -32,17 +60,26 @@ pub mod grammars {
     // --------------------------------
     pub const SEMANTICALLY_INCORRECT: &[&str] = &[
         EMPTY,
+        MULTIPLE_CONFLICTS,
         REDUCE_REDUCE_CONFLICT,
         SHIFT_REDUCE_CONFLICT,
+        UNDEFINED_LEXER_MODE,
+        UNDEFINED_PRECEDENCE,
         UNDEFINED_REGEX_TOKEN,
         UNDEFINED_SYMBOL,
     ];
 
     pub const EMPTY: &str = include_str!("../assets/grammars/incorrect/semantic/empty.lr");
+    pub const MULTIPLE_CONFLICTS: &str =
+        include_str!("../assets/grammars/incorrect/semantic/multiple-conflicts.lr");
     pub const REDUCE_REDUCE_CONFLICT: &str =
         include_str!("../assets/grammars/incorrect/semantic/reduce-reduce-conflict.lr");
     pub const SHIFT_REDUCE_CONFLICT: &str =
         include_str!("../assets/grammars/incorrect/semantic/shift-reduce-conflict.lr");
+    pub const UNDEFINED_LEXER_MODE: &str =
+        include_str!("../assets/grammars/incorrect/semantic/undefined-lexer-mode.lr");
+    pub const UNDEFINED_PRECEDENCE: &str =
+        include_str!("../assets/grammars/incorrect/semantic/undefined-precedence.lr");
     pub const UNDEFINED_REGEX_TOKEN: &str =
         include_str!("../assets/grammars/incorrect/semantic/undefined-regex-token.lr");
     pub const UNDEFINED_SYMBOL: &str =
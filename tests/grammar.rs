@@ -1,12 +1,17 @@
 mod common;
 
 use dotlr::{
+    Associativity,
+    AtomicPattern,
     ConstantToken,
     Grammar,
     GrammarError,
+    LexerModeTransition,
+    Precedence,
     RegexToken,
     Rule,
     Symbol,
+    Token,
 };
 
 #[cfg(target_family = "wasm")]
@@ -24,6 +29,19 @@ fn parsing_syntactically_correct_grammars() {
     }
 }
 
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_reparsing_formatted_correct_grammars() {
+    for grammar in common::grammars::CORRECT {
+        let grammar = Grammar::parse(grammar).unwrap();
+        let formatted_grammar = grammar.to_string();
+
+        let reparsed_grammar = Grammar::parse(&formatted_grammar).unwrap();
+        assert_eq!(reparsed_grammar.rules(), grammar.rules(), "{}", formatted_grammar);
+        assert_eq!(reparsed_grammar.precedences(), grammar.precedences());
+    }
+}
+
 #[test]
 #[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
 fn failing_to_parse_syntactically_incorrect_grammars() {
@@ -45,7 +63,29 @@ fn raising_correct_error_when_parsing_unexpected_token_grammar() {
             assert_eq!(expected.iter().map(|token| token.as_str()).collect::<Vec<_>>(), [
                 "symbol",
                 "constant token",
-                "regular expression token"
+                "regular expression token",
+                "'('",
+            ]);
+        },
+        error => panic!("unexpected grammar error {:?}", error),
+    }
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn raising_correct_error_when_parsing_unclosed_group_grammar() {
+    let error = Grammar::parse(common::grammars::UNCLOSED_GROUP).unwrap_err();
+    match error {
+        GrammarError::UnexpectedToken { line, column, token, expected } => {
+            assert_eq!(line, 1);
+            assert_eq!(column, 16);
+            assert_eq!(token.as_str(), "\\n");
+            assert_eq!(expected.iter().map(|token| token.as_str()).collect::<Vec<_>>(), [
+                "symbol",
+                "constant token",
+                "regular expression token",
+                "'('",
+                "')'",
             ]);
         },
         error => panic!("unexpected grammar error {:?}", error),
@@ -205,3 +245,499 @@ Term -> %f
         ],
     );
 }
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_parsing_calculator_precedence_grammar() {
+    let grammar = Grammar::parse(common::grammars::CALCULATOR_PRECEDENCE).unwrap();
+
+    assert_eq!(
+        grammar.to_string().trim(),
+        r#"
+
+Expr -> Expr '<' Expr
+Expr -> Expr '+' Expr
+Expr -> Expr '-' Expr
+Expr -> Expr '*' Expr
+Expr -> Expr '/' Expr
+Expr -> Expr '^' Expr
+Expr -> '-' Expr %prec UMINUS
+Expr -> '(' Expr ')'
+Expr -> %f
+
+%f -> /^[0-9]+(\.[0-9]+)?/
+
+%nonassoc '<'
+%left '+' '-'
+%left '*' '/'
+%right '^'
+%right UMINUS
+
+        "#
+        .trim(),
+    );
+
+    assert_eq!(grammar.symbols().iter().map(|symbol| symbol.as_str()).collect::<Vec<_>>(), [
+        "Expr"
+    ],);
+
+    assert_eq!(
+        grammar.precedences().iter().collect::<Vec<_>>(),
+        [
+            (&ConstantToken::from("<").into(), &Precedence::new(1, Associativity::NonAssociative)),
+            (&ConstantToken::from("+").into(), &Precedence::new(2, Associativity::Left)),
+            (&ConstantToken::from("-").into(), &Precedence::new(2, Associativity::Left)),
+            (&ConstantToken::from("*").into(), &Precedence::new(3, Associativity::Left)),
+            (&ConstantToken::from("/").into(), &Precedence::new(3, Associativity::Left)),
+            (&ConstantToken::from("^").into(), &Precedence::new(4, Associativity::Right)),
+            (&AtomicPattern::Symbol("UMINUS".into()), &Precedence::new(5, Associativity::Right)),
+        ],
+    );
+
+    assert_eq!(
+        grammar.rules()[6],
+        Rule::with_precedence(
+            "Expr",
+            [ConstantToken::from("-").into(), Symbol::from("Expr").into()],
+            Symbol::from("UMINUS"),
+        )
+    );
+
+    assert_eq!(
+        grammar.rule_precedence(&grammar.rules()[1]),
+        Some(Precedence::new(2, Associativity::Left)),
+    );
+    assert_eq!(
+        grammar.rule_precedence(&grammar.rules()[6]),
+        Some(Precedence::new(5, Associativity::Right)),
+    );
+    assert_eq!(grammar.rule_precedence(&grammar.rules()[8]), None);
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_formatting_redeclared_precedences() {
+    let grammar = Grammar::parse(
+        r#"
+E -> E '+' E
+E -> E '*' E
+E -> %n
+
+%n -> /[0-9]+/
+
+%left '+' '-'
+%right '*'
+%left '+'
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        grammar.precedences().iter().collect::<Vec<_>>(),
+        [
+            (&ConstantToken::from("+").into(), &Precedence::new(3, Associativity::Left)),
+            (&ConstantToken::from("-").into(), &Precedence::new(1, Associativity::Left)),
+            (&ConstantToken::from("*").into(), &Precedence::new(2, Associativity::Right)),
+        ],
+    );
+
+    // Tokens are grouped by their levels, even if they are not declared adjacently.
+    assert!(grammar.to_string().ends_with(
+        r#"
+%left '-'
+%right '*'
+%left '+'
+"#
+    ));
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_parsing_json_ebnf_grammar() {
+    let grammar = Grammar::parse(common::grammars::JSON_EBNF).unwrap();
+
+    assert_eq!(
+        grammar.to_string().trim(),
+        r#"
+
+Json -> Value
+Value -> Null
+Value -> Boolean
+Value -> Number
+Value -> String
+Value -> Array
+Value -> Object
+Null -> 'null'
+Boolean -> 'true'
+Boolean -> 'false'
+Number -> %f
+String -> %s
+Array -> '[' (Value (',' Value)*)? ']'
+Object -> '{' (Member (',' Member)*)? '}'
+Member -> String ':' Value
+
+%f -> /^[-]?(0|[1-9][0-9]*)(\.[0-9]+)?([eE][+-]?[0-9]+)?/
+%s -> /^"([^"\\]|\\["\\bnfrt]|u[a-fA-F0-9]{4})*"/
+
+        "#
+        .trim(),
+    );
+
+    assert_eq!(grammar.symbols().iter().map(|symbol| symbol.as_str()).collect::<Vec<_>>(), [
+        "Json",
+        "Value",
+        "Null",
+        "Boolean",
+        "Number",
+        "String",
+        "Array",
+        "(Value (',' Value)*)?",
+        "(',' Value)*",
+        "Object",
+        "(Member (',' Member)*)?",
+        "(',' Member)*",
+        "Member",
+    ],);
+
+    assert_eq!(
+        grammar.empty_symbols().iter().map(|symbol| symbol.as_str()).collect::<Vec<_>>(),
+        ["(Value (',' Value)*)?", "(',' Value)*", "(Member (',' Member)*)?", "(',' Member)*"],
+    );
+
+    assert_eq!(
+        grammar.generated_symbols().iter().map(|symbol| symbol.as_str()).collect::<Vec<_>>(),
+        ["(Value (',' Value)*)?", "(',' Value)*", "(Member (',' Member)*)?", "(',' Member)*"],
+    );
+
+    assert_eq!(
+        grammar.repetition_symbols().iter().map(|symbol| symbol.as_str()).collect::<Vec<_>>(),
+        ["(',' Value)*", "(',' Member)*"],
+    );
+
+    // Rules of the generated symbols are not formatted, as they are generated again.
+    assert_eq!(
+        Grammar::parse(&grammar.to_string()).unwrap().rules().len(),
+        grammar.rules().len(),
+    );
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_parsing_statements_ebnf_grammar() {
+    let grammar = Grammar::parse(common::grammars::STATEMENTS_EBNF).unwrap();
+
+    #[rustfmt::skip]
+    assert_eq!(
+        grammar.rules(),
+        [
+            // Program -> Statement+
+            Rule::new(
+                "Program",
+                [
+                    Symbol::from("Statement+").into(),
+                ]
+            ),
+            // Statement+ -> Statement+ Statement
+            Rule::new(
+                "Statement+",
+                [
+                    Symbol::from("Statement+").into(),
+                    Symbol::from("Statement").into(),
+                ]
+            ),
+            // Statement+ -> Statement
+            Rule::new(
+                "Statement+",
+                [
+                    Symbol::from("Statement").into(),
+                ]
+            ),
+
+            // Statement -> 'let' %id ('=' Expression)? ';'
+            Rule::new(
+                "Statement",
+                [
+                    ConstantToken::from("let").into(),
+                    RegexToken::from("id").into(),
+                    Symbol::from("('=' Expression)?").into(),
+                    ConstantToken::from(";").into(),
+                ]
+            ),
+            // Statement -> Expression ';'
+            Rule::new(
+                "Statement",
+                [
+                    Symbol::from("Expression").into(),
+                    ConstantToken::from(";").into(),
+                ]
+            ),
+            // ('=' Expression)? -> '=' Expression
+            Rule::new(
+                "('=' Expression)?",
+                [
+                    ConstantToken::from("=").into(),
+                    Symbol::from("Expression").into(),
+                ]
+            ),
+            // ('=' Expression)? -> ''
+            Rule::new(
+                "('=' Expression)?",
+                [
+                    Token::Empty.into(),
+                ]
+            ),
+
+            // Expression -> %id
+            Rule::new(
+                "Expression",
+                [
+                    RegexToken::from("id").into(),
+                ]
+            ),
+            // Expression -> %n
+            Rule::new(
+                "Expression",
+                [
+                    RegexToken::from("n").into(),
+                ]
+            ),
+            // Expression -> '(' Expression (',' Expression)* ')'
+            Rule::new(
+                "Expression",
+                [
+                    ConstantToken::from("(").into(),
+                    Symbol::from("Expression").into(),
+                    Symbol::from("(',' Expression)*").into(),
+                    ConstantToken::from(")").into(),
+                ]
+            ),
+            // (',' Expression)* -> (',' Expression)* ',' Expression
+            Rule::new(
+                "(',' Expression)*",
+                [
+                    Symbol::from("(',' Expression)*").into(),
+                    ConstantToken::from(",").into(),
+                    Symbol::from("Expression").into(),
+                ]
+            ),
+            // (',' Expression)* -> ''
+            Rule::new(
+                "(',' Expression)*",
+                [
+                    Token::Empty.into(),
+                ]
+            ),
+        ],
+    );
+
+    assert_eq!(
+        grammar.repetition_symbols().iter().map(|symbol| symbol.as_str()).collect::<Vec<_>>(),
+        ["Statement+", "(',' Expression)*"],
+    );
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_parsing_json_recovery_grammar() {
+    let grammar = Grammar::parse(common::grammars::JSON_RECOVERY).unwrap();
+
+    assert_eq!(grammar.rules()[7], Rule::new("Value", [Token::Error.into()]));
+    assert_eq!(grammar.rules()[21], Rule::new("ObjectElements", [Token::Error.into()]));
+    assert_eq!(
+        grammar.rules()[22],
+        Rule::new("ObjectElements", [
+            Symbol::from("ObjectElements").into(),
+            ConstantToken::from(",").into(),
+            Token::Error.into(),
+        ]),
+    );
+
+    assert!(grammar.to_string().contains("Value -> %error"));
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_parsing_json_comments_grammar() {
+    let grammar = Grammar::parse(common::grammars::JSON_COMMENTS).unwrap();
+
+    assert_eq!(
+        grammar
+            .ignored_regular_expressions()
+            .iter()
+            .map(|regex| regex.to_string())
+            .collect::<Vec<_>>(),
+        [r"^\/\/[^\n]*", r"^\/\*([^*]|\*+[^*\/])*\*+\/"],
+    );
+    assert!(!grammar.is_whitespace_significant());
+
+    assert!(grammar.to_string().ends_with(
+        r#"
+%ignore -> /^\/\/[^\n]*/
+%ignore -> /^\/\*([^*]|\*+[^*\/])*\*+\//
+"#
+    ));
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_parsing_interpolation_grammar() {
+    let grammar = Grammar::parse(common::grammars::INTERPOLATION).unwrap();
+
+    let lexer_modes = grammar.lexer_modes();
+    assert_eq!(lexer_modes.keys().map(|mode| mode.as_str()).collect::<Vec<_>>(), ["Default", "String"]);
+
+    assert_eq!(
+        lexer_modes["Default"].tokens(),
+        [
+            ConstantToken::from("+").into(),
+            ConstantToken::from("\"").into(),
+            ConstantToken::from("}").into(),
+            RegexToken::from("identifier").into(),
+        ],
+    );
+    assert_eq!(
+        lexer_modes["Default"].transitions().iter().collect::<Vec<_>>(),
+        [
+            (&ConstantToken::from("\"").into(), &LexerModeTransition::Push("String".into())),
+            (&ConstantToken::from("}").into(), &LexerModeTransition::Pop),
+        ],
+    );
+
+    assert_eq!(
+        lexer_modes["String"].tokens(),
+        [
+            ConstantToken::from("\"").into(),
+            RegexToken::from("text").into(),
+            ConstantToken::from("${").into(),
+        ],
+    );
+    assert_eq!(
+        lexer_modes["String"].transitions().iter().collect::<Vec<_>>(),
+        [
+            (&ConstantToken::from("\"").into(), &LexerModeTransition::Pop),
+            (&ConstantToken::from("${").into(), &LexerModeTransition::Push("Default".into())),
+        ],
+    );
+
+    assert!(grammar.to_string().ends_with(
+        r#"
+%mode Default -> '"'
+%mode String -> '"' %text '${'
+%push Default '"' -> String
+%pop Default '}'
+%pop String '"'
+%push String '${' -> Default
+"#
+    ));
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn raising_correct_error_when_parsing_lexer_mode_declaration_without_tokens() {
+    let error = Grammar::parse("%mode M ->\nS -> 'a'\n").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "unexpected token \\n at line 1 column 11 (expected one of constant token, regular expression token)",
+    );
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_parsing_indentation_grammar() {
+    let grammar = Grammar::parse(common::grammars::INDENTATION).unwrap();
+    assert!(grammar.is_indentation_sensitive());
+    assert!(!Grammar::parse(common::grammars::JSON).unwrap().is_indentation_sensitive());
+
+    assert_eq!(
+        grammar.rules().iter().find(|rule| rule.symbol().as_str() == "Block").unwrap().pattern(),
+        [
+            AtomicPattern::Token(Token::Indent),
+            AtomicPattern::Symbol(Symbol::from("Statement+")),
+            AtomicPattern::Token(Token::Dedent),
+        ],
+    );
+
+    assert!(grammar.to_string().ends_with(
+        r#"
+%ignore -> /^#[^\n]*/
+%indentation
+"#
+    ));
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_parsing_token_declarations() {
+    let grammar = Grammar::parse(
+        r#"
+Statement -> 'let' %identifier '=' %number
+
+%identifier -> /[a-z]+/
+%number -> /[0-9]+/
+%hex -> /[0-9a-f]+/
+
+%priority %number
+%priority %identifier %hex
+%keyword 'let' 'in'
+%reserved %identifier -> 'let' 'fn'
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        grammar.priorities().iter().collect::<Vec<_>>(),
+        [
+            (&RegexToken::from("number"), &1),
+            (&RegexToken::from("identifier"), &2),
+            (&RegexToken::from("hex"), &2),
+        ],
+    );
+    assert_eq!(
+        grammar.keywords().iter().collect::<Vec<_>>(),
+        [&ConstantToken::from("let"), &ConstantToken::from("in")],
+    );
+    assert_eq!(
+        grammar.reserved_words().iter().collect::<Vec<_>>(),
+        [(
+            &RegexToken::from("identifier"),
+            &[ConstantToken::from("let"), ConstantToken::from("fn")].into_iter().collect(),
+        )],
+    );
+
+    // Keywords and reserved words are scanned even if they are not used in the rules.
+    assert!(grammar.constant_tokens().contains(&ConstantToken::from("in")));
+    assert!(grammar.constant_tokens().contains(&ConstantToken::from("fn")));
+
+    assert!(grammar.to_string().ends_with(
+        r#"
+%priority %number
+%priority %identifier %hex
+%keyword 'let' 'in'
+%reserved %identifier -> 'let' 'fn'
+"#
+    ));
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_formatting_redeclared_priorities() {
+    let grammar = Grammar::parse(
+        r#"
+S -> %identifier %number
+
+%identifier -> /[a-z]+/
+%number -> /[0-9]+/
+
+%priority %identifier %number
+%priority %identifier
+        "#,
+    )
+    .unwrap();
+
+    assert!(grammar.to_string().ends_with(
+        r#"
+%priority %number
+%priority %identifier
+"#
+    ));
+}
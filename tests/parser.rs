@@ -3,11 +3,15 @@ mod common;
 use {
     dotlr::{
         Action,
+        ConflictKind,
         ConstantToken,
         Grammar,
         Item,
+        LlConflictKind,
+        LlParser,
         Parser,
         ParserError,
+        ParsingMethod,
         RegexToken,
         Rule,
         State,
@@ -68,6 +72,25 @@ fn raising_correct_error_when_creating_parser_for_undefined_regex_token_grammar(
     assert_eq!(error.to_string(), "regex token %i in rule E -> %i '+' %i is not defined");
 }
 
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn raising_correct_error_when_creating_parser_for_undefined_lexer_mode_grammar() {
+    let grammar = Grammar::parse(common::grammars::UNDEFINED_LEXER_MODE).unwrap();
+    let error = Parser::lr(grammar).unwrap_err();
+    assert_eq!(error.to_string(), "lexer mode String is not defined");
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn raising_correct_error_when_creating_parser_for_undefined_precedence_grammar() {
+    let grammar = Grammar::parse(common::grammars::UNDEFINED_PRECEDENCE).unwrap();
+    let error = Parser::lr(grammar).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "precedence UMINUS in rule E -> '-' E %prec UMINUS is not defined"
+    );
+}
+
 #[test]
 #[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
 fn raising_correct_error_when_creating_parser_for_shift_reduce_conflict_grammar() {
@@ -75,11 +98,20 @@ fn raising_correct_error_when_creating_parser_for_shift_reduce_conflict_grammar(
     let error = Parser::lr(grammar).unwrap_err();
 
     let error_string = error.to_string();
-    if let ParserError::Conflict { parser, token, state } = error {
+    if let ParserError::Conflicts { parser, conflicts } = error {
+        assert_eq!(conflicts.len(), 1);
+
+        let conflict = &conflicts[0];
+        let (state, token) = (conflict.state(), conflict.token());
         assert_eq!(error_string, format!("conflict at state {} on {}", state, token));
+        assert_eq!(conflict.kind(), ConflictKind::ShiftReduce);
 
-        let possible_actions = parser.action_table()[state].get(&token);
+        let possible_actions = parser.action_table()[state].get(token);
         assert!(possible_actions.is_some());
+        assert_eq!(
+            possible_actions.unwrap().iter().collect::<Vec<_>>(),
+            conflict.actions().iter().collect::<Vec<_>>()
+        );
 
         let mut has_shift_action = false;
         let mut has_reduce_action = false;
@@ -105,10 +137,15 @@ fn raising_correct_error_when_creating_parser_for_reduce_reduce_conflict_grammar
     let error = Parser::lr(grammar).unwrap_err();
 
     let error_string = error.to_string();
-    if let ParserError::Conflict { parser, token, state } = error {
+    if let ParserError::Conflicts { parser, conflicts } = error {
+        assert_eq!(conflicts.len(), 1);
+
+        let conflict = &conflicts[0];
+        let (state, token) = (conflict.state(), conflict.token());
         assert_eq!(error_string, format!("conflict at state {} on {}", state, token));
+        assert_eq!(conflict.kind(), ConflictKind::ReduceReduce);
 
-        let possible_actions = parser.action_table()[state].get(&token);
+        let possible_actions = parser.action_table()[state].get(token);
         assert!(possible_actions.is_some());
 
         let mut reduce_action_count = 0;
@@ -119,6 +156,7 @@ fn raising_correct_error_when_creating_parser_for_reduce_reduce_conflict_grammar
         }
 
         assert!(reduce_action_count >= 2);
+        assert_eq!(conflict.rules().len(), reduce_action_count);
     } else {
         panic!("unexpected parser error {:?}", error);
     }
@@ -131,21 +169,318 @@ fn raising_correct_error_when_creating_lalr_parser_for_non_lalr_grammar() {
     let error = Parser::lalr(grammar).unwrap_err();
 
     let error_string = error.to_string();
-    if let ParserError::Conflict { parser, token, state } = error {
-        assert_eq!(error_string, format!("conflict at state {} on {}", state, token));
+    if let ParserError::Conflicts { parser, conflicts } = error {
+        assert!(!conflicts.is_empty());
+        assert_eq!(
+            error_string,
+            format!(
+                "{} at {}",
+                if conflicts.len() == 1 { "conflict" } else { "conflicts" },
+                conflicts
+                    .iter()
+                    .map(|conflict| format!("state {} on {}", conflict.state(), conflict.token()))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            ),
+        );
 
-        let possible_actions = parser.action_table()[state].get(&token);
-        assert!(possible_actions.is_some());
+        for conflict in conflicts.iter() {
+            let possible_actions = parser.action_table()[conflict.state()].get(conflict.token());
+            assert!(possible_actions.is_some());
+
+            assert!(possible_actions.unwrap().len() >= 2);
+        }
+    } else {
+        panic!("unexpected parser error {:?}", error);
+    }
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn raising_correct_error_when_creating_slr_parser_for_non_slr_grammar() {
+    let grammar = Grammar::parse(common::grammars::G11).unwrap();
+    assert!(Parser::lalr(grammar.clone()).is_ok());
+
+    let error = Parser::slr(grammar).unwrap_err();
+    if let ParserError::Conflicts { parser, conflicts } = error {
+        assert_eq!(parser.method(), ParsingMethod::Slr);
+        assert_eq!(conflicts.len(), 1);
+
+        // Follow set of `V` contains `$` because of `S -> V '=' E` and `E -> V`,
+        // so `V -> %id .` is reduced on `$` next to `S -> %id .`, which LALR(1) avoids.
+        let conflict = &conflicts[0];
+        assert_eq!(conflict.state(), 3);
+        assert_eq!(*conflict.token(), Token::Eof);
+        assert_eq!(conflict.kind(), ConflictKind::ReduceReduce);
+        assert_eq!(
+            conflict.actions(),
+            [Action::Reduce { rule_index: 1 }, Action::Reduce { rule_index: 2 }]
+        );
+        assert_eq!(
+            conflict.items(),
+            [
+                Item::new(Rule::new("S", [RegexToken::from("id").into()]), 1, []),
+                Item::new(Rule::new("V", [RegexToken::from("id").into()]), 1, []),
+            ]
+        );
+    } else {
+        panic!("unexpected parser error {:?}", error);
+    }
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn raising_correct_error_when_creating_lr0_parser_for_non_lr0_grammar() {
+    let grammar = Grammar::parse(common::grammars::CALCULATOR).unwrap();
+    assert!(Parser::slr(grammar.clone()).is_ok());
+
+    let error = Parser::lr0(grammar).unwrap_err();
+    if let ParserError::Conflicts { parser, conflicts } = error {
+        assert_eq!(parser.method(), ParsingMethod::Lr0);
+        assert!(!conflicts.is_empty());
+
+        // LR(0) parsers reduce on every token, so completed items conflict with any shift.
+        for conflict in conflicts.iter() {
+            assert_eq!(conflict.kind(), ConflictKind::ShiftReduce);
+            assert!(conflict.items().iter().all(|item| item.lookahead().is_empty()));
+        }
+    } else {
+        panic!("unexpected parser error {:?}", error);
+    }
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn raising_correct_error_when_creating_parser_for_multiple_conflicts_grammar() {
+    let grammar = Grammar::parse(common::grammars::MULTIPLE_CONFLICTS).unwrap();
+    let error = Parser::lr(grammar).unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "conflicts at state 4 on '+', state 4 on '*', state 6 on '+', state 6 on '*'",
+    );
+
+    if let ParserError::Conflicts { conflicts, .. } = error {
+        assert_eq!(conflicts.len(), 4);
+        assert!(conflicts.iter().all(|conflict| conflict.kind() == ConflictKind::ShiftReduce));
+
+        let conflict = &conflicts[0];
+        assert_eq!(
+            conflict.actions(),
+            [Action::Reduce { rule_index: 1 }, Action::Shift { next_state: 5 }]
+        );
+        assert_eq!(
+            conflict.rules(),
+            [Rule::new(
+                "E",
+                [
+                    Symbol::from("E").into(),
+                    ConstantToken::from("*").into(),
+                    Symbol::from("E").into(),
+                ]
+            )]
+        );
+        assert_eq!(
+            conflict.items().iter().map(|item| item.to_string()).collect::<Vec<_>>(),
+            ["E -> E '*' E .", "E -> E . '+' E"],
+        );
+        assert_eq!(
+            conflict.to_string(),
+            r#"
+
+shift/reduce conflict at state 4 on '+'
+  reduce 2 (E -> E '*' E)
+    E -> E '*' E .  { $, '+', '*' }
+  shift 5
+    E -> E . '+' E  { $, '+', '*' }
+
+            "#
+            .trim(),
+        );
+    } else {
+        panic!("unexpected parser error {:?}", error);
+    }
+}
+
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn finding_unifying_counterexample_for_shift_reduce_conflict() {
+    let grammar = Grammar::parse(common::grammars::SHIFT_REDUCE_CONFLICT).unwrap();
+    let error = Parser::lr(grammar).unwrap_err();
+
+    if let ParserError::Conflicts { parser, conflicts } = error {
+        let counterexample = parser.counterexample(&conflicts[0]).unwrap();
+        assert!(counterexample.is_unifying());
+        assert_eq!(
+            counterexample.prefix(),
+            [
+                Symbol::from("E").into(),
+                ConstantToken::from("+").into(),
+                Symbol::from("E").into(),
+            ],
+        );
+        assert_eq!(
+            counterexample.to_string(),
+            r#"
+
+unifying at state 5 on '+'
+  prefix: E '+' E
+  reduce 1
+    example: E '+' E • '+' E
+    derivation: E -> [ E -> [ E '+' E • ] '+' E ]
+  shift 4
+    example: E '+' E • '+' E
+    derivation: E -> [ E '+' E -> [ E • '+' E ] ]
+
+            "#
+            .trim(),
+        );
+    } else {
+        panic!("unexpected parser error {:?}", error);
+    }
+}
+
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn finding_unifying_counterexample_for_reduce_reduce_conflict() {
+    let grammar = Grammar::parse(common::grammars::REDUCE_REDUCE_CONFLICT).unwrap();
+    let error = Parser::lr(grammar).unwrap_err();
+
+    if let ParserError::Conflicts { parser, conflicts } = error {
+        let counterexample = parser.counterexample(&conflicts[0]).unwrap();
+        assert!(counterexample.is_unifying());
+        assert_eq!(
+            counterexample
+                .derivations()
+                .iter()
+                .map(|(action, derivation)| (*action, derivation.to_string()))
+                .collect::<Vec<_>>(),
+            [
+                (Action::Reduce { rule_index: 2 }, "S -> [ A -> [ '0' • ] ]".to_owned()),
+                (Action::Reduce { rule_index: 3 }, "S -> [ B -> [ '0' • ] ]".to_owned()),
+            ],
+        );
+    } else {
+        panic!("unexpected parser error {:?}", error);
+    }
+}
+
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn finding_non_unifying_counterexample_for_lalr_conflict() {
+    let grammar = Grammar::parse(common::grammars::NOT_LALR).unwrap();
+    let error = Parser::lalr(grammar).unwrap_err();
+
+    if let ParserError::Conflicts { parser, conflicts } = error {
+        let counterexample = parser.counterexample(&conflicts[0]).unwrap();
+        assert!(!counterexample.is_unifying());
+        assert_eq!(
+            counterexample
+                .derivations()
+                .iter()
+                .map(|(_, derivation)| derivation.example())
+                .collect::<Vec<_>>(),
+            ["'a' 'x' • 'a'", "'b' 'x' • 'a'"],
+        );
+    } else {
+        panic!("unexpected parser error {:?}", error);
+    }
+}
+
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn raising_correct_error_when_creating_ll_parser_for_left_recursive_grammar() {
+    let grammar = Grammar::parse(common::grammars::BINARY_ADDITION).unwrap();
+
+    let error = LlParser::new(grammar).unwrap_err();
+    if let ParserError::LlConflicts { conflicts, .. } = &error {
+        assert_eq!(error.to_string(), "conflicts at E on '0', E on '1'");
+        assert_eq!(conflicts.len(), 2);
+
+        let conflict = &conflicts[0];
+        assert_eq!(*conflict.symbol(), Symbol::from("E"));
+        assert_eq!(*conflict.token(), ConstantToken::from("0").into());
+        assert_eq!(conflict.kind(), LlConflictKind::LeftRecursion);
+        assert_eq!(conflict.rule_indices(), [0, 1]);
+        assert_eq!(
+            conflict.rules(),
+            [
+                Rule::new(
+                    "E",
+                    [
+                        Symbol::from("E").into(),
+                        ConstantToken::from("+").into(),
+                        Symbol::from("B").into(),
+                    ]
+                ),
+                Rule::new("E", [Symbol::from("B").into()]),
+            ]
+        );
+    } else {
+        panic!("unexpected parser error {:?}", error);
+    }
+}
 
-        assert!(possible_actions.unwrap().len() >= 2);
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn raising_correct_error_when_creating_ll_parser_for_common_prefix_grammar() {
+    let grammar = Grammar::parse(common::grammars::CONDITIONAL).unwrap();
+    assert!(Parser::lr(grammar.clone()).is_ok());
+
+    let error = LlParser::new(grammar).unwrap_err();
+    if let ParserError::LlConflicts { conflicts, .. } = &error {
+        assert_eq!(conflicts.len(), 1);
+
+        let conflict = &conflicts[0];
+        assert_eq!(*conflict.symbol(), Symbol::from("Conditional"));
+        assert_eq!(*conflict.token(), ConstantToken::from("if").into());
+        assert_eq!(conflict.kind(), LlConflictKind::FirstFirst);
+        assert_eq!(conflict.rule_indices(), [0, 1]);
     } else {
         panic!("unexpected parser error {:?}", error);
     }
 }
 
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn raising_correct_error_when_creating_ll_parser_for_first_follow_conflict_grammar() {
+    let grammar = Grammar::parse(
+        r#"
+S -> A 'a'
+
+A -> 'a'
+A -> ''
+        "#,
+    )
+    .unwrap();
+
+    let error = LlParser::new(grammar).unwrap_err();
+    if let ParserError::LlConflicts { conflicts, .. } = &error {
+        assert_eq!(conflicts.len(), 1);
+
+        // `A` can be empty and `'a'` follows it, so `'a'` can be both the start of `A`
+        // and the token after an empty `A`.
+        let conflict = &conflicts[0];
+        assert_eq!(*conflict.symbol(), Symbol::from("A"));
+        assert_eq!(*conflict.token(), ConstantToken::from("a").into());
+        assert_eq!(conflict.kind(), LlConflictKind::FirstFollow);
+        assert_eq!(conflict.rule_indices(), [1, 2]);
+        assert_eq!(
+            conflict.to_string(),
+            "first/follow conflict at A on 'a'\n  expand 2 (A -> 'a')\n  expand 3 (A -> ε)",
+        );
+    } else {
+        panic!("unexpected parser error {:?}", error);
+    }
+}
 
 #[test]
 #[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+#[allow(clippy::useless_conversion)]
 fn correctly_creating_first_and_follow_sets_for_indirectly_empty_grammar() {
     let grammar = Grammar::parse(common::grammars::INDIRECT_EMPTY).unwrap();
     let parser = Parser::lr(grammar).unwrap();
@@ -331,12 +666,14 @@ B -> '1'
         // +-------+------------------+------------+--------------+
         // | State |      Items       | Lookaheads | Transitions  |
         // +-------+------------------+------------+--------------+
-        // | 0     |  E -> . E '+' B  | { $, '+' } |   E   ->  1  |
-        // |       |  E -> . B        | { $, '+' } |   B   ->  2  |
-        // |       |  B -> . '0'      | { $, '+' } |  '0'  ->  3  |
-        // |       |  B -> . '1'      | { $, '+' } |  '1'  ->  4  |
+        // | 0     |  E' -> . E $     | { $ }      |   E   ->  1  |
+        // |       |  E -> . E '+' B  | { $, '+' } |   B   ->  2  |
+        // |       |  E -> . B        | { $, '+' } |  '0'  ->  3  |
+        // |       |  B -> . '0'      | { $, '+' } |  '1'  ->  4  |
+        // |       |  B -> . '1'      | { $, '+' } |              |
         // +-------+------------------+------------+--------------+
-        // | 1     |  E -> E . '+' B  | { $, '+' } |  '+'  ->  5  |
+        // | 1     |  E' -> E . $     | { $ }      |  '+'  ->  5  |
+        // |       |  E -> E . '+' B  | { $, '+' } |              |
         // +-------+------------------+------------+--------------+
         // | 2     |  E -> B .        | { $, '+' } |              |
         // +-------+------------------+------------+--------------+
@@ -359,6 +696,18 @@ B -> '1'
                 State::new(
                     0,
                     [
+                        // E' -> . E $ | { $ }
+                        Item::new(
+                            Rule::new(
+                                "E'",
+                                [
+                                    Symbol::from("E").into(),
+                                    Token::Eof.into()
+                                ]
+                            ),
+                            0,
+                            [Token::Eof],
+                        ),
                         // E -> . E '+' B | { $, '+' }
                         Item::new(
                             Rule::new(
@@ -422,6 +771,18 @@ B -> '1'
                 State::new(
                     1,
                     [
+                        // E' -> E . $ | { $ }
+                        Item::new(
+                            Rule::new(
+                                "E'",
+                                [
+                                    Symbol::from("E").into(),
+                                    Token::Eof.into()
+                                ]
+                            ),
+                            1,
+                            [Token::Eof],
+                        ),
                         // E -> E . '+' B | { $, '+' }
                         Item::new(
                             Rule::new(
@@ -586,9 +947,9 @@ B -> '1'
         // +-------+--------------------------------+--------------+
         // | 0     |     -     s3     s4      -     |    1    2    |
         // +-------+--------------------------------+--------------+
-        // | 1     |    s5      -      -      -     |    -    -    |
+        // | 1     |    s5      -      -     acc    |    -    -    |
         // +-------+--------------------------------+--------------+
-        // | 2     |    r2      -      -     a2     |    -    -    |
+        // | 2     |    r2      -      -     r2     |    -    -    |
         // +-------+--------------------------------+--------------+
         // | 3     |    r3      -      -     r3     |    -    -    |
         // +-------+--------------------------------+--------------+
@@ -596,7 +957,7 @@ B -> '1'
         // +-------+--------------------------------+--------------+
         // | 5     |     -     s3     s4      -     |    -    6    |
         // +-------+--------------------------------+--------------+
-        // | 6     |    r1      -      -     a1     |    -    -    |
+        // | 6     |    r1      -      -     r1     |    -    -    |
         // +-------+--------------------------------+--------------+
 
         #[rustfmt::skip]
@@ -619,6 +980,10 @@ B -> '1'
                 // State 1
                 IndexMap::<Token, IndexSet<Action>>::from(
                     [
+                        (
+                            Token::Eof,
+                            IndexSet::from([Action::Accept]),
+                        ),
                         (
                             ConstantToken::from("+").into(),
                             IndexSet::from([Action::Shift { next_state: 5 }]),
@@ -634,7 +999,7 @@ B -> '1'
                         ),
                         (
                             Token::Eof,
-                            IndexSet::from([Action::Accept { rule_index: 1 }]),
+                            IndexSet::from([Action::Reduce { rule_index: 1 }]),
                         ),
                     ],
                 ),
@@ -686,7 +1051,7 @@ B -> '1'
                         ),
                         (
                             Token::Eof,
-                            IndexSet::from([Action::Accept { rule_index: 0 }]),
+                            IndexSet::from([Action::Reduce { rule_index: 0 }]),
                         ),
                     ],
                 ),
@@ -765,54 +1130,557 @@ B -> '1'
 
 #[test]
 #[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
-fn correctly_creating_lalr_parser_for_g10_grammar() {
-    let grammar = Grammar::parse(common::grammars::G10).unwrap();
-    let parser = Parser::lalr(grammar).unwrap();
-
-    assert_eq!(
-        parser.grammar().to_string().trim(),
-        r#"
-
-P -> E
-E -> E '+' T
-E -> T
-T -> %id '(' E ')'
-T -> %id
-
-%id -> /^[A-Za-z][A-Za-z0-9]+/
+fn correctly_creating_lr0_parser_for_binary_addition_grammar() {
+    let grammar = Grammar::parse(common::grammars::BINARY_ADDITION).unwrap();
+    let parser = Parser::lr0(grammar).unwrap();
 
-        "#
-        .trim()
-    );
+    assert_eq!(parser.method(), ParsingMethod::Lr0);
 
-    let first_table = parser.first_table();
+    let automaton = parser.automaton();
     {
-        // +--------+-----------+
-        // | Symbol | First Set |
-        // +--------+-----------+
-        // | T      | { %id }   |
-        // +--------+-----------+
-        // | E      | { %id }   |
-        // +--------+-----------+
-        // | P      | { %id }   |
-        // +--------+-----------+
+        // +-------+------------------+--------------+
+        // | State |      Items       | Transitions  |
+        // +-------+------------------+--------------+
+        // | 0     |  E' -> . E $     |   E   ->  1  |
+        // |       |  E -> . E '+' B  |   B   ->  2  |
+        // |       |  E -> . B        |  '0'  ->  3  |
+        // |       |  B -> . '0'      |  '1'  ->  4  |
+        // |       |  B -> . '1'      |              |
+        // +-------+------------------+--------------+
+        // | 1     |  E' -> E . $     |  '+'  ->  5  |
+        // |       |  E -> E . '+' B  |              |
+        // +-------+------------------+--------------+
+        // | 2     |  E -> B .        |              |
+        // +-------+------------------+--------------+
+        // | 3     |  B -> '0' .      |              |
+        // +-------+------------------+--------------+
+        // | 4     |  B -> '1' .      |              |
+        // +-------+------------------+--------------+
+        // | 5     |  E -> E '+' . B  |  '0'  ->  3  |
+        // |       |  B -> . '0'      |  '1'  ->  4  |
+        // |       |  B -> . '1'      |   B   ->  6  |
+        // +-------+------------------+--------------+
+        // | 6     |  E -> E '+' B .  |              |
+        // +-------+------------------+--------------+
 
         #[rustfmt::skip]
         assert_eq!(
-            *first_table.deref(),
+            automaton.states(),
             [
-                (
-                    Symbol::from("T"),
+                // State 0
+                State::new(
+                    0,
                     [
-                        RegexToken::from("id").into(),
-                    ]
-                        .into(),
-                ),
-                (
-                    Symbol::from("E"),
+                        // E' -> . E $
+                        Item::new(
+                            Rule::new(
+                                "E'",
+                                [
+                                    Symbol::from("E").into(),
+                                    Token::Eof.into(),
+                                ]
+                            ),
+                            0,
+                            [],
+                        ),
+                        // E -> . E '+' B
+                        Item::new(
+                            Rule::new(
+                                "E",
+                                [
+                                    Symbol::from("E").into(),
+                                    ConstantToken::from("+").into(),
+                                    Symbol::from("B").into(),
+                                ]
+                            ),
+                            0,
+                            [],
+                        ),
+                        // E -> . B
+                        Item::new(
+                            Rule::new(
+                                "E",
+                                [
+                                    Symbol::from("B").into(),
+                                ]
+                            ),
+                            0,
+                            [],
+                        ),
+                        // B -> . '0'
+                        Item::new(
+                            Rule::new(
+                                "B",
+                                [
+                                    ConstantToken::from("0").into(),
+                                ]
+                            ),
+                            0,
+                            [],
+                        ),
+                        // B -> . '1'
+                        Item::new(
+                            Rule::new(
+                                "B",
+                                [
+                                    ConstantToken::from("1").into(),
+                                ]
+                            ),
+                            0,
+                            [],
+                        ),
+                    ],
                     [
-                        RegexToken::from("id").into(),
-                    ]
+                        // E -> 1
+                        (Symbol::from("E").into(), 1),
+                        // B -> 2
+                        (Symbol::from("B").into(), 2),
+                        // '0' -> 3
+                        (ConstantToken::from("0").into(), 3),
+                        // '1' -> 4
+                        (ConstantToken::from("1").into(), 4),
+                    ],
+                ),
+
+                // State 1
+                State::new(
+                    1,
+                    [
+                        // E' -> E . $
+                        Item::new(
+                            Rule::new(
+                                "E'",
+                                [
+                                    Symbol::from("E").into(),
+                                    Token::Eof.into(),
+                                ]
+                            ),
+                            1,
+                            [],
+                        ),
+                        // E -> E . '+' B
+                        Item::new(
+                            Rule::new(
+                                "E",
+                                [
+                                    Symbol::from("E").into(),
+                                    ConstantToken::from("+").into(),
+                                    Symbol::from("B").into(),
+                                ]
+                            ),
+                            1,
+                            [],
+                        ),
+                    ],
+                    [
+                        // '+' -> 5
+                        (ConstantToken::from("+").into(), 5),
+                    ],
+                ),
+
+                // State 2
+                State::new(
+                    2,
+                    [
+                        // E -> B .
+                        Item::new(
+                            Rule::new(
+                                "E",
+                                [
+                                    Symbol::from("B").into(),
+                                ]
+                            ),
+                            1,
+                            [],
+                        ),
+                    ],
+                    [],
+                ),
+
+                // State 3
+                State::new(
+                    3,
+                    [
+                        // B -> '0' .
+                        Item::new(
+                            Rule::new(
+                                "B",
+                                [
+                                    ConstantToken::from("0").into(),
+                                ]
+                            ),
+                            1,
+                            [],
+                        ),
+                    ],
+                    [],
+                ),
+
+                // State 4
+                State::new(
+                    4,
+                    [
+                        // B -> '1' .
+                        Item::new(
+                            Rule::new(
+                                "B",
+                                [
+                                    ConstantToken::from("1").into(),
+                                ]
+                            ),
+                            1,
+                            [],
+                        ),
+                    ],
+                    [],
+                ),
+
+                // State 5
+                State::new(
+                    5,
+                    [
+                        // E -> E '+' . B
+                        Item::new(
+                            Rule::new(
+                                "E",
+                                [
+                                    Symbol::from("E").into(),
+                                    ConstantToken::from("+").into(),
+                                    Symbol::from("B").into(),
+                                ]
+                            ),
+                            2,
+                            [],
+                        ),
+                        // B -> . '0'
+                        Item::new(
+                            Rule::new(
+                                "B",
+                                [
+                                    ConstantToken::from("0").into(),
+                                ]
+                            ),
+                            0,
+                            [],
+                        ),
+                        // B -> . '1'
+                        Item::new(
+                            Rule::new(
+                                "B",
+                                [
+                                    ConstantToken::from("1").into(),
+                                ]
+                            ),
+                            0,
+                            [],
+                        ),
+                    ],
+                    [
+                        // B -> 6
+                        (Symbol::from("B").into(), 6),
+                        // '0' -> 3
+                        (ConstantToken::from("0").into(), 3),
+                        // '1' -> 4
+                        (ConstantToken::from("1").into(), 4),
+                    ],
+                ),
+
+                // State 6
+                State::new(
+                    6,
+                    [
+                        // E -> E '+' B .
+                        Item::new(
+                            Rule::new(
+                                "E",
+                                [
+                                    Symbol::from("E").into(),
+                                    ConstantToken::from("+").into(),
+                                    Symbol::from("B").into(),
+                                ]
+                            ),
+                            3,
+                            [],
+                        ),
+                    ],
+                    [],
+                ),
+            ]
+        );
+    }
+
+    let action_table = parser.action_table();
+    {
+        // +-------+--------------------------------+
+        // |       |             Action             |
+        // | State | ------------------------------ |
+        // |       |    '+'    '0'    '1'     $     |
+        // +-------+--------------------------------+
+        // | 0     |     -     s3     s4      -     |
+        // +-------+--------------------------------+
+        // | 1     |    s5      -      -     acc    |
+        // +-------+--------------------------------+
+        // | 2     |    r2     r2     r2     r2     |
+        // +-------+--------------------------------+
+        // | 3     |    r3     r3     r3     r3     |
+        // +-------+--------------------------------+
+        // | 4     |    r4     r4     r4     r4     |
+        // +-------+--------------------------------+
+        // | 5     |     -     s3     s4      -     |
+        // +-------+--------------------------------+
+        // | 6     |    r1     r1     r1     r1     |
+        // +-------+--------------------------------+
+
+        #[rustfmt::skip]
+        assert_eq!(
+            action_table,
+            [
+                // State 0
+                IndexMap::<Token, IndexSet<Action>>::from_iter(
+                    [
+                        (
+                            ConstantToken::from("0").into(),
+                            IndexSet::from([Action::Shift { next_state: 3 }]),
+                        ),
+                        (
+                            ConstantToken::from("1").into(),
+                            IndexSet::from([Action::Shift { next_state: 4 }]),
+                        ),
+                    ],
+                ),
+                // State 1
+                IndexMap::<Token, IndexSet<Action>>::from(
+                    [
+                        (
+                            Token::Eof,
+                            IndexSet::from([Action::Accept]),
+                        ),
+                        (
+                            ConstantToken::from("+").into(),
+                            IndexSet::from([Action::Shift { next_state: 5 }]),
+                        ),
+                    ],
+                ),
+                // State 2
+                IndexMap::<Token, IndexSet<Action>>::from(
+                    [
+                        (
+                            ConstantToken::from("+").into(),
+                            IndexSet::from([Action::Reduce { rule_index: 1 }]),
+                        ),
+                        (
+                            ConstantToken::from("0").into(),
+                            IndexSet::from([Action::Reduce { rule_index: 1 }]),
+                        ),
+                        (
+                            ConstantToken::from("1").into(),
+                            IndexSet::from([Action::Reduce { rule_index: 1 }]),
+                        ),
+                        (
+                            Token::Eof,
+                            IndexSet::from([Action::Reduce { rule_index: 1 }]),
+                        ),
+                    ],
+                ),
+                // State 3
+                IndexMap::<Token, IndexSet<Action>>::from(
+                    [
+                        (
+                            ConstantToken::from("+").into(),
+                            IndexSet::from([Action::Reduce { rule_index: 2 }]),
+                        ),
+                        (
+                            ConstantToken::from("0").into(),
+                            IndexSet::from([Action::Reduce { rule_index: 2 }]),
+                        ),
+                        (
+                            ConstantToken::from("1").into(),
+                            IndexSet::from([Action::Reduce { rule_index: 2 }]),
+                        ),
+                        (
+                            Token::Eof,
+                            IndexSet::from([Action::Reduce { rule_index: 2 }]),
+                        ),
+                    ],
+                ),
+                // State 4
+                IndexMap::<Token, IndexSet<Action>>::from(
+                    [
+                        (
+                            ConstantToken::from("+").into(),
+                            IndexSet::from([Action::Reduce { rule_index: 3 }]),
+                        ),
+                        (
+                            ConstantToken::from("0").into(),
+                            IndexSet::from([Action::Reduce { rule_index: 3 }]),
+                        ),
+                        (
+                            ConstantToken::from("1").into(),
+                            IndexSet::from([Action::Reduce { rule_index: 3 }]),
+                        ),
+                        (
+                            Token::Eof,
+                            IndexSet::from([Action::Reduce { rule_index: 3 }]),
+                        ),
+                    ],
+                ),
+                // State 5
+                IndexMap::<Token, IndexSet<Action>>::from(
+                    [
+                        (
+                            ConstantToken::from("0").into(),
+                            IndexSet::from([Action::Shift { next_state: 3 }]),
+                        ),
+                        (
+                            ConstantToken::from("1").into(),
+                            IndexSet::from([Action::Shift { next_state: 4 }]),
+                        ),
+                    ],
+                ),
+                // State 6
+                IndexMap::<Token, IndexSet<Action>>::from(
+                    [
+                        (
+                            ConstantToken::from("+").into(),
+                            IndexSet::from([Action::Reduce { rule_index: 0 }]),
+                        ),
+                        (
+                            ConstantToken::from("0").into(),
+                            IndexSet::from([Action::Reduce { rule_index: 0 }]),
+                        ),
+                        (
+                            ConstantToken::from("1").into(),
+                            IndexSet::from([Action::Reduce { rule_index: 0 }]),
+                        ),
+                        (
+                            Token::Eof,
+                            IndexSet::from([Action::Reduce { rule_index: 0 }]),
+                        ),
+                    ],
+                ),
+            ]
+        );
+    }
+
+    let goto_table = parser.goto_table();
+    {
+        // +-------+--------------+
+        // |       |     Goto     |
+        // | State | ------------ |
+        // |       |    E    B    |
+        // +-------+--------------+
+        // | 0     |    1    2    |
+        // +-------+--------------+
+        // | 1     |    -    -    |
+        // +-------+--------------+
+        // | 2     |    -    -    |
+        // +-------+--------------+
+        // | 3     |    -    -    |
+        // +-------+--------------+
+        // | 4     |    -    -    |
+        // +-------+--------------+
+        // | 5     |    -    6    |
+        // +-------+--------------+
+        // | 6     |    -    -    |
+        // +-------+--------------+
+
+        #[rustfmt::skip]
+        assert_eq!(
+            goto_table,
+            [
+                // State 0
+                IndexMap::<Symbol, usize>::from_iter(
+                    [
+                        (Symbol::from("E"), 1),
+                        (Symbol::from("B"), 2),
+                    ],
+                ),
+                // State 1
+                IndexMap::<Symbol, usize>::from_iter(
+                    [
+                    ],
+                ),
+                // State 2
+                IndexMap::<Symbol, usize>::from_iter(
+                    [
+                    ],
+                ),
+                // State 3
+                IndexMap::<Symbol, usize>::from_iter(
+                    [
+                    ],
+                ),
+                // State 4
+                IndexMap::<Symbol, usize>::from_iter(
+                    [
+                    ],
+                ),
+                // State 5
+                IndexMap::<Symbol, usize>::from_iter(
+                    [
+                        (Symbol::from("B"), 6),
+                    ],
+                ),
+                // State 6
+                IndexMap::<Symbol, usize>::from_iter(
+                    [
+                    ],
+                ),
+            ]
+        );
+    }
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_creating_lalr_parser_for_g10_grammar() {
+    let grammar = Grammar::parse(common::grammars::G10).unwrap();
+    let parser = Parser::lalr(grammar).unwrap();
+
+    assert_eq!(
+        parser.grammar().to_string().trim(),
+        r#"
+
+P -> E
+E -> E '+' T
+E -> T
+T -> %id '(' E ')'
+T -> %id
+
+%id -> /^[A-Za-z][A-Za-z0-9]+/
+
+        "#
+        .trim()
+    );
+
+    let first_table = parser.first_table();
+    {
+        // +--------+-----------+
+        // | Symbol | First Set |
+        // +--------+-----------+
+        // | T      | { %id }   |
+        // +--------+-----------+
+        // | E      | { %id }   |
+        // +--------+-----------+
+        // | P      | { %id }   |
+        // +--------+-----------+
+
+        #[rustfmt::skip]
+        assert_eq!(
+            *first_table.deref(),
+            [
+                (
+                    Symbol::from("T"),
+                    [
+                        RegexToken::from("id").into(),
+                    ]
+                        .into(),
+                ),
+                (
+                    Symbol::from("E"),
+                    [
+                        RegexToken::from("id").into(),
+                    ]
                         .into(),
                 ),
                 (
@@ -880,36 +1748,39 @@ T -> %id
         // +-------+------------------------+-----------------+--------------+
         // | State |         Items          |   Lookaheads    | Transitions  |
         // +-------+------------------------+-----------------+--------------+
-        // | 0     |  P -> . E              | { $ }           |   E   ->  1  |
-        // |       |  E -> . E '+' T        | { $, '+' }      |   T   ->  2  |
-        // |       |  E -> . T              | { $, '+' }      |  %id  ->  3  |
+        // | 0     |  P' -> . P $           | { $ }           |   P   ->  1  |
+        // |       |  P -> . E              | { $ }           |   E   ->  2  |
+        // |       |  E -> . E '+' T        | { $, '+' }      |   T   ->  3  |
+        // |       |  E -> . T              | { $, '+' }      |  %id  ->  4  |
         // |       |  T -> . %id '(' E ')'  | { $, '+' }      |              |
         // |       |  T -> . %id            | { $, '+' }      |              |
         // +-------+------------------------+-----------------+--------------+
-        // | 1     |  P -> E .              | { $ }           |  '+'  ->  7  |
+        // | 1     |  P' -> P . $           | { $ }           |              |
+        // +-------+------------------------+-----------------+--------------+
+        // | 2     |  P -> E .              | { $ }           |  '+'  ->  8  |
         // |       |  E -> E . '+' T        | { $, '+' }      |              |
         // +-------+------------------------+-----------------+--------------+
-        // | 2     |  E -> T .              | { $, '+', ')' } |              |
+        // | 3     |  E -> T .              | { $, '+', ')' } |              |
         // +-------+------------------------+-----------------+--------------+
-        // | 3     |  T -> %id . '(' E ')'  | { $, '+', ')' } |  '('  ->  4  |
+        // | 4     |  T -> %id . '(' E ')'  | { $, '+', ')' } |  '('  ->  5  |
         // |       |  T -> %id .            | { $, '+', ')' } |              |
         // +-------+------------------------+-----------------+--------------+
-        // | 4     |  T -> %id '(' . E ')'  | { $, '+', ')' } |   T   ->  2  |
-        // |       |  E -> . E '+' T        | { ')', '+' }    |  %id  ->  3  |
-        // |       |  E -> . T              | { ')', '+' }    |   E   ->  5  |
+        // | 5     |  T -> %id '(' . E ')'  | { $, '+', ')' } |   T   ->  3  |
+        // |       |  E -> . E '+' T        | { ')', '+' }    |  %id  ->  4  |
+        // |       |  E -> . T              | { ')', '+' }    |   E   ->  6  |
         // |       |  T -> . %id '(' E ')'  | { ')', '+' }    |              |
         // |       |  T -> . %id            | { ')', '+' }    |              |
         // +-------+------------------------+-----------------+--------------+
-        // | 5     |  T -> %id '(' E . ')'  | { $, '+', ')' } |  ')'  ->  6  |
-        // |       |  E -> E . '+' T        | { ')', '+' }    |  '+'  ->  7  |
+        // | 6     |  T -> %id '(' E . ')'  | { $, '+', ')' } |  ')'  ->  7  |
+        // |       |  E -> E . '+' T        | { ')', '+' }    |  '+'  ->  8  |
         // +-------+------------------------+-----------------+--------------+
-        // | 6     |  T -> %id '(' E ')' .  | { ')', '+', $ } |              |
+        // | 7     |  T -> %id '(' E ')' .  | { ')', '+', $ } |              |
         // +-------+------------------------+-----------------+--------------+
-        // | 7     |  E -> E '+' . T        | { ')', '+', $ } |  %id  ->  3  |
-        // |       |  T -> . %id '(' E ')'  | { ')', '+', $ } |   T   ->  8  |
+        // | 8     |  E -> E '+' . T        | { ')', '+', $ } |  %id  ->  4  |
+        // |       |  T -> . %id '(' E ')'  | { ')', '+', $ } |   T   ->  9  |
         // |       |  T -> . %id            | { ')', '+', $ } |              |
         // +-------+------------------------+-----------------+--------------+
-        // | 8     |  E -> E '+' T .        | { ')', '+', $ } |              |
+        // | 9     |  E -> E '+' T .        | { ')', '+', $ } |              |
         // +-------+------------------------+-----------------+--------------+
 
         #[rustfmt::skip]
@@ -920,6 +1791,18 @@ T -> %id
                 State::new(
                     0,
                     [
+                        // P' -> . P $ | { $ }
+                        Item::new(
+                            Rule::new(
+                                "P'",
+                                [
+                                    Symbol::from("P").into(),
+                                    Token::Eof.into(),
+                                ]
+                            ),
+                            0,
+                            [Token::Eof],
+                        ),
                         // P -> . E | { $ }
                         Item::new(
                             Rule::new(
@@ -972,28 +1855,50 @@ T -> %id
                         // T -> . %id | { $, '+' }
                         Item::new(
                             Rule::new(
-                                "T",
+                                "T",
+                                [
+                                    RegexToken::from("id").into(),
+                                ]
+                            ),
+                            0,
+                            [Token::Eof, ConstantToken::from("+").into()],
+                        ),
+                    ],
+                    [
+                        // P -> 1
+                        (Symbol::from("P").into(), 1),
+                        // E -> 2
+                        (Symbol::from("E").into(), 2),
+                        // T -> 3
+                        (Symbol::from("T").into(), 3),
+                        // %id -> 4
+                        (RegexToken::from("id").into(), 4),
+                    ],
+                ),
+
+                // State 1
+                State::new(
+                    1,
+                    [
+                        // P' -> P . $ | { $ }
+                        Item::new(
+                            Rule::new(
+                                "P'",
                                 [
-                                    RegexToken::from("id").into(),
+                                    Symbol::from("P").into(),
+                                    Token::Eof.into(),
                                 ]
                             ),
-                            0,
-                            [Token::Eof, ConstantToken::from("+").into()],
+                            1,
+                            [Token::Eof],
                         ),
                     ],
-                    [
-                        // E -> 1
-                        (Symbol::from("E").into(), 1),
-                        // T -> 2
-                        (Symbol::from("T").into(), 2),
-                        // %id -> 3
-                        (RegexToken::from("id").into(), 3),
-                    ],
+                    [],
                 ),
 
-                 // State 1
+                // State 2
                 State::new(
-                    1,
+                    2,
                     [
                         // P -> E . | { $ }
                         Item::new(
@@ -1021,14 +1926,14 @@ T -> %id
                         ),
                     ],
                     [
-                        // '+' -> 7
-                        (ConstantToken::from("+").into(), 7),
+                        // '+' -> 8
+                        (ConstantToken::from("+").into(), 8),
                     ],
                 ),
 
-                // State 2
+                // State 3
                 State::new(
-                    2,
+                    3,
                     [
                         // E -> T . | { $, '+', ')' }
                         Item::new(
@@ -1049,9 +1954,9 @@ T -> %id
                     [],
                 ),
 
-                // State 3
+                // State 4
                 State::new(
-                    3,
+                    4,
                     [
                         // T -> %id . '(' E ')' | { $, '+', ')' }
                         Item::new(
@@ -1088,14 +1993,14 @@ T -> %id
                         ),
                     ],
                     [
-                        // '(' -> 4
-                        (ConstantToken::from("(").into(), 4),
+                        // '(' -> 5
+                        (ConstantToken::from("(").into(), 5),
                     ],
                 ),
 
-                // State 4
+                // State 5
                 State::new(
-                    4,
+                    5,
                     [
                         // T -> %id '(' . E ')' | { $, '+', ')' }
                         Item::new(
@@ -1166,18 +2071,18 @@ T -> %id
                         ),
                     ],
                     [
-                        // T -> 2
-                        (Symbol::from("T").into(), 2),
-                        // %id -> 3
-                        (RegexToken::from("id").into(), 3),
-                        // 'E' -> 5
-                        (Symbol::from("E").into(), 5),
+                        // E -> 6
+                        (Symbol::from("E").into(), 6),
+                        // T -> 3
+                        (Symbol::from("T").into(), 3),
+                        // %id -> 4
+                        (RegexToken::from("id").into(), 4),
                     ],
                 ),
 
-                // State 5
+                // State 6
                 State::new(
-                    5,
+                    6,
                     [
                         // T -> %id '(' E . ')' | { $, '+', ')' }
                         Item::new(
@@ -1212,16 +2117,16 @@ T -> %id
                         ),
                     ],
                     [
-                        // ')' -> 6
-                        (ConstantToken::from(")").into(), 6),
-                        // '+' -> 7
-                        (ConstantToken::from("+").into(), 7),
+                        // ')' -> 7
+                        (ConstantToken::from(")").into(), 7),
+                        // '+' -> 8
+                        (ConstantToken::from("+").into(), 8),
                     ],
                 ),
 
-                // State 6
+                // State 7
                 State::new(
-                    6,
+                    7,
                     [
                         // T -> %id '(' E ')' . | { ')', '+', $ }
                         Item::new(
@@ -1245,9 +2150,9 @@ T -> %id
                     [],
                 ),
 
-                // State 7
+                // State 8
                 State::new(
-                    7,
+                    8,
                     [
                         // E -> E '+' . T | { ')', '+', $ }
                         Item::new(
@@ -1301,16 +2206,16 @@ T -> %id
                         ),
                     ],
                     [
-                        // %id -> 3
-                        (RegexToken::from("id").into(), 3),
-                        // T -> 8
-                        (Symbol::from("T").into(), 8),
+                        // T -> 9
+                        (Symbol::from("T").into(), 9),
+                        // %id -> 4
+                        (RegexToken::from("id").into(), 4),
                     ],
                 ),
 
-                // State 8
+                // State 9
                 State::new(
-                    8,
+                    9,
                     [
                         // E -> E '+' T . | { ')', '+', $ }
                         Item::new(
@@ -1343,23 +2248,25 @@ T -> %id
         // | State | ------------------------------------- |
         // |       |    '+'    '('    ')'    %id     $     |
         // +-------+---------------------------------------+
-        // | 0     |     -      -      -     s3      -     |
+        // | 0     |     -      -      -     s4      -     |
+        // +-------+---------------------------------------+
+        // | 1     |     -      -      -      -     acc    |
         // +-------+---------------------------------------+
-        // | 1     |    s7      -      -      -     a1     |
+        // | 2     |    s8      -      -      -     r1     |
         // +-------+---------------------------------------+
-        // | 2     |    r3      -     r3      -     r3     |
+        // | 3     |    r3      -     r3      -     r3     |
         // +-------+---------------------------------------+
-        // | 3     |    r5     s4     r5      -     r5     |
+        // | 4     |    r5     s5     r5      -     r5     |
         // +-------+---------------------------------------+
-        // | 4     |     -      -      -     s3      -     |
+        // | 5     |     -      -      -     s4      -     |
         // +-------+---------------------------------------+
-        // | 5     |    s7      -     s6      -      -     |
+        // | 6     |    s8      -     s7      -      -     |
         // +-------+---------------------------------------+
-        // | 6     |    r4      -     r4      -     r4     |
+        // | 7     |    r4      -     r4      -     r4     |
         // +-------+---------------------------------------+
-        // | 7     |     -      -      -     s3      -     |
+        // | 8     |     -      -      -     s4      -     |
         // +-------+---------------------------------------+
-        // | 8     |    r2      -     r2      -     r2     |
+        // | 9     |    r2      -     r2      -     r2     |
         // +-------+---------------------------------------+
 
         #[rustfmt::skip]
@@ -1371,122 +2278,131 @@ T -> %id
                     [
                         (
                             RegexToken::from("id").into(),
-                            IndexSet::from([Action::Shift { next_state: 3 }]),
+                            IndexSet::from([Action::Shift { next_state: 4 }]),
                         ),
                     ],
                 ),
                 // State 1
                 IndexMap::<Token, IndexSet<Action>>::from(
                     [
-                        (
-                            ConstantToken::from("+").into(),
-                            IndexSet::from([Action::Shift { next_state: 7 }]),
-                        ),
                         (
                             Token::Eof,
-                            IndexSet::from([Action::Accept { rule_index: 0 }]),
+                            IndexSet::from([Action::Accept]),
                         ),
                     ],
                 ),
                 // State 2
                 IndexMap::<Token, IndexSet<Action>>::from(
                     [
+                        (
+                            Token::Eof,
+                            IndexSet::from([Action::Reduce { rule_index: 0 }]),
+                        ),
                         (
                             ConstantToken::from("+").into(),
+                            IndexSet::from([Action::Shift { next_state: 8 }]),
+                        ),
+                    ],
+                ),
+                // State 3
+                IndexMap::<Token, IndexSet<Action>>::from(
+                    [
+                        (
+                            Token::Eof,
                             IndexSet::from([Action::Reduce { rule_index: 2 }]),
                         ),
                         (
-                            ConstantToken::from(")").into(),
+                            ConstantToken::from("+").into(),
                             IndexSet::from([Action::Reduce { rule_index: 2 }]),
                         ),
                         (
-                            Token::Eof,
+                            ConstantToken::from(")").into(),
                             IndexSet::from([Action::Reduce { rule_index: 2 }]),
                         ),
                     ],
                 ),
-                // State 3
+                // State 4
                 IndexMap::<Token, IndexSet<Action>>::from(
                     [
                         (
-                            ConstantToken::from("+").into(),
-                            IndexSet::from([Action::Reduce { rule_index: 4 }]),
+                            ConstantToken::from("(").into(),
+                            IndexSet::from([Action::Shift { next_state: 5 }]),
                         ),
                         (
-                            ConstantToken::from("(").into(),
-                            IndexSet::from([Action::Shift { next_state: 4 }]),
+                            Token::Eof,
+                            IndexSet::from([Action::Reduce { rule_index: 4 }]),
                         ),
                         (
-                            ConstantToken::from(")").into(),
+                            ConstantToken::from("+").into(),
                             IndexSet::from([Action::Reduce { rule_index: 4 }]),
                         ),
                         (
-                            Token::Eof,
+                            ConstantToken::from(")").into(),
                             IndexSet::from([Action::Reduce { rule_index: 4 }]),
                         ),
                     ],
                 ),
-                // State 4
+                // State 5
                 IndexMap::<Token, IndexSet<Action>>::from(
                     [
                         (
                             RegexToken::from("id").into(),
-                            IndexSet::from([Action::Shift { next_state: 3 }]),
+                            IndexSet::from([Action::Shift { next_state: 4 }]),
                         ),
                     ],
                 ),
-                // State 5
+                // State 6
                 IndexMap::<Token, IndexSet<Action>>::from(
                     [
                         (
-                            ConstantToken::from("+").into(),
+                            ConstantToken::from(")").into(),
                             IndexSet::from([Action::Shift { next_state: 7 }]),
                         ),
                         (
-                            ConstantToken::from(")").into(),
-                            IndexSet::from([Action::Shift { next_state: 6 }]),
+                            ConstantToken::from("+").into(),
+                            IndexSet::from([Action::Shift { next_state: 8 }]),
                         ),
                     ],
                 ),
-                // State 6
+                // State 7
                 IndexMap::<Token, IndexSet<Action>>::from(
                     [
                         (
-                            ConstantToken::from("+").into(),
+                            Token::Eof,
                             IndexSet::from([Action::Reduce { rule_index: 3 }]),
                         ),
                         (
-                            ConstantToken::from(")").into(),
+                            ConstantToken::from("+").into(),
                             IndexSet::from([Action::Reduce { rule_index: 3 }]),
                         ),
                         (
-                            Token::Eof,
+                            ConstantToken::from(")").into(),
                             IndexSet::from([Action::Reduce { rule_index: 3 }]),
                         ),
                     ],
                 ),
-                // State 7
+                // State 8
                 IndexMap::<Token, IndexSet<Action>>::from(
                     [
                         (
                             RegexToken::from("id").into(),
-                            IndexSet::from([Action::Shift { next_state: 3 }]),
+                            IndexSet::from([Action::Shift { next_state: 4 }]),
                         ),
                     ],
                 ),
-                // State 8
+                // State 9
                 IndexMap::<Token, IndexSet<Action>>::from(
                     [
                         (
-                            ConstantToken::from("+").into(),
+                            Token::Eof,
                             IndexSet::from([Action::Reduce { rule_index: 1 }]),
                         ),
                         (
-                            ConstantToken::from(")").into(),
+                            ConstantToken::from("+").into(),
                             IndexSet::from([Action::Reduce { rule_index: 1 }]),
                         ),
                         (
-                            Token::Eof,
+                            ConstantToken::from(")").into(),
                             IndexSet::from([Action::Reduce { rule_index: 1 }]),
                         ),
                     ],
@@ -1497,29 +2413,31 @@ T -> %id
 
     let goto_table = parser.goto_table();
     {
-        // +-------+-------------------+
-        // |       |       Goto        |
-        // | State | ----------------- |
-        // |       |    P    E    T    |
-        // +-------+-------------------+
-        // | 0     |    -    1    2    |
-        // +-------+-------------------+
-        // | 1     |    -    -    -    |
-        // +-------+-------------------+
-        // | 2     |    -    -    -    |
-        // +-------+-------------------+
-        // | 3     |    -    -    -    |
-        // +-------+-------------------+
-        // | 4     |    -    5    2    |
-        // +-------+-------------------+
-        // | 5     |    -    -    -    |
-        // +-------+-------------------+
-        // | 6     |    -    -    -    |
-        // +-------+-------------------+
-        // | 7     |    -    -    8    |
-        // +-------+-------------------+
-        // | 8     |    -    -    -    |
-        // +-------+-------------------+
+        // +-------+----------------------+
+        // |       |         Goto         |
+        // | State | -------------------- |
+        // |       |    P     E     T     |
+        // +-------+----------------------+
+        // | 0     |    1     2     3     |
+        // +-------+----------------------+
+        // | 1     |    -     -     -     |
+        // +-------+----------------------+
+        // | 2     |    -     -     -     |
+        // +-------+----------------------+
+        // | 3     |    -     -     -     |
+        // +-------+----------------------+
+        // | 4     |    -     -     -     |
+        // +-------+----------------------+
+        // | 5     |    -     6     3     |
+        // +-------+----------------------+
+        // | 6     |    -     -     -     |
+        // +-------+----------------------+
+        // | 7     |    -     -     -     |
+        // +-------+----------------------+
+        // | 8     |    -     -     9     |
+        // +-------+----------------------+
+        // | 9     |    -     -     -     |
+        // +-------+----------------------+
 
         #[rustfmt::skip]
         assert_eq!(
@@ -1528,8 +2446,9 @@ T -> %id
                 // State 0
                 IndexMap::<Symbol, usize>::from_iter(
                     [
-                        (Symbol::from("E"), 1),
-                        (Symbol::from("T"), 2),
+                        (Symbol::from("P"), 1),
+                        (Symbol::from("E"), 2),
+                        (Symbol::from("T"), 3),
                     ],
                 ),
                 // State 1
@@ -1550,13 +2469,13 @@ T -> %id
                 // State 4
                 IndexMap::<Symbol, usize>::from_iter(
                     [
-                        (Symbol::from("E"), 5),
-                        (Symbol::from("T"), 2),
                     ],
                 ),
                 // State 5
                 IndexMap::<Symbol, usize>::from_iter(
                     [
+                        (Symbol::from("E"), 6),
+                        (Symbol::from("T"), 3),
                     ],
                 ),
                 // State 6
@@ -1567,10 +2486,15 @@ T -> %id
                 // State 7
                 IndexMap::<Symbol, usize>::from_iter(
                     [
-                        (Symbol::from("T"), 8),
                     ],
                 ),
                 // State 8
+                IndexMap::<Symbol, usize>::from_iter(
+                    [
+                        (Symbol::from("T"), 9),
+                    ],
+                ),
+                // State 9
                 IndexMap::<Symbol, usize>::from_iter(
                     [
                     ],
@@ -1674,23 +2598,26 @@ O -> ε
         // +-------+----------------------+------------+--------------+
         // | State |        Items         | Lookaheads | Transitions  |
         // +-------+----------------------+------------+--------------+
-        // | 0     |  P -> . O 'x' O 'z'  | { $ }      |   O   ->  1  |
-        // |       |  O -> . 'y'          | { 'x' }    |  'y'  ->  2  |
+        // | 0     |  P' -> . P $         | { $ }      |   P   ->  1  |
+        // |       |  P -> . O 'x' O 'z'  | { $ }      |   O   ->  2  |
+        // |       |  O -> . 'y'          | { 'x' }    |  'y'  ->  3  |
         // |       |  O -> . ε            | { 'x' }    |              |
         // +-------+----------------------+------------+--------------+
-        // | 1     |  P -> O . 'x' O 'z'  | { $ }      |  'x'  ->  3  |
+        // | 1     |  P' -> P . $         | { $ }      |              |
+        // +-------+----------------------+------------+--------------+
+        // | 2     |  P -> O . 'x' O 'z'  | { $ }      |  'x'  ->  4  |
         // +-------+----------------------+------------+--------------+
-        // | 2     |  O -> 'y' .          | { 'x' }    |              |
+        // | 3     |  O -> 'y' .          | { 'x' }    |              |
         // +-------+----------------------+------------+--------------+
-        // | 3     |  P -> O 'x' . O 'z'  | { $ }      |   O   ->  4  |
-        // |       |  O -> . 'y'          | { 'z' }    |  'y'  ->  5  |
+        // | 4     |  P -> O 'x' . O 'z'  | { $ }      |   O   ->  5  |
+        // |       |  O -> . 'y'          | { 'z' }    |  'y'  ->  6  |
         // |       |  O -> . ε            | { 'z' }    |              |
         // +-------+----------------------+------------+--------------+
-        // | 4     |  P -> O 'x' O . 'z'  | { $ }      |  'z'  ->  6  |
+        // | 5     |  P -> O 'x' O . 'z'  | { $ }      |  'z'  ->  7  |
         // +-------+----------------------+------------+--------------+
-        // | 5     |  O -> 'y' .          | { 'z' }    |              |
+        // | 6     |  O -> 'y' .          | { 'z' }    |              |
         // +-------+----------------------+------------+--------------+
-        // | 6     |  P -> O 'x' O 'z' .  | { $ }      |              |
+        // | 7     |  P -> O 'x' O 'z' .  | { $ }      |              |
         // +-------+----------------------+------------+--------------+
 
         #[rustfmt::skip]
@@ -1701,6 +2628,18 @@ O -> ε
                 State::new(
                     0,
                     [
+                        // P' -> . P $ | { $ }
+                        Item::new(
+                            Rule::new(
+                                "P'",
+                                [
+                                    Symbol::from("P").into(),
+                                    Token::Eof.into(),
+                                ]
+                            ),
+                            0,
+                            [Token::Eof],
+                        ),
                         // P -> . O 'x' O 'z' | { $ }
                         Item::new(
                             Rule::new(
@@ -1739,16 +2678,38 @@ O -> ε
                         ),
                     ],
                     [
-                        // O -> 1
-                        (Symbol::from("O").into(), 1),
-                        // 'y' -> 2
-                        (ConstantToken::from("y").into(), 2),
+                        // P -> 1
+                        (Symbol::from("P").into(), 1),
+                        // O -> 2
+                        (Symbol::from("O").into(), 2),
+                        // 'y' -> 3
+                        (ConstantToken::from("y").into(), 3),
                     ],
                 ),
 
                 // State 1
                 State::new(
                     1,
+                    [
+                        // P' -> P . $ | { $ }
+                        Item::new(
+                            Rule::new(
+                                "P'",
+                                [
+                                    Symbol::from("P").into(),
+                                    Token::Eof.into(),
+                                ]
+                            ),
+                            1,
+                            [Token::Eof],
+                        ),
+                    ],
+                    [],
+                ),
+
+                // State 2
+                State::new(
+                    2,
                     [
                         // P -> O . 'x' O 'z' | { $ }
                         Item::new(
@@ -1766,14 +2727,14 @@ O -> ε
                         ),
                     ],
                     [
-                        // 'x' -> 2
-                        (ConstantToken::from("x").into(), 3),
+                        // 'x' -> 4
+                        (ConstantToken::from("x").into(), 4),
                     ],
                 ),
 
-                // State 2
+                // State 3
                 State::new(
-                    2,
+                    3,
                     [
                         // O -> 'y' . | { 'x' }
                         Item::new(
@@ -1790,9 +2751,9 @@ O -> ε
                     [],
                 ),
 
-                // State 3
+                // State 4
                 State::new(
-                    3,
+                    4,
                     [
                         // P -> O 'x' . O 'z' | { $ }
                         Item::new(
@@ -1832,16 +2793,16 @@ O -> ε
                         ),
                     ],
                     [
-                        // O -> 4
-                        (Symbol::from("O").into(), 4),
-                        // 'y' -> 5
-                        (ConstantToken::from("y").into(), 5),
+                        // O -> 5
+                        (Symbol::from("O").into(), 5),
+                        // 'y' -> 6
+                        (ConstantToken::from("y").into(), 6),
                     ],
                 ),
 
-                // State 4
+                // State 5
                 State::new(
-                    4,
+                    5,
                     [
                         // P -> O 'x' O . 'z' | { $ }
                         Item::new(
@@ -1859,14 +2820,14 @@ O -> ε
                         ),
                     ],
                     [
-                        // 'z' -> 6
-                        (ConstantToken::from("z").into(), 6),
+                        // 'z' -> 7
+                        (ConstantToken::from("z").into(), 7),
                     ],
                 ),
 
-                // State 5
+                // State 6
                 State::new(
-                    5,
+                    6,
                     [
                         // O -> 'y' . | { 'z' }
                         Item::new(
@@ -1883,9 +2844,9 @@ O -> ε
                     [],
                 ),
 
-                // State 6
+                // State 7
                 State::new(
-                    6,
+                    7,
                     [
                         // P -> O 'x' O 'z' . | { $ }
                         Item::new(
@@ -1915,19 +2876,21 @@ O -> ε
         // | State | ------------------------------ |
         // |       |    'x'    'z'    'y'     $     |
         // +-------+--------------------------------+
-        // | 0     |    r3      -     s2      -     |
+        // | 0     |    r3      -     s3      -     |
         // +-------+--------------------------------+
-        // | 1     |    s3      -      -      -     |
+        // | 1     |     -      -      -     acc    |
         // +-------+--------------------------------+
-        // | 2     |    r2      -      -      -     |
+        // | 2     |    s4      -      -      -     |
         // +-------+--------------------------------+
-        // | 3     |     -     r3     s5      -     |
+        // | 3     |    r2      -      -      -     |
         // +-------+--------------------------------+
-        // | 4     |     -     s6      -      -     |
+        // | 4     |     -     r3     s6      -     |
         // +-------+--------------------------------+
-        // | 5     |     -     r2      -      -     |
+        // | 5     |     -     s7      -      -     |
         // +-------+--------------------------------+
-        // | 6     |     -      -      -     a1     |
+        // | 6     |     -     r2      -      -     |
+        // +-------+--------------------------------+
+        // | 7     |     -      -      -     r1     |
         // +-------+--------------------------------+
 
         #[rustfmt::skip]
@@ -1938,57 +2901,66 @@ O -> ε
                 IndexMap::<Token, IndexSet<Action>>::from_iter(
                     [
                         (
-                            ConstantToken::from("x").into(),
-                            IndexSet::from([Action::Reduce { rule_index: 2 }]),
+                            ConstantToken::from("y").into(),
+                            IndexSet::from([Action::Shift { next_state: 3 }]),
                         ),
                         (
-                            ConstantToken::from("y").into(),
-                            IndexSet::from([Action::Shift { next_state: 2 }]),
+                            ConstantToken::from("x").into(),
+                            IndexSet::from([Action::Reduce { rule_index: 2 }]),
                         ),
                     ],
                 ),
                 // State 1
-                IndexMap::<Token, IndexSet<Action>>::from_iter(
+                IndexMap::<Token, IndexSet<Action>>::from(
                     [
                         (
-                            ConstantToken::from("x").into(),
-                            IndexSet::from([Action::Shift { next_state: 3 }]),
+                            Token::Eof,
+                            IndexSet::from([Action::Accept]),
                         ),
                     ],
                 ),
                 // State 2
-                IndexMap::<Token, IndexSet<Action>>::from_iter(
+                IndexMap::<Token, IndexSet<Action>>::from(
                     [
                         (
                             ConstantToken::from("x").into(),
-                            IndexSet::from([Action::Reduce { rule_index: 1 }]),
+                            IndexSet::from([Action::Shift { next_state: 4 }]),
                         ),
                     ],
                 ),
                 // State 3
-                IndexMap::<Token, IndexSet<Action>>::from_iter(
+                IndexMap::<Token, IndexSet<Action>>::from(
                     [
                         (
-                            ConstantToken::from("z").into(),
-                            IndexSet::from([Action::Reduce { rule_index: 2 }]),
+                            ConstantToken::from("x").into(),
+                            IndexSet::from([Action::Reduce { rule_index: 1 }]),
                         ),
+                    ],
+                ),
+                // State 4
+                IndexMap::<Token, IndexSet<Action>>::from(
+                    [
                         (
                             ConstantToken::from("y").into(),
-                            IndexSet::from([Action::Shift { next_state: 5 }]),
+                            IndexSet::from([Action::Shift { next_state: 6 }]),
+                        ),
+                        (
+                            ConstantToken::from("z").into(),
+                            IndexSet::from([Action::Reduce { rule_index: 2 }]),
                         ),
                     ],
                 ),
-                // State 4
-                IndexMap::<Token, IndexSet<Action>>::from_iter(
+                // State 5
+                IndexMap::<Token, IndexSet<Action>>::from(
                     [
                         (
                             ConstantToken::from("z").into(),
-                            IndexSet::from([Action::Shift { next_state: 6 }]),
+                            IndexSet::from([Action::Shift { next_state: 7 }]),
                         ),
                     ],
                 ),
-                // State 5
-                IndexMap::<Token, IndexSet<Action>>::from_iter(
+                // State 6
+                IndexMap::<Token, IndexSet<Action>>::from(
                     [
                         (
                             ConstantToken::from("z").into(),
@@ -1996,13 +2968,13 @@ O -> ε
                         ),
                     ],
                 ),
-                // State 6
-                IndexMap::<Token, IndexSet<Action>>::from_iter(
+                // State 7
+                IndexMap::<Token, IndexSet<Action>>::from(
                     [
                         (
                             Token::Eof,
-                            IndexSet::from([Action::Accept { rule_index: 0 }]),
-                        )
+                            IndexSet::from([Action::Reduce { rule_index: 0 }]),
+                        ),
                     ],
                 ),
             ]
@@ -2016,20 +2988,22 @@ O -> ε
         // | State | ------------ |
         // |       |    P    O    |
         // +-------+--------------+
-        // | 0     |    -    1    |
+        // | 0     |    1    2    |
         // +-------+--------------+
         // | 1     |    -    -    |
         // +-------+--------------+
         // | 2     |    -    -    |
         // +-------+--------------+
-        // | 3     |    -    4    |
+        // | 3     |    -    -    |
         // +-------+--------------+
-        // | 4     |    -    -    |
+        // | 4     |    -    5    |
         // +-------+--------------+
         // | 5     |    -    -    |
         // +-------+--------------+
         // | 6     |    -    -    |
         // +-------+--------------+
+        // | 7     |    -    -    |
+        // +-------+--------------+
 
         #[rustfmt::skip]
         assert_eq!(
@@ -2038,7 +3012,8 @@ O -> ε
                 // State 0
                 IndexMap::<Symbol, usize>::from_iter(
                     [
-                        (Symbol::from("O"), 1),
+                        (Symbol::from("P"), 1),
+                        (Symbol::from("O"), 2),
                     ],
                 ),
                 // State 1
@@ -2054,12 +3029,12 @@ O -> ε
                 // State 3
                 IndexMap::<Symbol, usize>::from_iter(
                     [
-                        (Symbol::from("O"), 4),
                     ],
                 ),
                 // State 4
                 IndexMap::<Symbol, usize>::from_iter(
                     [
+                        (Symbol::from("O"), 5),
                     ],
                 ),
                 // State 5
@@ -2072,7 +3047,146 @@ O -> ε
                     [
                     ],
                 ),
+                // State 7
+                IndexMap::<Symbol, usize>::from_iter(
+                    [
+                    ],
+                ),
+            ]
+        );
+    }
+}
+
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_resolving_conflicts_using_precedences_for_calculator_precedence_grammar() {
+    let grammar = Grammar::parse(common::grammars::CALCULATOR_PRECEDENCE).unwrap();
+    let parser = Parser::lalr(grammar).unwrap();
+
+    let resolved_conflicts = parser.parsing_tables().resolved_conflicts();
+    assert!(!resolved_conflicts.is_empty());
+
+    for conflict in resolved_conflicts {
+        let actions = parser.action_table()[conflict.state()].get(conflict.token());
+        match conflict.chosen_action() {
+            Some(action) => assert_eq!(actions.unwrap().iter().collect::<Vec<_>>(), [&action]),
+            None => assert!(actions.is_none()),
+        }
+    }
+
+    let find = |rule_index: usize, token: &str| {
+        resolved_conflicts
+            .iter()
+            .find(|conflict| {
+                conflict.reduce() == Action::Reduce { rule_index }
+                    && *conflict.token() == Token::Constant(ConstantToken::from(token))
+            })
+            .unwrap()
+            .chosen_action()
+    };
+
+    // Expr -> Expr '<' Expr . on '<' (non associative)
+    assert_eq!(find(0, "<"), None);
+    // Expr -> Expr '<' Expr . on '+' (higher precedence token)
+    assert!(matches!(find(0, "+"), Some(Action::Shift { .. })));
+
+    // Expr -> Expr '+' Expr . on '-' (left associative)
+    assert_eq!(find(1, "-"), Some(Action::Reduce { rule_index: 1 }));
+    // Expr -> Expr '+' Expr . on '*' (higher precedence token)
+    assert!(matches!(find(1, "*"), Some(Action::Shift { .. })));
+
+    // Expr -> Expr '*' Expr . on '+' (lower precedence token)
+    assert_eq!(find(3, "+"), Some(Action::Reduce { rule_index: 3 }));
+
+    // Expr -> Expr '^' Expr . on '^' (right associative)
+    assert!(matches!(find(5, "^"), Some(Action::Shift { .. })));
+
+    // Expr -> '-' Expr %prec UMINUS . on '^' (higher precedence rule)
+    assert_eq!(find(6, "^"), Some(Action::Reduce { rule_index: 6 }));
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_resolving_shift_reduce_reduce_conflicts_using_precedences() {
+    let grammar = Grammar::parse(
+        r#"
+S -> E '+' 'a'
+S -> F '+' 'b'
+S -> 'x' '+' 'c'
+
+E -> 'x' %prec LOW
+F -> 'x' %prec LOW
+
+%left LOW
+%left '+'
+        "#,
+    )
+    .unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    // Both reductions on '+' lose to the shift, so the shift is the only action left.
+    let resolved_conflicts = parser.parsing_tables().resolved_conflicts();
+    assert_eq!(
+        resolved_conflicts.iter().map(|conflict| conflict.reduce()).collect::<Vec<_>>(),
+        [Action::Reduce { rule_index: 3 }, Action::Reduce { rule_index: 4 }],
+    );
+    for conflict in resolved_conflicts {
+        assert_eq!(conflict.chosen_action(), Some(conflict.shift()));
+
+        let actions = parser.action_table()[conflict.state()].get(conflict.token());
+        assert_eq!(actions.unwrap().iter().collect::<Vec<_>>(), [&conflict.shift()]);
+    }
+
+    assert!(parser.parse(parser.tokenize("x + c").unwrap()).is_ok());
+    assert!(parser.parse(parser.tokenize("x + a").unwrap()).is_err());
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_creating_ll_parser_for_g9_grammar() {
+    let grammar = Grammar::parse(common::grammars::G9).unwrap();
+    let parser = LlParser::new(grammar).unwrap();
+
+    let ll_table = parser.ll_table();
+    {
+        // +--------+-----+-----+-----+-----+------+---+
+        // | Symbol | '+' | '*' | '(' | ')' | %int | $ |
+        // +--------+-----+-----+-----+-----+------+---+
+        // | P      |  -  |  -  |  1  |  -  |  1   | - |
+        // | E      |  -  |  -  |  2  |  -  |  2   | - |
+        // | Ep     |  3  |  -  |  -  |  4  |  -   | 4 |
+        // | T      |  -  |  -  |  5  |  -  |  5   | - |
+        // | Tp     |  7  |  6  |  -  |  7  |  -   | 7 |
+        // | F      |  -  |  -  |  8  |  -  |  9   | - |
+        // +--------+-----+-----+-----+-----+------+---+
+
+        let plus = Token::from(ConstantToken::from("+"));
+        let times = Token::from(ConstantToken::from("*"));
+        let open = Token::from(ConstantToken::from("("));
+        let close = Token::from(ConstantToken::from(")"));
+        let int = Token::from(RegexToken::from("int"));
+
+        let entries = |entries: &[(&Token, usize)]| {
+            entries
+                .iter()
+                .map(|(token, rule_index)| ((*token).clone(), IndexSet::from([*rule_index])))
+                .collect::<IndexMap<_, _>>()
+        };
+
+        #[rustfmt::skip]
+        assert_eq!(
+            *ll_table.deref(),
+            [
+                (Symbol::from("P"), entries(&[(&open, 0), (&int, 0)])),
+                (Symbol::from("E"), entries(&[(&open, 1), (&int, 1)])),
+                (Symbol::from("Ep"), entries(&[(&plus, 2), (&close, 3), (&Token::Eof, 3)])),
+                (Symbol::from("T"), entries(&[(&open, 4), (&int, 4)])),
+                (Symbol::from("Tp"), entries(&[(&times, 5), (&plus, 6), (&close, 6), (&Token::Eof, 6)])),
+                (Symbol::from("F"), entries(&[(&open, 7), (&int, 8)])),
             ]
+                .into_iter()
+                .collect::<IndexMap<_, _>>()
         );
     }
 }
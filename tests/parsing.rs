@@ -2,8 +2,30 @@ mod common;
 
 use dotlr::{
     Action,
+    Alternative,
+    Completion,
+    ConstantToken,
+    Edit,
+    Event,
+    ForestNode,
     Grammar,
+    Lexer,
+    LlAction,
+    LlParser,
     Parser,
+    ParserError,
+    ParsingError,
+    ParsingMethod,
+    RegexToken,
+    Rule,
+    SemanticActions,
+    Span,
+    Spanned,
+    Status,
+    Symbol,
+    TextEdit,
+    Token,
+    Tree,
 };
 
 #[cfg(target_family = "wasm")]
@@ -28,7 +50,10 @@ fn raising_correct_error_when_encountering_unexpected_token_during_parsing_calcu
     let tokens = parser.tokenize("1 + /").unwrap();
 
     let error = parser.parse(tokens).unwrap_err();
-    assert_eq!(error.to_string(), "unexpected token / at 1:5 (expected one of '(', %f)");
+    assert_eq!(
+        error.to_string(),
+        "unexpected token / at 1:5 (expected one of '(', %f), try to replace / with %f at 1:5"
+    );
 }
 
 #[test]
@@ -41,7 +66,8 @@ fn raising_correct_error_when_encountering_unexpected_eof_during_parsing_calcula
     let error = parser.parse(tokens).unwrap_err();
     assert_eq!(
         error.to_string(),
-        "unexpected end of input at 1:7 (expected one of '^', '+', '-', '*', '/', ')')",
+        "unexpected end of input at 1:7 (expected one of '^', '+', '-', '*', '/', ')'), try to \
+         insert ')' at 1:7",
     );
 }
 
@@ -90,7 +116,9 @@ fn correctly_trace_parsing_of_calculator_grammar() {
         // +------+-----------------+------------------------------+--------------------+--------------------------------------+
         // | 14   | 0 1 32 35 28 31 | Expr '+' Factor '*' Exponent |                  $ | Reduce Factor -> Factor '*' Exponent |
         // +------+-----------------+------------------------------+--------------------+--------------------------------------+
-        // | 15   | 0 1 32 35       | Expr '+' Factor              |                  $ | Accept Expr -> Expr '+' Factor       |
+        // | 15   | 0 1 32 35       | Expr '+' Factor              |                  $ | Reduce Expr -> Expr '+' Factor       |
+        // +------+-----------------+------------------------------+--------------------+--------------------------------------+
+        // | 16   | 0 1             | Expr                         |                  $ | Accept Expr' -> Expr $               |
         // +------+-----------------+------------------------------+--------------------+--------------------------------------+
         assert_eq!(
             parse_trace.steps().iter().map(|step| *step.action_taken()).collect::<Vec<_>>(),
@@ -125,7 +153,8 @@ fn correctly_trace_parsing_of_calculator_grammar() {
                 Action::Reduce { rule_index: 8 },
                 Action::Reduce { rule_index: 7 },
                 Action::Reduce { rule_index: 4 },
-                Action::Accept { rule_index: 0 }
+                Action::Reduce { rule_index: 0 },
+                Action::Accept
             ],
         );
     }
@@ -172,6 +201,73 @@ Expr
     }
 }
 
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_parsing_calculator_precedence_grammar() {
+    let grammar = Grammar::parse(common::grammars::CALCULATOR_PRECEDENCE).unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let expression = "(1 - 2 - -3 * 4 ^ 5 ^ 6 < 7)";
+    let tokens = parser.tokenize(expression).unwrap();
+
+    let parse_tree = parser.parse(tokens).unwrap();
+    assert_eq!(
+        parse_tree.to_string().trim(),
+        r#"
+
+Expr
+├─ (
+├─ Expr
+│  ├─ Expr
+│  │  ├─ Expr
+│  │  │  ├─ Expr
+│  │  │  │  └─ 1
+│  │  │  ├─ -
+│  │  │  └─ Expr
+│  │  │     └─ 2
+│  │  ├─ -
+│  │  └─ Expr
+│  │     ├─ Expr
+│  │     │  ├─ -
+│  │     │  └─ Expr
+│  │     │     └─ 3
+│  │     ├─ *
+│  │     └─ Expr
+│  │        ├─ Expr
+│  │        │  └─ 4
+│  │        ├─ ^
+│  │        └─ Expr
+│  │           ├─ Expr
+│  │           │  └─ 5
+│  │           ├─ ^
+│  │           └─ Expr
+│  │              └─ 6
+│  ├─ <
+│  └─ Expr
+│     └─ 7
+└─ )
+
+            "#
+        .trim(),
+    );
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn raising_correct_error_when_chaining_non_associative_tokens_during_parsing_calculator_precedence_grammar()
+ {
+    let grammar = Grammar::parse(common::grammars::CALCULATOR_PRECEDENCE).unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+    let tokens = parser.tokenize("1 < 2 < 3").unwrap();
+
+    let error = parser.parse(tokens).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "unexpected token < at 1:7 (expected one of $, '+', '-', '*', '/', '^'), try to \
+         replace < with '+' at 1:7 or replace < with '-' at 1:7 or replace < with '*' at 1:7",
+    );
+}
+
 #[test]
 #[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
 fn correctly_parsing_conditional_grammar() {
@@ -310,6 +406,165 @@ Json
     );
 }
 
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_parsing_calculator_grammar_with_every_method() {
+    let expression = "1 + 2 * 3 / (4 ^ 5)";
+
+    let grammar = Grammar::parse(common::grammars::CALCULATOR).unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+    let expected_tree = parser.parse(parser.tokenize(expression).unwrap()).unwrap().to_string();
+
+    for method in [ParsingMethod::Slr, ParsingMethod::Lalr] {
+        let grammar = Grammar::parse(common::grammars::CALCULATOR).unwrap();
+        let parser = Parser::with_method(grammar, method).unwrap();
+
+        let tokens = parser.tokenize(expression).unwrap();
+        let parse_tree = parser.parse(tokens).unwrap();
+        assert_eq!(parse_tree.to_string(), expected_tree, "{} parser", method);
+    }
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_parsing_grammar_with_nullable_rest_of_pattern_with_lr_and_lalr() {
+    let definition = "S -> Y Z\nY -> 'y'\nZ -> 'z'\nZ -> ''";
+    for method in [ParsingMethod::Lr, ParsingMethod::Lalr] {
+        for expression in ["y", "y z"] {
+            let grammar = Grammar::parse(definition).unwrap();
+            let parser = Parser::with_method(grammar, method).unwrap();
+
+            let tokens = parser.tokenize(expression).unwrap();
+            assert!(parser.parse(tokens).is_ok(), "{} parser on {:?}", method, expression);
+        }
+    }
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_parsing_recursive_start_grammar_with_lr0() {
+    let grammar = Grammar::parse(common::grammars::RECURSIVE_START).unwrap();
+    let parser = Parser::lr0(grammar).unwrap();
+
+    let tokens = parser.tokenize("x x y").unwrap();
+    let parse_tree = parser.parse(tokens).unwrap();
+    assert_eq!(
+        parse_tree.to_string().trim(),
+        r#"
+
+S
+├─ x
+└─ S
+   ├─ x
+   └─ S
+      └─ y
+
+        "#
+        .trim()
+    );
+
+    // LR(0) parsers reduce without looking at the next token, so the error is detected after reductions.
+    let tokens = parser.tokenize("x y x").unwrap();
+    let error = parser.parse(tokens).unwrap_err();
+    assert_eq!(error.to_string(), "unexpected token x at 1:5 (expected $), try to delete x at 1:5");
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_parsing_json_ebnf_grammar() {
+    let grammar = Grammar::parse(common::grammars::JSON_EBNF).unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let expression = r#"[1, [], "x"]"#;
+    let tokens = parser.tokenize(expression).unwrap();
+
+    let parse_tree = parser.parse(tokens).unwrap();
+    assert_eq!(
+        parse_tree.to_string().trim(),
+        r#"
+
+Json
+└─ Value
+   └─ Array
+      ├─ [
+      ├─ (Value (',' Value)*)?
+      │  ├─ Value
+      │  │  └─ Number
+      │  │     └─ 1
+      │  └─ (',' Value)*
+      │     ├─ ,
+      │     ├─ Value
+      │     │  └─ Array
+      │     │     ├─ [
+      │     │     ├─ (Value (',' Value)*)?
+      │     │     └─ ]
+      │     ├─ ,
+      │     └─ Value
+      │        └─ String
+      │           └─ "x"
+      └─ ]
+
+            "#
+        .trim(),
+    );
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_parsing_statements_ebnf_grammar() {
+    let grammar = Grammar::parse(common::grammars::STATEMENTS_EBNF).unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let expression = "let x = (1, (2, y), z); let y; x;";
+    let tokens = parser.tokenize(expression).unwrap();
+
+    let parse_tree = parser.parse(tokens).unwrap();
+    assert_eq!(
+        parse_tree.to_string().trim(),
+        r#"
+
+Program
+└─ Statement+
+   ├─ Statement
+   │  ├─ let
+   │  ├─ x
+   │  ├─ ('=' Expression)?
+   │  │  ├─ =
+   │  │  └─ Expression
+   │  │     ├─ (
+   │  │     ├─ Expression
+   │  │     │  └─ 1
+   │  │     ├─ (',' Expression)*
+   │  │     │  ├─ ,
+   │  │     │  ├─ Expression
+   │  │     │  │  ├─ (
+   │  │     │  │  ├─ Expression
+   │  │     │  │  │  └─ 2
+   │  │     │  │  ├─ (',' Expression)*
+   │  │     │  │  │  ├─ ,
+   │  │     │  │  │  └─ Expression
+   │  │     │  │  │     └─ y
+   │  │     │  │  └─ )
+   │  │     │  ├─ ,
+   │  │     │  └─ Expression
+   │  │     │     └─ z
+   │  │     └─ )
+   │  └─ ;
+   ├─ Statement
+   │  ├─ let
+   │  ├─ y
+   │  ├─ ('=' Expression)?
+   │  └─ ;
+   └─ Statement
+      ├─ Expression
+      │  └─ x
+      └─ ;
+
+            "#
+        .trim(),
+    );
+}
+
 #[test]
 #[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
 fn correctly_trace_parsing_of_optional_grammar() {
@@ -327,26 +582,29 @@ fn correctly_trace_parsing_of_optional_grammar() {
             // +------+-------------+--------------+-----------------+-----------------------------+
             // | 0    | 0           |              |   'x' 'y' 'z' $ | Reduce 3 (O -> ε)           |
             // +------+-------------+--------------+-----------------+-----------------------------+
-            // | 1    | 0 1         | O            |   'x' 'y' 'z' $ | Shift 3                     |
+            // | 1    | 0 2         | O            |   'x' 'y' 'z' $ | Shift 4                     |
+            // +------+-------------+--------------+-----------------+-----------------------------+
+            // | 2    | 0 2 4       | O 'x'        |       'y' 'z' $ | Shift 6                     |
             // +------+-------------+--------------+-----------------+-----------------------------+
-            // | 2    | 0 1 3       | O 'x'        |       'y' 'z' $ | Shift 5                     |
+            // | 3    | 0 2 4 6     | O 'x' 'y'    |           'z' $ | Reduce 2 (O -> 'y')         |
             // +------+-------------+--------------+-----------------+-----------------------------+
-            // | 3    | 0 1 3 5     | O 'x' 'y'    |           'z' $ | Reduce 2 (O -> 'y')         |
+            // | 4    | 0 2 4 5     | O 'x' O      |           'z' $ | Shift 7                     |
             // +------+-------------+--------------+-----------------+-----------------------------+
-            // | 4    | 0 1 3 4     | O 'x' O      |           'z' $ | Shift 6                     |
+            // | 5    | 0 2 4 5 7   | O 'x' O 'z'  |               $ | Reduce 1 (P -> O 'x' O 'z') |
             // +------+-------------+--------------+-----------------+-----------------------------+
-            // | 5    | 0 1 3 4 6   | O 'x' O 'z'  |               $ | Accept 1 (P -> O 'x' O 'z') |
+            // | 6    | 0 1         | P            |               $ | Accept (P' -> P $)          |
             // +------+-------------+--------------+-----------------+-----------------------------+
 
             assert_eq!(
                 parse_trace.steps().iter().map(|step| *step.action_taken()).collect::<Vec<_>>(),
                 [
                     Action::Reduce { rule_index: 2 },
-                    Action::Shift { next_state: 3 },
-                    Action::Shift { next_state: 5 },
-                    Action::Reduce { rule_index: 1 },
+                    Action::Shift { next_state: 4 },
                     Action::Shift { next_state: 6 },
-                    Action::Accept { rule_index: 0 }
+                    Action::Reduce { rule_index: 1 },
+                    Action::Shift { next_state: 7 },
+                    Action::Reduce { rule_index: 0 },
+                    Action::Accept
                 ],
             );
         }
@@ -378,23 +636,26 @@ P
             // +------+-------------+--------------+-----------------+-----------------------------+
             // | 0    | 0           |              |       'x' 'z' $ | Reduce 3 (O -> ε)           |
             // +------+-------------+--------------+-----------------+-----------------------------+
-            // | 1    | 0 1         | O            |       'x' 'z' $ | Shift 3                     |
+            // | 1    | 0 2         | O            |       'x' 'z' $ | Shift 4                     |
+            // +------+-------------+--------------+-----------------+-----------------------------+
+            // | 2    | 0 2 4       | O 'x'        |           'z' $ | Reduce 3 (O -> ε)           |
             // +------+-------------+--------------+-----------------+-----------------------------+
-            // | 2    | 0 1 3       | O 'x'        |           'z' $ | Reduce 3 (O -> ε)           |
+            // | 3    | 0 2 4 5     | O 'x' O      |           'z' $ | Shift 7                     |
             // +------+-------------+--------------+-----------------+-----------------------------+
-            // | 3    | 0 1 3 4     | O 'x' O      |           'z' $ | Shift 6                     |
+            // | 4    | 0 2 4 5 7   | O 'x' O 'z'  |               $ | Reduce 1 (P -> O 'x' O 'z') |
             // +------+-------------+--------------+-----------------+-----------------------------+
-            // | 4    | 0 1 3 4 6   | O 'x' O 'z'  |               $ | Accept 1 (P -> O 'x' O 'z') |
+            // | 5    | 0 1         | P            |               $ | Accept (P' -> P $)          |
             // +------+-------------+--------------+-----------------+-----------------------------+
 
             assert_eq!(
                 parse_trace.steps().iter().map(|step| *step.action_taken()).collect::<Vec<_>>(),
                 [
                     Action::Reduce { rule_index: 2 },
-                    Action::Shift { next_state: 3 },
+                    Action::Shift { next_state: 4 },
                     Action::Reduce { rule_index: 2 },
-                    Action::Shift { next_state: 6 },
-                    Action::Accept { rule_index: 0 }
+                    Action::Shift { next_state: 7 },
+                    Action::Reduce { rule_index: 0 },
+                    Action::Accept
                 ],
             );
         }
@@ -423,31 +684,34 @@ P
             // +------+-------------+--------------+-------------------+-----------------------------+
             // | Step | State Stack | Symbol Stack |  Remaining Input  |        Action Taken         |
             // +------+-------------+--------------+-------------------+-----------------------------+
-            // | 0    | 0           |              | 'y' 'x' 'y' 'z' $ | Shift 2                     |
+            // | 0    | 0           |              | 'y' 'x' 'y' 'z' $ | Shift 3                     |
             // +------+-------------+--------------+-------------------+-----------------------------+
-            // | 1    | 0 2         | 'y'          |     'x' 'y' 'z' $ | Reduce 2 (O -> 'y')         |
+            // | 1    | 0 3         | 'y'          |     'x' 'y' 'z' $ | Reduce 2 (O -> 'y')         |
             // +------+-------------+--------------+-------------------+-----------------------------+
-            // | 2    | 0 1         | O            |     'x' 'y' 'z' $ | Shift 3                     |
+            // | 2    | 0 2         | O            |     'x' 'y' 'z' $ | Shift 4                     |
             // +------+-------------+--------------+-------------------+-----------------------------+
-            // | 3    | 0 1 3       | O 'x'        |         'y' 'z' $ | Shift 5                     |
+            // | 3    | 0 2 4       | O 'x'        |         'y' 'z' $ | Shift 6                     |
             // +------+-------------+--------------+-------------------+-----------------------------+
-            // | 4    | 0 1 3 5     | O 'x' 'y'    |             'z' $ | Reduce 2 (O -> 'y')         |
+            // | 4    | 0 2 4 6     | O 'x' 'y'    |             'z' $ | Reduce 2 (O -> 'y')         |
             // +------+-------------+--------------+-------------------+-----------------------------+
-            // | 5    | 0 1 3 4     | O 'x' O      |             'z' $ | Shift 6                     |
+            // | 5    | 0 2 4 5     | O 'x' O      |             'z' $ | Shift 7                     |
             // +------+-------------+--------------+-------------------+-----------------------------+
-            // | 6    | 0 1 3 4 6   | O 'x' O 'z'  |                 $ | Accept 1 (P -> O 'x' O 'z') |
+            // | 6    | 0 2 4 5 7   | O 'x' O 'z'  |                 $ | Reduce 1 (P -> O 'x' O 'z') |
+            // +------+-------------+--------------+-------------------+-----------------------------+
+            // | 7    | 0 1         | P            |                 $ | Accept (P' -> P $)          |
             // +------+-------------+--------------+-------------------+-----------------------------+
 
             assert_eq!(
                 parse_trace.steps().iter().map(|step| *step.action_taken()).collect::<Vec<_>>(),
                 [
-                    Action::Shift { next_state: 2 },
-                    Action::Reduce { rule_index: 1 },
                     Action::Shift { next_state: 3 },
-                    Action::Shift { next_state: 5 },
                     Action::Reduce { rule_index: 1 },
+                    Action::Shift { next_state: 4 },
                     Action::Shift { next_state: 6 },
-                    Action::Accept { rule_index: 0 }
+                    Action::Reduce { rule_index: 1 },
+                    Action::Shift { next_state: 7 },
+                    Action::Reduce { rule_index: 0 },
+                    Action::Accept
                 ],
             );
         }
@@ -470,3 +734,1727 @@ P
         }
     }
 }
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_trace_parsing_of_recursive_start_grammar() {
+    let grammar = Grammar::parse(common::grammars::RECURSIVE_START).unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let expression = "x x y";
+    let tokens = parser.tokenize(expression).unwrap();
+
+    let (parse_trace, parse_tree) = parser.trace(tokens).unwrap();
+    {
+        // +------+-------------+--------------+-----------------+-----------------------+
+        // | Step | State Stack | Symbol Stack | Remaining Input |     Action Taken      |
+        // +------+-------------+--------------+-----------------+-----------------------+
+        // | 0    | 0           |              |   'x' 'x' 'y' $ | Shift 2               |
+        // +------+-------------+--------------+-----------------+-----------------------+
+        // | 1    | 0 2         | 'x'          |       'x' 'y' $ | Shift 2               |
+        // +------+-------------+--------------+-----------------+-----------------------+
+        // | 2    | 0 2 2       | 'x' 'x'      |           'y' $ | Shift 3               |
+        // +------+-------------+--------------+-----------------+-----------------------+
+        // | 3    | 0 2 2 3     | 'x' 'x' 'y'  |               $ | Reduce 2 (S -> 'y')   |
+        // +------+-------------+--------------+-----------------+-----------------------+
+        // | 4    | 0 2 2 4     | 'x' 'x' S    |               $ | Reduce 1 (S -> 'x' S) |
+        // +------+-------------+--------------+-----------------+-----------------------+
+        // | 5    | 0 2 4       | 'x' S        |               $ | Reduce 1 (S -> 'x' S) |
+        // +------+-------------+--------------+-----------------+-----------------------+
+        // | 6    | 0 1         | S            |               $ | Accept (S' -> S $)    |
+        // +------+-------------+--------------+-----------------+-----------------------+
+        assert_eq!(
+            parse_trace.steps().iter().map(|step| *step.action_taken()).collect::<Vec<_>>(),
+            [
+                Action::Shift { next_state: 2 },
+                Action::Shift { next_state: 2 },
+                Action::Shift { next_state: 3 },
+                Action::Reduce { rule_index: 1 },
+                Action::Reduce { rule_index: 0 },
+                Action::Reduce { rule_index: 0 },
+                Action::Accept
+            ],
+        );
+    }
+    {
+        assert_eq!(
+            parse_tree.to_string().trim(),
+            r#"
+
+S
+├─ x
+└─ S
+   ├─ x
+   └─ S
+      └─ y
+
+            "#
+            .trim(),
+        );
+    }
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_trace_parsing_of_g9_grammar_with_ll() {
+    let grammar = Grammar::parse(common::grammars::G9).unwrap();
+    let parser = LlParser::new(grammar).unwrap();
+
+    let expression = "1 + 2 * 3";
+    let tokens = parser.tokenize(expression).unwrap();
+
+    let (parse_trace, parse_tree) = parser.trace(tokens).unwrap();
+    {
+        // +------+---------------+--------------------------+---------------------------+
+        // | Step |     Stack     |     Remaining Input      |       Action Taken        |
+        // +------+---------------+--------------------------+---------------------------+
+        // | 0    | $ P           | %int '+' %int '*' %int $ | Expand 1 (P -> E)         |
+        // | 1    | $ E           | %int '+' %int '*' %int $ | Expand 2 (E -> T Ep)      |
+        // | 2    | $ Ep T        | %int '+' %int '*' %int $ | Expand 5 (T -> F Tp)      |
+        // | 3    | $ Ep Tp F     | %int '+' %int '*' %int $ | Expand 9 (F -> %int)      |
+        // | 4    | $ Ep Tp %int  | %int '+' %int '*' %int $ | Match %int                |
+        // | 5    | $ Ep Tp       |      '+' %int '*' %int $ | Expand 7 (Tp -> ε)        |
+        // | 6    | $ Ep          |      '+' %int '*' %int $ | Expand 3 (Ep -> '+' T Ep) |
+        // | 7    | $ Ep T '+'    |      '+' %int '*' %int $ | Match '+'                 |
+        // | 8    | $ Ep T        |          %int '*' %int $ | Expand 5 (T -> F Tp)      |
+        // | 9    | $ Ep Tp F     |          %int '*' %int $ | Expand 9 (F -> %int)      |
+        // | 10   | $ Ep Tp %int  |          %int '*' %int $ | Match %int                |
+        // | 11   | $ Ep Tp       |               '*' %int $ | Expand 6 (Tp -> '*' F Tp) |
+        // | 12   | $ Ep Tp F '*' |               '*' %int $ | Match '*'                 |
+        // | 13   | $ Ep Tp F     |                   %int $ | Expand 9 (F -> %int)      |
+        // | 14   | $ Ep Tp %int  |                   %int $ | Match %int                |
+        // | 15   | $ Ep Tp       |                        $ | Expand 7 (Tp -> ε)        |
+        // | 16   | $ Ep          |                        $ | Expand 4 (Ep -> ε)        |
+        // | 17   | $             |                        $ | Accept                    |
+        // +------+---------------+--------------------------+---------------------------+
+        let int = Token::from(RegexToken::from("int"));
+        assert_eq!(
+            parse_trace.steps().iter().map(|step| step.action_taken().clone()).collect::<Vec<_>>(),
+            [
+                LlAction::Expand { rule_index: 0 },
+                LlAction::Expand { rule_index: 1 },
+                LlAction::Expand { rule_index: 4 },
+                LlAction::Expand { rule_index: 8 },
+                LlAction::Match { token: int.clone() },
+                LlAction::Expand { rule_index: 6 },
+                LlAction::Expand { rule_index: 2 },
+                LlAction::Match { token: ConstantToken::from("+").into() },
+                LlAction::Expand { rule_index: 4 },
+                LlAction::Expand { rule_index: 8 },
+                LlAction::Match { token: int.clone() },
+                LlAction::Expand { rule_index: 5 },
+                LlAction::Match { token: ConstantToken::from("*").into() },
+                LlAction::Expand { rule_index: 8 },
+                LlAction::Match { token: int },
+                LlAction::Expand { rule_index: 6 },
+                LlAction::Expand { rule_index: 3 },
+                LlAction::Accept,
+            ]
+        );
+    }
+
+    let grammar = Grammar::parse(common::grammars::G9).unwrap();
+    let lr_parser = Parser::lr(grammar).unwrap();
+    let lr_parse_tree = lr_parser.parse(lr_parser.tokenize(expression).unwrap()).unwrap();
+    assert_eq!(parse_tree.to_string(), lr_parse_tree.to_string());
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn raising_correct_error_when_encountering_unexpected_token_during_parsing_g9_grammar_with_ll() {
+    let grammar = Grammar::parse(common::grammars::G9).unwrap();
+    let parser = LlParser::new(grammar).unwrap();
+
+    let tokens = parser.tokenize("1 + )").unwrap();
+    let error = parser.parse(tokens).unwrap_err();
+    assert_eq!(error.to_string(), "unexpected token ) at 1:5 (expected one of '(', %int)");
+
+    let tokens = parser.tokenize("(1 + 2").unwrap();
+    let error = parser.parse(tokens).unwrap_err();
+    assert_eq!(error.to_string(), "unexpected end of input at 1:7 (expected ')')");
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_parsing_grammar_with_multiple_empty_tokens_in_a_pattern_with_ll() {
+    let grammar = Grammar::parse("S -> A 'x'\nA -> '' ''\nA -> 'a'").unwrap();
+    let parser = LlParser::new(grammar).unwrap();
+
+    let tokens = parser.tokenize("x").unwrap();
+    let parse_tree = parser.parse(tokens).unwrap();
+
+    let grammar = Grammar::parse("S -> A 'x'\nA -> ''\nA -> 'a'").unwrap();
+    let lr_parser = Parser::lr(grammar).unwrap();
+    let lr_parse_tree = lr_parser.parse(lr_parser.tokenize("x").unwrap()).unwrap();
+    assert_eq!(parse_tree.to_string(), lr_parse_tree.to_string());
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_parsing_shift_reduce_conflict_grammar_with_glr() {
+    let grammar = Grammar::parse(common::grammars::SHIFT_REDUCE_CONFLICT).unwrap();
+    let parser = match Parser::lr(grammar) {
+        Err(ParserError::Conflicts { parser, .. }) => parser,
+        result => panic!("unexpected parser result {:?}", result),
+    };
+
+    let tokens = parser.tokenize("1 + 0 + 1").unwrap();
+    let parse_forest = parser.parse_forest(tokens).unwrap();
+    assert!(parse_forest.is_ambiguous());
+
+    // Both `(1 + 0) + 1` and `1 + (0 + 1)` share the same root node.
+    match &parse_forest.nodes()[parse_forest.root()] {
+        ForestNode::NonTerminal { symbol, start, end, alternatives } => {
+            assert_eq!(*symbol, Symbol::from("E"));
+            assert_eq!((*start, *end), (0, 5));
+            assert_eq!(
+                *alternatives,
+                [Alternative::new(0, [5, 6, 8]), Alternative::new(0, [1, 2, 9])]
+            );
+        },
+        node => panic!("unexpected root node {:?}", node),
+    }
+
+    let parse_trees = parse_forest.trees(10);
+    assert_eq!(parse_trees.len(), 2);
+    assert_eq!(
+        parse_trees[0].to_string(),
+        r#"
+E
+├─ E
+│  ├─ E
+│  │  └─ 1
+│  ├─ +
+│  └─ E
+│     └─ 0
+├─ +
+└─ E
+   └─ 1
+        "#
+        .trim()
+    );
+    assert_eq!(
+        parse_trees[1].to_string(),
+        r#"
+E
+├─ E
+│  └─ 1
+├─ +
+└─ E
+   ├─ E
+   │  └─ 0
+   ├─ +
+   └─ E
+      └─ 1
+        "#
+        .trim()
+    );
+
+    assert_eq!(parse_forest.trees(1).len(), 1);
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_parsing_ambiguous_empty_symbols_with_glr() {
+    let grammar = Grammar::parse(
+        r#"
+S -> A A 'x'
+
+A -> B
+A -> ''
+
+B -> ''
+        "#,
+    )
+    .unwrap();
+    let parser = match Parser::lr(grammar) {
+        Err(ParserError::Conflicts { parser, .. }) => parser,
+        result => panic!("unexpected parser result {:?}", result),
+    };
+
+    let tokens = parser.tokenize("x").unwrap();
+    let parse_forest = parser.parse_forest(tokens).unwrap();
+
+    // Each `A` is either empty or an empty `B`.
+    assert!(parse_forest.is_ambiguous());
+    assert_eq!(parse_forest.trees(10).len(), 4);
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_parsing_conflict_free_grammars_with_glr() {
+    for (grammar, input) in [
+        (common::grammars::CALCULATOR, "1 + 2 * 3 / (4 ^ 5)"),
+        (common::grammars::G9, "(1 + 2) * 3"),
+        (common::grammars::OPTIONAL, "y x z"),
+        (common::grammars::JSON_EBNF, r#"{ "a": [1, 2, 3], "b": null }"#),
+    ] {
+        let grammar = Grammar::parse(grammar).unwrap();
+        let parser = Parser::lr(grammar).unwrap();
+
+        let parse_tree = parser.parse(parser.tokenize(input).unwrap()).unwrap();
+        let parse_forest = parser.parse_forest(parser.tokenize(input).unwrap()).unwrap();
+
+        assert!(!parse_forest.is_ambiguous());
+        let parse_trees = parse_forest.trees(10);
+        assert_eq!(parse_trees.len(), 1);
+        assert_eq!(parse_trees[0].to_string(), parse_tree.to_string());
+    }
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn raising_correct_error_when_encountering_unexpected_token_during_parsing_with_glr() {
+    let grammar = Grammar::parse(common::grammars::SHIFT_REDUCE_CONFLICT).unwrap();
+    let parser = match Parser::lr(grammar) {
+        Err(ParserError::Conflicts { parser, .. }) => parser,
+        result => panic!("unexpected parser result {:?}", result),
+    };
+
+    let tokens = parser.tokenize("1 + 0 1").unwrap();
+    let error = parser.parse_forest(tokens).unwrap_err();
+    assert_eq!(error.to_string(), "unexpected token 1 at 1:7 (expected one of $, '+')");
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_parsing_tokens_without_end_of_input_with_glr() {
+    let grammar = Grammar::parse(common::grammars::SHIFT_REDUCE_CONFLICT).unwrap();
+    let parser = match Parser::lr(grammar) {
+        Err(ParserError::Conflicts { parser, .. }) => parser,
+        result => panic!("unexpected parser result {:?}", result),
+    };
+
+    // End of input is assumed to be right after the last token.
+    let mut tokens = parser.tokenize("1 + 0 + 1").unwrap();
+    tokens.pop();
+    let parse_forest = parser.parse_forest(tokens).unwrap();
+    assert_eq!(parse_forest.trees(10).len(), 2);
+
+    let mut tokens = parser.tokenize("1 +").unwrap();
+    tokens.pop();
+    let error = parser.parse_forest(tokens).unwrap_err();
+    assert_eq!(error.to_string(), "unexpected end of input at 1:4 (expected one of '0', '1')");
+
+    let error = parser.parse_forest(vec![]).unwrap_err();
+    assert!(matches!(error, ParsingError::UnexpectedEof { .. }));
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_recovering_from_syntax_errors_during_parsing_json_recovery_grammar() {
+    let grammar = Grammar::parse(common::grammars::JSON_RECOVERY).unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let tokens = parser.tokenize(r#"{"a": 1 "b": 2, "c": [1 2, 3], "d": true}"#).unwrap();
+    let (parse_tree, errors) = parser.parse_with_recovery(tokens);
+
+    assert_eq!(
+        errors.iter().map(|error| error.to_string()).collect::<Vec<_>>(),
+        [
+            r#"unexpected token "b" at 1:9 (expected one of ',', '}'), try to insert ',' at 1:9"#,
+            "unexpected token 2 at 1:25 (expected one of ']', ','), try to insert ',' at 1:25 or \
+             delete 2 at 1:25",
+        ]
+    );
+    assert_eq!(
+        parse_tree.to_string().trim(),
+        r#"
+
+Json
+└─ Value
+   └─ Object
+      ├─ {
+      ├─ ObjectElements
+      │  ├─ ObjectElements
+      │  │  ├─ ObjectElements
+      │  │  │  ├─ String
+      │  │  │  │  └─ "a"
+      │  │  │  ├─ :
+      │  │  │  └─ Value
+      │  │  │     └─ %error
+      │  │  │        ├─ 1
+      │  │  │        ├─ "b"
+      │  │  │        ├─ :
+      │  │  │        └─ 2
+      │  │  ├─ ,
+      │  │  ├─ String
+      │  │  │  └─ "c"
+      │  │  ├─ :
+      │  │  └─ Value
+      │  │     └─ Array
+      │  │        ├─ [
+      │  │        ├─ ArrayElements
+      │  │        │  ├─ ArrayElements
+      │  │        │  │  └─ Value
+      │  │        │  │     └─ %error
+      │  │        │  │        ├─ 1
+      │  │        │  │        └─ 2
+      │  │        │  ├─ ,
+      │  │        │  └─ Value
+      │  │        │     └─ Number
+      │  │        │        └─ 3
+      │  │        └─ ]
+      │  ├─ ,
+      │  ├─ String
+      │  │  └─ "d"
+      │  ├─ :
+      │  └─ Value
+      │     └─ Boolean
+      │        └─ true
+      └─ }
+
+        "#
+        .trim(),
+    );
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_recovering_from_syntax_errors_without_errors_during_parsing_json_recovery_grammar() {
+    let grammar = Grammar::parse(common::grammars::JSON_RECOVERY).unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let input = r#"{ "a": [1, 2, 3], "b": null }"#;
+
+    let parse_tree = parser.parse(parser.tokenize(input).unwrap()).unwrap();
+    let (recovered_parse_tree, errors) =
+        parser.parse_with_recovery(parser.tokenize(input).unwrap());
+
+    assert!(errors.is_empty());
+    assert_eq!(recovered_parse_tree.to_string(), parse_tree.to_string());
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn raising_correct_errors_when_failing_to_recover_from_syntax_errors() {
+    let grammar = Grammar::parse(common::grammars::JSON_RECOVERY).unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let tokens = parser.tokenize("[1, 2] 3").unwrap();
+    let (parse_tree, errors) = parser.parse_with_recovery(tokens);
+
+    assert_eq!(
+        errors.iter().map(|error| error.to_string()).collect::<Vec<_>>(),
+        [
+            "unexpected token 3 at 1:8 (expected $), try to delete 3 at 1:8",
+            "unexpected end of input at 1:9 (expected one of ']', ','), try to insert ']' at 1:9",
+        ]
+    );
+    assert_eq!(
+        parse_tree.to_string().trim(),
+        r#"
+
+%error
+├─ [
+└─ %error
+   ├─ ArrayElements
+   │  ├─ ArrayElements
+   │  │  └─ Value
+   │  │     └─ Number
+   │  │        └─ 1
+   │  ├─ ,
+   │  └─ Value
+   │     └─ Number
+   │        └─ 2
+   ├─ ]
+   └─ 3
+
+        "#
+        .trim(),
+    );
+
+    let grammar = Grammar::parse(common::grammars::JSON).unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let tokens = parser.tokenize("[1 2, 3 4]").unwrap();
+    let (parse_tree, errors) = parser.parse_with_recovery(tokens);
+
+    assert_eq!(
+        errors.iter().map(|error| error.to_string()).collect::<Vec<_>>(),
+        ["unexpected token 2 at 1:4 (expected one of ']', ','), try to insert ',' at 1:4"]
+    );
+    assert_eq!(
+        parse_tree.to_string().trim(),
+        r#"
+
+%error
+├─ [
+└─ 1
+
+        "#
+        .trim(),
+    );
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn suggesting_cheapest_repairs_for_syntax_errors_during_parsing_calculator_grammar() {
+    let grammar = Grammar::parse(common::grammars::CALCULATOR).unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let tokens = parser.tokenize("1 + (2 *").unwrap();
+    let repairs = match parser.parse(tokens).unwrap_err() {
+        ParsingError::UnexpectedEof { repairs, .. } => repairs,
+        error => panic!("unexpected parsing error {:?}", error),
+    };
+
+    let span = Span { offset: 8, length: 0, line: 1, column: 9 };
+    assert_eq!(repairs.len(), 1);
+    assert_eq!(repairs[0].cost(), 2);
+    assert_eq!(
+        repairs[0].edits(),
+        [
+            Edit::Insert { token: RegexToken::from("f").into(), span: span.clone() },
+            Edit::Insert { token: ConstantToken::from(")").into(), span },
+        ]
+    );
+
+    let tokens = parser.tokenize("1 2 + 3").unwrap();
+    let repairs = match parser.parse(tokens).unwrap_err() {
+        ParsingError::UnexpectedToken { repairs, .. } => repairs,
+        error => panic!("unexpected parsing error {:?}", error),
+    };
+
+    let span = Span { offset: 2, length: 1, line: 1, column: 3 };
+    assert_eq!(
+        repairs.iter().map(|repair| repair.edits().to_vec()).collect::<Vec<_>>(),
+        [
+            vec![Edit::Insert { token: ConstantToken::from("^").into(), span: span.clone() }],
+            vec![Edit::Insert { token: ConstantToken::from("+").into(), span: span.clone() }],
+            vec![Edit::Insert { token: ConstantToken::from("-").into(), span }],
+        ]
+    );
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_tokenizing_with_longest_match_and_constant_priority() {
+    let grammar = Grammar::parse(
+        r#"
+P -> T+
+
+T -> 'if'
+T -> 'i'
+T -> %id
+T -> %word
+
+%id -> /[a-z]+/
+%word -> /[a-z]+[0-9]*/
+        "#,
+    )
+    .unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let tokens = parser.tokenize("if iffy i ifs2").unwrap();
+    assert_eq!(
+        tokens
+            .into_iter()
+            .map(|(token, slice)| (token.into_components().0, slice))
+            .collect::<Vec<_>>(),
+        [
+            (ConstantToken::from("if").into(), "if"),
+            (RegexToken::from("id").into(), "iffy"),
+            (ConstantToken::from("i").into(), "i"),
+            (RegexToken::from("word").into(), "ifs2"),
+            (Token::Eof, "\0"),
+        ],
+    );
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_tokenizing_with_regular_expressions_that_cannot_be_compiled_into_a_dfa() {
+    // Unicode word boundaries are not supported by the DFA, so each token is tried one by one.
+    let grammar = Grammar::parse(
+        r#"
+P -> T+
+
+T -> 'in'
+T -> %id
+
+%id -> /\b[a-zçğış]+\b/
+        "#,
+    )
+    .unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let tokens = parser.tokenize("in ağaç inç").unwrap();
+    assert_eq!(
+        tokens
+            .into_iter()
+            .map(|(token, slice)| (token.into_components().0, slice))
+            .collect::<Vec<_>>(),
+        [
+            (ConstantToken::from("in").into(), "in"),
+            (RegexToken::from("id").into(), "ağaç"),
+            (RegexToken::from("id").into(), "inç"),
+            (Token::Eof, "\0"),
+        ],
+    );
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_tokenizing_with_lazy_regular_expressions() {
+    // Unicode word boundaries are not supported by the DFA, so each token is tried one by one.
+    for word in [r"/[a-z]+/", r"/\b[a-z]+\b/"] {
+        let grammar = Grammar::parse(&format!(
+            r#"
+S -> %s %w %s
+
+%s -> /".*?"/
+%w -> {}
+            "#,
+            word,
+        ))
+        .unwrap();
+        let parser = Parser::lr(grammar).unwrap();
+
+        let tokens = parser.tokenize(r#""a" b "c""#).unwrap();
+        assert_eq!(
+            tokens
+                .iter()
+                .map(|(token, slice)| (token.clone().into_components().0, *slice))
+                .collect::<Vec<_>>(),
+            [
+                (RegexToken::from("s").into(), r#""a""#),
+                (RegexToken::from("w").into(), "b"),
+                (RegexToken::from("s").into(), r#""c""#),
+                (Token::Eof, "\0"),
+            ],
+        );
+        assert!(parser.parse(tokens).is_ok());
+    }
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_tokenizing_with_leftmost_first_regular_expressions() {
+    // Earlier alternatives are preferred, even if the later alternatives match longer.
+    // Unicode word boundaries are not supported by the DFA, so each token is tried one by one.
+    for letters in [r"/a|ab/", r"/\b(a|ab)/"] {
+        let grammar = Grammar::parse(&format!(
+            r#"
+S -> %l 'b' %n
+
+%l -> {}
+%n -> /[0-9]+/
+            "#,
+            letters,
+        ))
+        .unwrap();
+        let parser = Parser::lr(grammar).unwrap();
+
+        let tokens = parser.tokenize("ab 12").unwrap();
+        assert_eq!(
+            tokens
+                .iter()
+                .map(|(token, slice)| (token.clone().into_components().0, *slice))
+                .collect::<Vec<_>>(),
+            [
+                (RegexToken::from("l").into(), "a"),
+                (ConstantToken::from("b").into(), "b"),
+                (RegexToken::from("n").into(), "12"),
+                (Token::Eof, "\0"),
+            ],
+        );
+        assert!(parser.parse(tokens).is_ok());
+    }
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_parsing_token_streams_of_calculator_grammar() {
+    let grammar = Grammar::parse(common::grammars::CALCULATOR).unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let input = "1 + 2 * (3 - 4)";
+
+    let tokens = parser.token_stream(input).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(
+        tokens
+            .iter()
+            .map(|(token, slice)| (token.clone().into_components(), *slice))
+            .collect::<Vec<_>>(),
+        parser
+            .tokenize(input)
+            .unwrap()
+            .into_iter()
+            .map(|(token, slice)| (token.into_components(), slice))
+            .collect::<Vec<_>>(),
+    );
+
+    let tree = parser.parse_iter(parser.token_stream(input)).unwrap();
+    assert_eq!(tree.to_string(), parser.parse(tokens.clone()).unwrap().to_string());
+
+    // End of input is assumed to be right after the last token if it's not in the stream.
+    let tokens_without_eof =
+        tokens.into_iter().filter(|(token, _)| **token != Token::Eof).map(Ok).collect::<Vec<_>>();
+    let tree_without_eof = parser.parse_iter(tokens_without_eof).unwrap();
+    assert_eq!(tree_without_eof.to_string(), tree.to_string());
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn stopping_at_the_first_error_when_parsing_token_streams_of_calculator_grammar() {
+    let grammar = Grammar::parse(common::grammars::CALCULATOR).unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let mut consumed_tokens = 0;
+    let error = parser
+        .parse_iter(parser.token_stream("1 + ) 2 + 3 + 4 + 5 + 6 + 7 + 8 + 9").inspect(|_| {
+            consumed_tokens += 1;
+        }))
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "unexpected token ) at 1:5 (expected one of '(', %f), try to delete ) at 1:5 or replace ) \
+         with '(' at 1:5",
+    );
+
+    // Tokens are only consumed until the unexpected token and the ones needed to suggest repairs.
+    assert_eq!(consumed_tokens, 9);
+
+    let mut stream = parser.token_stream("1 + # 2 + 3");
+    assert!(stream.next().unwrap().is_ok());
+    assert!(stream.next().unwrap().is_ok());
+    assert_eq!(stream.next().unwrap().unwrap_err().to_string(), "unknown token # at 1:5");
+    assert!(stream.next().is_none());
+
+    let error = parser.parse_iter(parser.token_stream("1 + # 2 + 3")).unwrap_err();
+    assert_eq!(error.to_string(), "unknown token # at 1:5");
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_skipping_ignored_regular_expressions_during_parsing_json_comments_grammar() {
+    let grammar = Grammar::parse(common::grammars::JSON_COMMENTS).unwrap();
+    let parser = Parser::lalr(grammar).unwrap();
+
+    let input = "// header\n{ /* a\n   b */ \"a\": 1, // one\n  \"b\": 2 }";
+    let tokens = parser.tokenize(input).unwrap();
+    assert_eq!(
+        tokens
+            .iter()
+            .map(|(token, slice)| (*slice, token.span().line, token.span().column))
+            .collect::<Vec<_>>(),
+        [
+            ("{", 2, 1),
+            ("\"a\"", 3, 9),
+            (":", 3, 12),
+            ("1", 3, 14),
+            (",", 3, 15),
+            ("\"b\"", 4, 3),
+            (":", 4, 6),
+            ("2", 4, 8),
+            ("}", 4, 10),
+            ("\0", 4, 11),
+        ],
+    );
+    assert_eq!(tokens[1].0.span().offset, input.find("\"a\"").unwrap());
+
+    let tree = parser.parse(tokens).unwrap();
+    assert!(!tree.to_string().contains("//"));
+
+    let error = parser.tokenize("[1, /* unterminated").unwrap_err();
+    assert_eq!(error.to_string(), "unknown token / at 1:5");
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_tokenizing_with_significant_whitespace() {
+    let grammar = Grammar::parse(
+        r#"
+P -> Words
+
+Words -> %w
+Words -> Words %s %w
+
+%w -> /[a-z]+/
+%s -> / +/
+
+%significant_whitespace
+        "#,
+    )
+    .unwrap();
+    assert!(grammar.is_whitespace_significant());
+
+    let parser = Parser::lr(grammar).unwrap();
+
+    let tokens = parser.tokenize("ab  cd e").unwrap();
+    assert_eq!(
+        tokens
+            .iter()
+            .map(|(token, slice)| (token.object().clone(), *slice, token.span().column))
+            .collect::<Vec<_>>(),
+        [
+            (RegexToken::from("w").into(), "ab", 1),
+            (RegexToken::from("s").into(), "  ", 3),
+            (RegexToken::from("w").into(), "cd", 5),
+            (RegexToken::from("s").into(), " ", 7),
+            (RegexToken::from("w").into(), "e", 8),
+            (Token::Eof, "\0", 9),
+        ],
+    );
+    assert!(parser.parse(tokens).is_ok());
+
+    let error = parser.tokenize("ab\tcd").unwrap_err();
+    match error {
+        ParsingError::UnknownToken { token, span, mode } => {
+            assert_eq!(token.as_str(), "\t");
+            assert_eq!(span, Span { offset: 2, length: 1, line: 1, column: 3 });
+            assert_eq!(mode, None);
+        },
+        error => panic!("unexpected parsing error {:?}", error),
+    }
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_tokenizing_with_lexer_modes_during_parsing_interpolation_grammar() {
+    let grammar = Grammar::parse(common::grammars::INTERPOLATION).unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let tokens = parser.tokenize(r#"a + "b + ${ "c${d}" } e""#).unwrap();
+    assert_eq!(
+        tokens.iter().map(|(token, slice)| (token.object().clone(), *slice)).collect::<Vec<_>>(),
+        [
+            (RegexToken::from("identifier").into(), "a"),
+            (ConstantToken::from("+").into(), "+"),
+            (ConstantToken::from("\"").into(), "\""),
+            (RegexToken::from("text").into(), "b + "),
+            (ConstantToken::from("${").into(), "${"),
+            (ConstantToken::from("\"").into(), "\""),
+            (RegexToken::from("text").into(), "c"),
+            (ConstantToken::from("${").into(), "${"),
+            (RegexToken::from("identifier").into(), "d"),
+            (ConstantToken::from("}").into(), "}"),
+            (ConstantToken::from("\"").into(), "\""),
+            (ConstantToken::from("}").into(), "}"),
+            (RegexToken::from("text").into(), " e"),
+            (ConstantToken::from("\"").into(), "\""),
+            (Token::Eof, "\0"),
+        ],
+    );
+    assert!(parser.parse(tokens).is_ok());
+
+    let error = parser.tokenize(r#"a + "b $""#).unwrap_err();
+    assert_eq!(error.to_string(), "unknown token $ at 1:8 in String mode");
+    match error {
+        ParsingError::UnknownToken { mode, .. } => assert_eq!(mode.as_deref(), Some("String")),
+        error => panic!("unexpected parsing error {:?}", error),
+    }
+
+    let error = parser.tokenize("a + $").unwrap_err();
+    assert_eq!(error.to_string(), "unknown token $ at 1:5 in Default mode");
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_parsing_keywords_as_identifiers_with_contextual_lexing() {
+    for identifier_regex in [r"[a-z]+", r"\b[a-z]+\b"] {
+        let grammar = Grammar::parse(&format!(
+            "Statement -> 'for' %identifier 'in' %identifier\n%identifier -> /{}/",
+            identifier_regex,
+        ))
+        .unwrap();
+        let parser = Parser::lr(grammar).unwrap();
+
+        // Without contextual lexing, keywords always win over identifiers.
+        let error = parser.parse(parser.tokenize("for in in in").unwrap()).unwrap_err();
+        assert!(matches!(error, ParsingError::UnexpectedToken { .. }));
+
+        let tree = parser.parse_contextual("for in in in").unwrap();
+        match tree {
+            Tree::NonTerminal { pattern, .. } => {
+                assert_eq!(
+                    pattern
+                        .iter()
+                        .map(|terminal| {
+                            match terminal {
+                                Tree::Terminal { token, slice, .. } => (token.clone(), *slice),
+                                _ => unreachable!(),
+                            }
+                        })
+                        .collect::<Vec<_>>(),
+                    [
+                        (ConstantToken::from("for").into(), "for"),
+                        (RegexToken::from("identifier").into(), "in"),
+                        (ConstantToken::from("in").into(), "in"),
+                        (RegexToken::from("identifier").into(), "in"),
+                    ],
+                );
+            },
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn raising_correct_error_when_no_acceptable_token_matches_during_contextual_lexing() {
+    let grammar =
+        Grammar::parse("Statement -> 'for' %identifier 'in' %identifier\n%identifier -> /[a-z]+/")
+            .unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    // All tokens are tried when none of the acceptable tokens match.
+    let error = parser.parse_contextual("for x of y").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "unexpected token of at 1:7 (expected 'in'), try to replace of with 'in' at 1:7",
+    );
+
+    let error = parser.parse_contextual("for x in 1").unwrap_err();
+    assert_eq!(error.to_string(), "unknown token 1 at 1:10");
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_synthesizing_layout_tokens_during_parsing_indentation_grammar() {
+    let grammar = Grammar::parse(common::grammars::INDENTATION).unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let tokens = parser.tokenize("if a:\n  b = 1\n\n  # c\nd = 2").unwrap();
+    assert_eq!(
+        tokens
+            .iter()
+            .map(|(token, slice)| {
+                let span = token.span();
+                (token.object().clone(), *slice, span.offset, span.line, span.column)
+            })
+            .collect::<Vec<_>>(),
+        [
+            (ConstantToken::from("if").into(), "if", 0, 1, 1),
+            (RegexToken::from("identifier").into(), "a", 3, 1, 4),
+            (ConstantToken::from(":").into(), ":", 4, 1, 5),
+            (Token::Newline, "\n", 5, 1, 6),
+            (Token::Indent, "  ", 6, 2, 1),
+            (RegexToken::from("identifier").into(), "b", 8, 2, 3),
+            (ConstantToken::from("=").into(), "=", 10, 2, 5),
+            (RegexToken::from("number").into(), "1", 12, 2, 7),
+            (Token::Newline, "\n", 13, 2, 8),
+            (Token::Dedent, "", 21, 5, 1),
+            (RegexToken::from("identifier").into(), "d", 21, 5, 1),
+            (ConstantToken::from("=").into(), "=", 23, 5, 3),
+            (RegexToken::from("number").into(), "2", 25, 5, 5),
+            (Token::Newline, "", 26, 5, 6),
+            (Token::Eof, "\0", 26, 5, 6),
+        ],
+    );
+    assert!(parser.parse(tokens).is_ok());
+
+    // Indentation levels that are still open are closed at the end of input.
+    let tokens = parser.tokenize("if a:\n  if b:\n    c = 1\n").unwrap();
+    assert_eq!(
+        tokens.iter().rev().take(4).map(|(token, _)| token.object().clone()).collect::<Vec<_>>(),
+        [Token::Eof, Token::Dedent, Token::Dedent, Token::Newline],
+    );
+    assert!(parser.parse(tokens).is_ok());
+
+    let error = parser.parse(parser.tokenize("if a:\nb = 1\n").unwrap()).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "unexpected token b at 2:1 (expected %indent), try to insert %indent at 2:1",
+    );
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn raising_correct_error_when_dedenting_inconsistently_during_parsing_indentation_grammar() {
+    let grammar = Grammar::parse(common::grammars::INDENTATION).unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let error = parser.tokenize("if a:\n    b = 1\n  c = 2\n").unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "inconsistent dedent at 3:1 (indentation doesn't match any outer indentation level)",
+    );
+    match error {
+        ParsingError::InconsistentDedent { span } => {
+            assert_eq!(span, Span { offset: 16, length: 2, line: 3, column: 1 });
+        },
+        error => panic!("unexpected parsing error {:?}", error),
+    }
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_round_tripping_lossless_parse_trees_of_correct_grammars() {
+    let json = "{\n  \"a\": [1, 2.5, true],\n\t\"b\": null\n}\n";
+    let inputs = [
+        (common::grammars::BINARY_ADDITION, "  1 +\t0\n+ 1  \n"),
+        (common::grammars::CALCULATOR, "\n  1 + 2 *\n (3 - 4) ^ 2 / 5\t"),
+        (common::grammars::CALCULATOR_PRECEDENCE, " 1 < 2 + - 3 * 4 ^ 5 \n"),
+        (common::grammars::CONDITIONAL, "if a {\n  true\n} else {\n  false\n}\n"),
+        (common::grammars::G9, " ( 1 + 2 ) * 3 "),
+        (common::grammars::G10, " foo ( bar + baz ) + qux\n"),
+        (common::grammars::G11, "\tab [ cd ] = ef\n"),
+        (common::grammars::INDENTATION, "\na = 1 # one\nif a:\n\n  b = 2\n  if b:\n    c = 3\n"),
+        (common::grammars::INTERPOLATION, " \"a ${ b + \"c\" } d\"\n + e "),
+        (common::grammars::JSON, json),
+        (
+            common::grammars::JSON_COMMENTS,
+            "// header\n{ /* a\n  b */ \"a\": 1, // one\n  \"b\": [ /* none */ ] }\n// footer\n",
+        ),
+        (common::grammars::JSON_EBNF, json),
+        (common::grammars::JSON_RECOVERY, json),
+        (common::grammars::NOT_LALR, " a x  a "),
+        (common::grammars::OPTIONAL, "y x\n z"),
+        (common::grammars::RECURSIVE_START, " x x\n y "),
+        (common::grammars::STATEMENTS_EBNF, "let a = (b, 1);\n  c;\n"),
+    ];
+    assert_eq!(inputs.len(), common::grammars::CORRECT.len());
+
+    for (grammar, input) in inputs {
+        assert!(common::grammars::CORRECT.contains(&grammar));
+
+        let grammar = Grammar::parse(grammar).unwrap();
+        let parser = Parser::lr(grammar).unwrap();
+
+        let tree = parser.parse_lossless(input).unwrap();
+        assert_eq!(tree.text(), input);
+    }
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn keeping_trivia_of_lossless_parse_trees_without_terminals() {
+    let grammar = Grammar::parse("P -> O\nO -> 'x'\nO -> ''\n%ignore -> /#[^\\n]*/").unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    // There are no terminals to attach the trivia to, so it's attached to the end of input.
+    let tree = parser.parse_lossless("  # comment\n").unwrap();
+    assert_eq!(tree.text(), "  # comment\n");
+    assert_eq!(
+        tree.to_string().trim(),
+        r#"
+
+P
+├─ O
+└─ $
+
+        "#
+        .trim(),
+    );
+
+    let tree = parser.parse_lossless("  # comment\nx ").unwrap();
+    assert_eq!(tree.text(), "  # comment\nx ");
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_attaching_trivia_to_terminals_during_parsing_json_comments_grammar() {
+    let grammar = Grammar::parse(common::grammars::JSON_COMMENTS).unwrap();
+    let parser = Parser::lalr(grammar).unwrap();
+
+    let input = "// header\n{ /* a\n  b */ \"a\": 1, // one\n  \"b\": 2 }  \n";
+    let tree = parser.parse_lossless(input).unwrap();
+
+    fn collect_terminals<'t, 'i>(tree: &'t Tree<'i>, terminals: &mut Vec<&'t Tree<'i>>) {
+        match tree {
+            Tree::Terminal { .. } => terminals.push(tree),
+            Tree::NonTerminal { pattern, .. } => {
+                for branch in pattern {
+                    collect_terminals(branch, terminals);
+                }
+            },
+            Tree::Error { .. } => unreachable!(),
+        }
+    }
+    let mut terminals = vec![];
+    collect_terminals(&tree, &mut terminals);
+
+    assert_eq!(
+        terminals
+            .iter()
+            .map(|terminal| {
+                match terminal {
+                    Tree::Terminal { slice, trivia: Some(trivia), .. } => {
+                        (*trivia.leading.object(), *slice, *trivia.trailing.object())
+                    },
+                    _ => unreachable!(),
+                }
+            })
+            .collect::<Vec<_>>(),
+        [
+            ("// header\n", "{", " /* a\n  b */ "),
+            ("", "\"a\"", ""),
+            ("", ":", " "),
+            ("", "1", ""),
+            ("", ",", " // one\n"),
+            ("  ", "\"b\"", ""),
+            ("", ":", " "),
+            ("", "2", " "),
+            ("", "}", "  \n"),
+        ],
+    );
+
+    match terminals[5] {
+        Tree::Terminal { trivia: Some(trivia), .. } => {
+            assert_eq!(*trivia.leading.span(), Span { offset: 39, length: 2, line: 4, column: 1 });
+        },
+        _ => unreachable!(),
+    }
+
+    // Trivia is only kept in lossless parse trees.
+    let tree = parser.parse(parser.tokenize(input).unwrap()).unwrap();
+    assert_eq!(tree.text(), "{\"a\":1,\"b\":2}");
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_parsing_with_external_lexer() {
+    struct CalculatorLexer<'i> {
+        input: &'i str,
+        offset: usize,
+    }
+
+    impl<'i> Lexer<'i> for CalculatorLexer<'i> {
+        fn next_token(&mut self) -> Option<Result<(Spanned<Token>, &'i str), ParsingError>> {
+            let remaining_input = &self.input[self.offset..];
+            self.offset += remaining_input.len() - remaining_input.trim_start().len();
+
+            let remaining_input = &self.input[self.offset..];
+            let length = match remaining_input.find(|c: char| !c.is_ascii_digit()) {
+                Some(0) => 1,
+                Some(length) => length,
+                None if remaining_input.is_empty() => return None,
+                None => remaining_input.len(),
+            };
+
+            let slice = &remaining_input[..length];
+            let token = if slice.starts_with(|c: char| c.is_ascii_digit()) {
+                RegexToken::from("f").into()
+            } else {
+                ConstantToken::from(slice).into()
+            };
+            let span = Span { offset: self.offset, length, line: 1, column: self.offset + 1 };
+
+            self.offset += length;
+            Some(Ok((Spanned::new(token, span), slice)))
+        }
+    }
+
+    let grammar = Grammar::parse(common::grammars::CALCULATOR).unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let input = "12 + 3 * (4 - 5)";
+    let tokens = parser.tokenize_with(CalculatorLexer { input, offset: 0 }).unwrap();
+    assert_eq!(
+        tokens
+            .iter()
+            .map(|(token, slice)| (token.object().clone(), token.span().clone(), *slice))
+            .collect::<Vec<_>>(),
+        parser
+            .tokenize(input)
+            .unwrap()
+            .iter()
+            .map(|(token, slice)| (token.object().clone(), token.span().clone(), *slice))
+            .collect::<Vec<_>>(),
+    );
+
+    let tree = parser.parse(tokens).unwrap();
+    assert_eq!(tree.text(), "12+3*(4-5)");
+
+    let (trace, _) =
+        parser.trace(parser.tokenize_with(CalculatorLexer { input, offset: 0 }).unwrap()).unwrap();
+    assert!(!trace.steps().is_empty());
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn raising_correct_error_when_external_lexer_produces_undefined_token() {
+    let grammar = Grammar::parse(common::grammars::CALCULATOR).unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    for (token, expected_error) in [
+        (ConstantToken::from("%").into(), "undefined token '%' at 1:3"),
+        (RegexToken::from("i").into(), "undefined token %i at 1:3"),
+        (Token::Error, "undefined token %error at 1:3"),
+    ] {
+        let tokens = [
+            (
+                Spanned::new(
+                    RegexToken::from("f").into(),
+                    Span { offset: 0, length: 1, line: 1, column: 1 },
+                ),
+                "1",
+            ),
+            (Spanned::new(token, Span { offset: 2, length: 1, line: 1, column: 3 }), "%"),
+        ];
+
+        let error = parser.tokenize_with(tokens.clone().into_iter().map(Ok)).unwrap_err();
+        assert_eq!(error.to_string(), expected_error);
+
+        let error = parser.parse_iter(tokens.into_iter().map(Ok)).unwrap_err();
+        assert_eq!(error.to_string(), expected_error);
+    }
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_tokenizing_with_recovery() {
+    let grammar = Grammar::parse(common::grammars::JSON).unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let (tokens, errors) = parser.tokenize_with_recovery("[1, @@2, é$ #, true]");
+    assert_eq!(
+        tokens.iter().map(|(_, slice)| *slice).collect::<Vec<_>>(),
+        ["[", "1", ",", "2", ",", ",", "true", "]", "\0"],
+    );
+    assert_eq!(
+        errors
+            .iter()
+            .map(|error| {
+                match error {
+                    ParsingError::UnknownToken { token, span, .. } => {
+                        (token.as_str(), span.clone())
+                    },
+                    error => panic!("unexpected parsing error {:?}", error),
+                }
+            })
+            .collect::<Vec<_>>(),
+        [
+            ("@@", Span { offset: 4, length: 2, line: 1, column: 5 }),
+            ("é$", Span { offset: 9, length: 3, line: 1, column: 10 }),
+            ("#", Span { offset: 13, length: 1, line: 1, column: 13 }),
+        ],
+    );
+
+    let (tokens, errors) = parser.tokenize_with_recovery("[1, 2]");
+    assert_eq!(tokens.len(), 6);
+    assert!(errors.is_empty());
+
+    // Lines with inconsistent dedents stay in the indentation level they dedent out of.
+    let grammar = Grammar::parse(common::grammars::INDENTATION).unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let (tokens, errors) =
+        parser.tokenize_with_recovery("if a:\n    b = 1\n  c = 2\n  d = 3\ne = 4");
+    assert_eq!(
+        errors.iter().map(|error| error.to_string()).collect::<Vec<_>>(),
+        ["inconsistent dedent at 3:1 (indentation doesn't match any outer indentation level)"],
+    );
+    assert_eq!(
+        tokens
+            .iter()
+            .filter(|(token, _)| token.is_layout())
+            .map(|(token, _)| token.object().clone())
+            .collect::<Vec<_>>(),
+        [
+            Token::Newline,
+            Token::Indent,
+            Token::Newline,
+            Token::Newline,
+            Token::Newline,
+            Token::Dedent,
+            Token::Newline,
+        ],
+    );
+    assert!(parser.parse(tokens).is_ok());
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_breaking_ties_between_regex_tokens_with_priorities() {
+    for priority in ["", "%priority %hex"] {
+        let grammar = Grammar::parse(&format!(
+            "Value -> %decimal | %hex\n%decimal -> /[0-9]+/\n%hex -> /[0-9a-f]+/\n{}",
+            priority,
+        ))
+        .unwrap();
+        let parser = Parser::lr(grammar).unwrap();
+
+        let tokens = parser.tokenize("12").unwrap();
+        let expected = if priority.is_empty() { "decimal" } else { "hex" };
+        assert_eq!(*tokens[0].0, RegexToken::from(expected).into());
+
+        // Longer matches still win over tokens with higher priorities.
+        let tokens = parser.tokenize("1f").unwrap();
+        assert_eq!(*tokens[0].0, RegexToken::from("hex").into());
+    }
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_scanning_keywords_only_at_word_boundaries() {
+    for number_regex in [r"[0-9]+", r"\b[0-9]+\b"] {
+        let grammar = Grammar::parse(&format!(
+            "Statement -> 'if' %number\n%number -> /{}/\n%keyword 'if'",
+            number_regex,
+        ))
+        .unwrap();
+        let parser = Parser::lr(grammar).unwrap();
+
+        assert!(parser.parse(parser.tokenize("if 1").unwrap()).is_ok());
+
+        let error = parser.tokenize("if1").unwrap_err();
+        assert_eq!(error.to_string(), "unknown token i at 1:1");
+    }
+
+    // Constant tokens that are not keywords match anywhere.
+    let grammar = Grammar::parse("Statement -> 'if' %number\n%number -> /[0-9]+/").unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+    assert!(parser.parse(parser.tokenize("if1").unwrap()).is_ok());
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_scanning_reserved_words_of_regex_tokens() {
+    for identifier_regex in [r"[a-z]+", r"\b[a-z]+\b"] {
+        let grammar = Grammar::parse(&format!(
+            "Statement -> 'let' %identifier '=' %identifier\n%identifier -> /{}/\n%priority %identifier\n%reserved %identifier -> 'let'",
+            identifier_regex,
+        ))
+        .unwrap();
+        let parser = Parser::lr(grammar).unwrap();
+
+        // Reserved words win over regex tokens with higher priorities.
+        let tokens = parser.tokenize("let x = lettuce").unwrap();
+        assert_eq!(
+            tokens.iter().map(|(token, _)| token.object().clone()).collect::<Vec<_>>(),
+            [
+                ConstantToken::from("let").into(),
+                RegexToken::from("identifier").into(),
+                ConstantToken::from("=").into(),
+                RegexToken::from("identifier").into(),
+                Token::Eof,
+            ],
+        );
+        assert!(parser.parse(tokens).is_ok());
+
+        // Reserved words are not scanned as regex tokens even with contextual lexing.
+        for error in [
+            parser.parse(parser.tokenize("let x = let").unwrap()).unwrap_err(),
+            parser.parse_contextual("let x = let").unwrap_err(),
+        ] {
+            assert!(
+                error.to_string().starts_with("unexpected token let at 1:9 (expected %identifier)"),
+            );
+        }
+    }
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_parsing_with_semantic_actions_calculator_grammar() {
+    struct Printer;
+
+    impl<'i> SemanticActions<'i> for Printer {
+        type Value = String;
+
+        fn shift(&mut self, _token: Spanned<Token>, slice: &'i str) -> String {
+            slice.to_owned()
+        }
+
+        fn reduce(&mut self, _rule_index: usize, rule: &Rule, values: Vec<String>) -> String {
+            match values.as_slice() {
+                [value] => value.clone(),
+                [_, value, _] if rule.symbol().as_str() == "Term" => value.clone(),
+                [lhs, operator, rhs] => format!("({} {} {})", operator, lhs, rhs),
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    let grammar = Grammar::parse(common::grammars::CALCULATOR).unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let tokens = parser.tokenize("1 + 2 * (3 - 4) ^ 5 ^ 6").unwrap();
+    assert_eq!(parser.parse_with(tokens, &mut Printer).unwrap(), "(+ 1 (* 2 (^ (- 3 4) (^ 5 6))))",);
+
+    let tokens = parser.tokenize("1 + (2 *").unwrap();
+    let error = parser.parse_with(tokens.clone(), &mut Printer).unwrap_err();
+    assert_eq!(error.to_string(), parser.parse(tokens).unwrap_err().to_string());
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_reducing_empty_patterns_with_semantic_actions_optional_grammar() {
+    #[derive(Default)]
+    struct Recorder {
+        shifted: Vec<Token>,
+        reduced: Vec<(usize, usize)>,
+    }
+
+    impl<'i> SemanticActions<'i> for Recorder {
+        type Value = ();
+
+        fn shift(&mut self, token: Spanned<Token>, _slice: &'i str) {
+            self.shifted.push(token.into_components().0);
+        }
+
+        fn reduce(&mut self, rule_index: usize, _rule: &Rule, values: Vec<()>) {
+            self.reduced.push((rule_index, values.len()));
+        }
+    }
+
+    let grammar = Grammar::parse(common::grammars::OPTIONAL).unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let mut recorder = Recorder::default();
+    parser.parse_with(parser.tokenize("x y z").unwrap(), &mut recorder).unwrap();
+
+    assert_eq!(
+        recorder.shifted,
+        [
+            ConstantToken::from("x").into(),
+            ConstantToken::from("y").into(),
+            ConstantToken::from("z").into()
+        ],
+    );
+    assert_eq!(recorder.reduced, [(2, 0), (1, 1), (0, 4)]);
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_emitting_parse_events_binary_addition_grammar() {
+    let grammar = Grammar::parse(common::grammars::BINARY_ADDITION).unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let events = parser
+        .events(parser.token_stream("1 + 0"))
+        .map(|event| {
+            match event {
+                Event::Shift { token, span, slice } => {
+                    format!("shift {} {} {}", token, span.offset, slice)
+                },
+                Event::Reduce { rule_index, child_count } => {
+                    format!("reduce {} {}", rule_index, child_count)
+                },
+                Event::Accept => "accept".to_owned(),
+                Event::Error { error } => format!("error {}", error),
+            }
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        events,
+        [
+            "shift '1' 0 1",
+            "reduce 3 1",
+            "reduce 1 1",
+            "shift '+' 2 +",
+            "shift '0' 4 0",
+            "reduce 2 1",
+            "reduce 0 3",
+            "accept",
+        ],
+    );
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn stopping_at_the_first_error_when_emitting_parse_events_calculator_grammar() {
+    let grammar = Grammar::parse(common::grammars::CALCULATOR).unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let mut consumed_tokens = 0;
+    let mut events =
+        parser.events(parser.token_stream("1 + ) 2 + 3 + 4 + 5 + 6 + 7 + 8 + 9").inspect(|_| {
+            consumed_tokens += 1;
+        }));
+
+    let error = events.find_map(|event| {
+        match event {
+            Event::Error { error } => Some(error),
+            _ => None,
+        }
+    });
+    assert!(events.next().is_none());
+    drop(events);
+
+    let tokens = parser.tokenize("1 + ) 2 + 3 + 4 + 5 + 6 + 7 + 8 + 9").unwrap();
+    assert_eq!(error.unwrap().to_string(), parser.parse(tokens).unwrap_err().to_string());
+
+    // Tokens are only consumed until the unexpected token and the ones needed to suggest repairs.
+    assert_eq!(consumed_tokens, 9);
+
+    let error = parser.events(parser.token_stream("1 + 2 @")).last().unwrap();
+    assert!(matches!(error, Event::Error { error: ParsingError::UnknownToken { .. } }));
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_parsing_with_sessions_calculator_grammar() {
+    let grammar = Grammar::parse(common::grammars::CALCULATOR).unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let input = "1 + 2 * (3 - 4)";
+    let tokens = parser.tokenize(input).unwrap();
+
+    let mut session = parser.session();
+    assert_eq!(
+        session.expected_tokens().as_slice(),
+        [ConstantToken::from("(").into(), RegexToken::from("f").into()],
+    );
+    for (token, slice) in tokens.iter().cloned() {
+        let status = session.feed(token.clone(), slice).unwrap();
+        if *token == Token::Eof {
+            assert_eq!(status, Status::Accepted);
+        } else {
+            assert_eq!(status, Status::Shifted);
+        }
+    }
+    assert!(session.is_accepted());
+    assert_eq!(
+        session.finish().unwrap().to_string(),
+        parser.parse(tokens.clone()).unwrap().to_string()
+    );
+
+    // End of input is assumed to be right after the last token if it's not fed.
+    let mut session = parser.session();
+    for (token, slice) in tokens.iter().filter(|(token, _)| **token != Token::Eof).cloned() {
+        session.feed(token, slice).unwrap();
+    }
+    assert!(!session.is_accepted());
+    assert_eq!(session.finish().unwrap().to_string(), parser.parse(tokens).unwrap().to_string());
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn continuing_after_rejected_tokens_with_sessions_calculator_grammar() {
+    let grammar = Grammar::parse(common::grammars::CALCULATOR).unwrap();
+    let parser = Parser::lalr(grammar).unwrap();
+
+    let tokens = parser.tokenize("1 + ) 2").unwrap();
+
+    let mut session = parser.session();
+    session.feed(tokens[0].0.clone(), tokens[0].1).unwrap();
+    session.feed(tokens[1].0.clone(), tokens[1].1).unwrap();
+
+    let expected_tokens = session.expected_tokens();
+    let error = session.feed(tokens[2].0.clone(), tokens[2].1).unwrap_err();
+    assert_eq!(error.to_string(), "unexpected token ) at 1:5 (expected one of '(', %f)",);
+
+    // Rejected tokens don't change the session.
+    assert_eq!(session.expected_tokens(), expected_tokens);
+    session.feed(tokens[3].0.clone(), tokens[3].1).unwrap();
+    assert_eq!(
+        session.finish().unwrap().to_string(),
+        parser.parse(parser.tokenize("1 + 2").unwrap()).unwrap().to_string()
+    );
+
+    let mut session = parser.session();
+    session.feed(tokens[0].0.clone(), tokens[0].1).unwrap();
+    session.feed(tokens[1].0.clone(), tokens[1].1).unwrap();
+    assert_eq!(
+        session.finish().unwrap_err().to_string(),
+        "unexpected end of input at 1:4 (expected one of '(', %f), try to insert %f at 1:4"
+    );
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_completing_input_prefixes_json_grammar() {
+    let grammar = Grammar::parse(common::grammars::JSON).unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let completions = parser.complete(r#"{ "a": [1, "#).unwrap();
+    assert_eq!(
+        completions.iter().map(|completion| completion.to_string()).collect::<Vec<_>>(),
+        ["'null'", "'true'", "'false'", "%f", "%s", "'['", "'{'"],
+    );
+    assert_eq!(
+        completions.iter().map(|completion| completion.text()).collect::<Vec<_>>(),
+        ["null", "true", "false", "f", "s", "[", "{"],
+    );
+
+    let completions = parser.complete(r#"{ "a": [1, 2"#).unwrap();
+    assert_eq!(
+        completions,
+        [
+            Completion::Constant(ConstantToken::from("]")),
+            Completion::Constant(ConstantToken::from(","))
+        ],
+    );
+
+    // Completions of a complete input don't include the end of input.
+    assert!(parser.complete("[]").unwrap().is_empty());
+
+    let error = parser.complete("[1 2").unwrap_err();
+    assert!(matches!(error, ParsingError::UnexpectedToken { .. }));
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn completing_only_tokens_that_are_shifted_after_pending_reductions() {
+    let grammar = Grammar::parse("S -> A 'a' | 'b' A 'c'\nA -> 'x'").unwrap();
+    let parser = Parser::slr(grammar).unwrap();
+
+    // SLR(1) parsers reduce `A -> 'x'` on every token in the follow set of `A`,
+    // but only `'a'` can follow `A` if it's at the start of the input.
+    let tokens = parser.tokenize("x").unwrap();
+    let state = match parser.action_table()[0][&*tokens[0].0].iter().next().unwrap() {
+        Action::Shift { next_state } => *next_state,
+        _ => unreachable!(),
+    };
+    assert_eq!(
+        parser.action_table()[state].keys().cloned().collect::<Vec<_>>(),
+        [ConstantToken::from("a").into(), ConstantToken::from("c").into()],
+    );
+
+    assert_eq!(parser.complete("x").unwrap(), [Completion::Constant(ConstantToken::from("a"))]);
+    assert_eq!(parser.complete("b x").unwrap(), [Completion::Constant(ConstantToken::from("c"))]);
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_reparsing_after_edits_json_grammar() {
+    let input = "{\n  \"a\": [1, 2, 3],\n  \"b\": { \"c\": null }\n}";
+    let edits = [
+        // Replace a value in the middle of a line.
+        TextEdit::new(13..14, "true"),
+        // Append digits to a number.
+        TextEdit::new(11..11, "0"),
+        // Insert an element at the start of an array.
+        TextEdit::new(10..10, "[], "),
+        // Insert a member across lines.
+        TextEdit::new(1..1, "\n  \"x\": \"y\","),
+        // Remove a member and the line it's on.
+        TextEdit::new(18..40, ""),
+        // Replace the whole input.
+        TextEdit::new(0..input.len(), "[]"),
+    ];
+
+    for grammar in [common::grammars::JSON, common::grammars::JSON_EBNF] {
+        let grammar = Grammar::parse(grammar).unwrap();
+        let parser = Parser::lalr(grammar).unwrap();
+
+        let tokens = parser.tokenize(input).unwrap();
+        let tree = parser.parse(tokens.clone()).unwrap();
+
+        for edit in edits.iter() {
+            let edited_input = edit.apply(input);
+            let (new_tokens, new_tree) =
+                parser.reparse(tree.clone(), tokens.clone(), edit, &edited_input).unwrap();
+
+            let expected_tokens = parser.tokenize(&edited_input).unwrap();
+            let expected_tree = parser.parse(expected_tokens.clone()).unwrap();
+            assert_eq!(format!("{:?}", new_tokens), format!("{:?}", expected_tokens));
+            assert_eq!(format!("{:?}", new_tree), format!("{:?}", expected_tree));
+
+            // Reparsing can continue from the result of the previous reparse.
+            let next_edit = TextEdit::new(0..0, " ");
+            let next_input = next_edit.apply(&edited_input);
+            let (_, next_tree) =
+                parser.reparse(new_tree, new_tokens, &next_edit, &next_input).unwrap();
+            assert_eq!(
+                format!("{:?}", next_tree),
+                format!("{:?}", parser.parse(parser.tokenize(&next_input).unwrap()).unwrap()),
+            );
+        }
+    }
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_reparsing_after_edits_calculator_grammars() {
+    let input = "1 + 2 + 3 * (4 - 5) ^ 6";
+    let edits = [
+        // Changing the operator after a subtree changes how the subtree is built.
+        TextEdit::new(6..7, "*"),
+        TextEdit::new(10..11, "+"),
+        TextEdit::new(20..21, "-"),
+        // Edits at the start and at the end of the input.
+        TextEdit::new(0..1, "(7 ^ 8)"),
+        TextEdit::new(23..23, " - -9"),
+    ];
+
+    for grammar in [common::grammars::CALCULATOR, common::grammars::CALCULATOR_PRECEDENCE] {
+        let grammar = Grammar::parse(grammar).unwrap();
+        for parser in [Parser::lr(grammar.clone()).unwrap(), Parser::lalr(grammar).unwrap()] {
+            let tokens = parser.tokenize(input).unwrap();
+            let tree = parser.parse(tokens.clone()).unwrap();
+
+            for edit in edits.iter() {
+                let edited_input = edit.apply(input);
+                let expected = match parser.tokenize(&edited_input) {
+                    Ok(tokens) => parser.parse(tokens.clone()).map(|tree| (tokens, tree)),
+                    Err(error) => Err(error),
+                };
+                let result = parser.reparse(tree.clone(), tokens.clone(), edit, &edited_input);
+                assert_eq!(format!("{:?}", result), format!("{:?}", expected));
+            }
+        }
+    }
+
+    // Errors are the same as parsing from scratch.
+    let grammar = Grammar::parse(common::grammars::CALCULATOR).unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let tokens = parser.tokenize(input).unwrap();
+    let tree = parser.parse(tokens.clone()).unwrap();
+
+    let edit = TextEdit::new(4..5, "");
+    let edited_input = edit.apply(input);
+    let error = parser.reparse(tree, tokens, &edit, &edited_input).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        parser.parse(parser.tokenize(&edited_input).unwrap()).unwrap_err().to_string(),
+    );
+}
+
+#[test]
+#[cfg_attr(target_family = "wasm", wasm_bindgen_test)]
+fn correctly_reparsing_after_edits_that_change_tokens_before_them() {
+    let grammar = Grammar::parse(
+        r#"
+S -> T+
+
+T -> %n | %id | '+'
+
+%n -> /[0-9]+(e[+-][0-9]+)?/
+%id -> /[a-z]+/
+        "#,
+    )
+    .unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let input = "1e+";
+    let tokens = parser.tokenize(input).unwrap();
+    let tree = parser.parse(tokens.clone()).unwrap();
+    assert_eq!(tokens.len(), 4);
+
+    // Scanner looks ahead of `1` until the end of the input, so `1e+5` is a single number.
+    let edit = TextEdit::new(3..3, "5");
+    let edited_input = edit.apply(input);
+    let (new_tokens, new_tree) = parser.reparse(tree, tokens, &edit, &edited_input).unwrap();
+
+    let expected_tokens = parser.tokenize(&edited_input).unwrap();
+    let expected_tree = parser.parse(expected_tokens.clone()).unwrap();
+    assert_eq!(expected_tokens.len(), 2);
+    assert_eq!(format!("{:?}", new_tokens), format!("{:?}", expected_tokens));
+    assert_eq!(format!("{:?}", new_tree), format!("{:?}", expected_tree));
+
+    let grammar = Grammar::parse(
+        r#"
+S -> T+
+
+T -> %id | '/' | '*'
+
+%id -> /[a-z]+/
+
+%ignore -> /\/\*([^*]|\*+[^*\/])*\*+\//
+        "#,
+    )
+    .unwrap();
+    let parser = Parser::lr(grammar).unwrap();
+
+    let input = "a /* b *";
+    let tokens = parser.tokenize(input).unwrap();
+    let tree = parser.parse(tokens.clone()).unwrap();
+    assert_eq!(tokens.len(), 6);
+
+    // Scanner looks ahead of `/` without a bound, since it may start a comment of any length.
+    let edit = TextEdit::new(8..8, "/");
+    let edited_input = edit.apply(input);
+    let (new_tokens, new_tree) = parser.reparse(tree, tokens, &edit, &edited_input).unwrap();
+
+    let expected_tokens = parser.tokenize(&edited_input).unwrap();
+    let expected_tree = parser.parse(expected_tokens.clone()).unwrap();
+    assert_eq!(expected_tokens.len(), 2);
+    assert_eq!(format!("{:?}", new_tokens), format!("{:?}", expected_tokens));
+    assert_eq!(format!("{:?}", new_tree), format!("{:?}", expected_tree));
+}